@@ -0,0 +1,423 @@
+// Runner for all the puzzles.
+//
+// Dispatches to the solution of any day and part through the aoc2024 library
+// instead of having a binary per puzzle.
+//
+//    aoc run <day> <part> [--input FILE | --name NAME] [--format FORMAT]
+//            [--explain]
+//    aoc bench [day] [--runs N] [--json]
+//    aoc verify [day]
+//    aoc new <day>
+//
+// The run command reads the puzzle input from FILE if given, from stdin if FILE
+// is -, or the input of the day with the given NAME from the input store, see
+// aoc2024::aoc::inputs. Without either the input is read from stdin when it is
+// piped and not empty, and the main input of the day is read from the input
+// store otherwise, so running under cron or with </dev/null still uses the
+// store. The outcome is printed as text, json or tsv, see
+// aoc2024::aoc::output. With --explain the notes explaining the answer are
+// printed to stderr first, in the same format, see aoc2024::aoc::explain. The
+// bench command times the parse and solve phases of both parts of the day, or
+// of every day, on the main inputs in the input store. The verify command runs
+// both parts of the day, or of every day, on the main inputs and compares the
+// answers to the answers stored next to the inputs, see aoc2024::verify. The
+// new command creates the files of a new day in the repository in the working
+// directory and registers it, see aoc2024::scaffold.
+use std::env;
+use std::io;
+use std::io::{BufRead, IsTerminal};
+use std::path::Path;
+use std::process::ExitCode;
+use aoc2024::aoc::{AocError, Outcome};
+use aoc2024::aoc::inputs::{read_path, InputStore};
+use aoc2024::aoc::output::Format;
+
+/// Usage text printed when given invalid arguments.
+const USAGE: &str = "\
+Usage: aoc run <day> <part> [--input FILE | --name NAME] [--format FORMAT]
+               [--explain]
+       aoc bench [day] [--runs N] [--json]
+       aoc verify [day]
+       aoc new <day>";
+
+/// Number of runs of each puzzle when benchmarking, unless given.
+const DEFAULT_RUNS: u32 = 5;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match execute(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// A command given on the command line.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+
+    /// Run the solution of a single day and part.
+    Run {
+
+        /// The day to run.
+        day: u8,
+
+        /// The part to run.
+        part: u8,
+
+        /// Where to read the input from.
+        input: Input,
+
+        /// Format to print the outcome in, chosen from the environment if
+        /// not given.
+        format: Option<String>,
+
+        /// Whether to print the notes explaining the answer to stderr.
+        explain: bool
+
+    },
+
+    /// Benchmark a single day or all days.
+    Bench {
+
+        /// The day to benchmark, all days are benchmarked if none is given.
+        day: Option<u8>,
+
+        /// Number of times to run each part of a puzzle.
+        runs: u32,
+
+        /// Whether to print the timings as JSON instead of a table.
+        json: bool
+
+    },
+
+    /// Verify the answers of a single day or all days.
+    Verify {
+
+        /// The day to verify, all days are verified if none is given.
+        day: Option<u8>
+
+    },
+
+    /// Create the files of a new day.
+    New {
+
+        /// The day to create.
+        day: u8
+
+    }
+
+}
+
+/// Where the run command reads the puzzle input from.
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+
+    /// Read stdin if it is piped and not empty and the main input of the day
+    /// from the input store otherwise.
+    Default,
+
+    /// Read the file at the path.
+    File(String),
+
+    /// Read stdin, given as the file -.
+    Stdin,
+
+    /// Read the input of the day with the name from the input store.
+    Named(String)
+
+}
+
+/// Execute the given command.
+fn execute(command: Command) -> Result<(), AocError> {
+    match command {
+        Command::Run { day, part, input, format, explain } => {
+            let format = Format::choose(format.as_deref())?;
+            let store = InputStore::from_env();
+            let run = |reader: &mut dyn BufRead| {
+                return run_puzzle(day, part, format, explain, reader);
+            };
+            let outcome = match input {
+                Input::Default if !io::stdin().is_terminal()
+                    && !io::stdin().lock().fill_buf()?.is_empty() => {
+
+                    run(&mut io::stdin().lock())?
+                },
+                Input::Default => run(&mut store.read(day, None)?.as_slice())?,
+                Input::Stdin => run(&mut io::stdin().lock())?,
+                Input::File(path) => {
+                    run(&mut read_path(Path::new(&path))?.as_slice())?
+                },
+                Input::Named(name) => {
+                    run(&mut store.read(day, Some(&name))?.as_slice())?
+                }
+            };
+            println!("{}", format.render(day, part, &outcome));
+        },
+        Command::Bench { day, runs, json } => {
+            let benchmarks = aoc2024::bench::bench(day, runs)?;
+            if json {
+                println!("{}", aoc2024::bench::to_json(&benchmarks));
+            } else {
+                print!("{}", aoc2024::bench::to_table(&benchmarks));
+            }
+        },
+        Command::Verify { day } => {
+            let checks = aoc2024::verify::verify(day)?;
+            print!("{}", aoc2024::verify::to_matrix(&checks));
+            let failed = checks.iter().filter(|c| c.failed()).count();
+            if failed > 0 {
+                return Err(AocError::UnexpectedError(format!(
+                    "{} of {} parts failed verification.", failed,
+                    checks.len())));
+            }
+        },
+        Command::New { day } => {
+            for path in aoc2024::scaffold::new_day(Path::new("."), day)? {
+                println!("Wrote {}", path.display());
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Parse the command line arguments, not including the program name.
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|a| a.as_str()) {
+        Some("run") => parse_run_args(&args[1..]),
+        Some("bench") => parse_bench_args(&args[1..]),
+        Some("verify") => parse_verify_args(&args[1..]),
+        Some("new") => parse_new_args(&args[1..]),
+        Some(command) => Err(format!("Unknown command '{}'.", command)),
+        None => Err("Missing command.".to_string())
+    }
+}
+
+/// Run the day and part on the input from the reader, printing the notes
+/// explaining the answer to stderr in the format if asked to.
+fn run_puzzle(day: u8, part: u8, format: Format, explain: bool,
+    reader: &mut dyn BufRead) -> Result<Outcome, AocError> {
+
+    if !explain {
+        return aoc2024::aoc::run_timed(day, part, reader);
+    }
+
+    let (outcome, trace) = aoc2024::aoc::run_explained(day, part, reader)?;
+    for note in trace.notes() {
+        eprintln!("{}", format.render_note(day, part, note));
+    }
+    return Ok(outcome);
+}
+
+/// Parse the arguments of the run command.
+fn parse_run_args(args: &[String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut input = Input::Default;
+    let mut format = None;
+    let mut explain = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if input != Input::Default && (arg == "--input" || arg == "--name") {
+            return Err("Expected only one of --input and --name.".to_string());
+        } else if arg == "--input" {
+            let path = iter.next()
+                .ok_or("Expected a file after --input.".to_string())?;
+            input = match path.as_str() {
+                "-" => Input::Stdin,
+                _ => Input::File(path.clone())
+            };
+        } else if arg == "--name" {
+            let name = iter.next()
+                .ok_or("Expected a name after --name.".to_string())?;
+            input = Input::Named(name.clone());
+        } else if arg == "--format" {
+            let name = iter.next()
+                .ok_or("Expected a format after --format.".to_string())?;
+            format = Some(name.clone());
+        } else if arg == "--explain" {
+            explain = true;
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    match positional.as_slice() {
+        [day, part] => Ok(Command::Run {
+            day: parse_number(day, "day")?,
+            part: parse_number(part, "part")?,
+            input: input,
+            format: format,
+            explain: explain
+        }),
+        _ => Err("Expected a day and a part.".to_string())
+    }
+}
+
+/// Parse the arguments of the bench command.
+fn parse_bench_args(args: &[String]) -> Result<Command, String> {
+    let mut positional = Vec::new();
+    let mut runs = DEFAULT_RUNS;
+    let mut json = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--runs" {
+            let n = iter.next()
+                .ok_or("Expected a number after --runs.".to_string())?;
+            runs = n.parse().ok()
+                .filter(|&runs| runs > 0)
+                .ok_or(format!("Invalid number of runs '{}'.", n))?;
+        } else if arg == "--json" {
+            json = true;
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    match positional.as_slice() {
+        [] => Ok(Command::Bench { day: None, runs: runs, json: json }),
+        [day] => Ok(Command::Bench {
+            day: Some(parse_number(day, "day")?),
+            runs: runs,
+            json: json
+        }),
+        _ => Err("Expected at most a day.".to_string())
+    }
+}
+
+/// Parse the arguments of the verify command.
+fn parse_verify_args(args: &[String]) -> Result<Command, String> {
+    match args {
+        [] => Ok(Command::Verify { day: None }),
+        [day] => Ok(Command::Verify { day: Some(parse_number(day, "day")?) }),
+        _ => Err("Expected at most a day.".to_string())
+    }
+}
+
+/// Parse the arguments of the new command.
+fn parse_new_args(args: &[String]) -> Result<Command, String> {
+    match args {
+        [day] => Ok(Command::New { day: parse_number(day, "day")? }),
+        _ => Err("Expected a day.".to_string())
+    }
+}
+
+/// Parse a day or part number.
+fn parse_number(arg: &str, name: &str) -> Result<u8, String> {
+    arg.parse().map_err(|_| format!("Invalid {} '{}'.", name, arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert a list of string slices to owned arguments.
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    /// Test that parsing a run command works.
+    #[test]
+    fn test_parse_args_run() {
+        assert_eq!(parse_args(&args(&["run", "9", "2"])), Ok(Command::Run {
+            day: 9,
+            part: 2,
+            input: Input::Default,
+            format: None,
+            explain: false
+        }));
+        assert_eq!(
+            parse_args(&args(&["run", "--input", "in.txt", "12", "1"])),
+            Ok(Command::Run {
+                day: 12,
+                part: 1,
+                input: Input::File("in.txt".to_string()),
+                format: None,
+                explain: false
+            }));
+        assert_eq!(parse_args(&args(&["run", "1", "2", "--input", "-"])),
+            Ok(Command::Run {
+                day: 1,
+                part: 2,
+                input: Input::Stdin,
+                format: None,
+                explain: false
+            }));
+        assert_eq!(
+            parse_args(&args(&["run", "12", "1", "--name", "alt", "--format",
+                "json", "--explain"])),
+            Ok(Command::Run {
+                day: 12,
+                part: 1,
+                input: Input::Named("alt".to_string()),
+                format: Some("json".to_string()),
+                explain: true
+            }));
+    }
+
+    /// Test that parsing a bench command works.
+    #[test]
+    fn test_parse_args_bench() {
+        assert_eq!(parse_args(&args(&["bench"])), Ok(Command::Bench {
+            day: None,
+            runs: DEFAULT_RUNS,
+            json: false
+        }));
+        assert_eq!(
+            parse_args(&args(&["bench", "6", "--runs", "10", "--json"])),
+            Ok(Command::Bench {
+                day: Some(6),
+                runs: 10,
+                json: true
+            }));
+    }
+
+    /// Test that parsing a verify command works.
+    #[test]
+    fn test_parse_args_verify() {
+        assert_eq!(parse_args(&args(&["verify"])),
+            Ok(Command::Verify { day: None }));
+        assert_eq!(parse_args(&args(&["verify", "7"])),
+            Ok(Command::Verify { day: Some(7) }));
+    }
+
+    /// Test that parsing a new command works.
+    #[test]
+    fn test_parse_args_new() {
+        assert_eq!(parse_args(&args(&["new", "13"])),
+            Ok(Command::New { day: 13 }));
+    }
+
+    /// Test that invalid arguments are rejected.
+    #[test]
+    fn test_parse_args_invalid() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["walk", "1", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "x", "1"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--input"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--name"])).is_err());
+        assert!(parse_args(&args(&["run", "1", "1", "--format"])).is_err());
+        assert!(parse_args(
+            &args(&["run", "1", "1", "--input", "a", "--name", "b"])).is_err());
+        assert!(parse_args(&args(&["bench", "1", "2"])).is_err());
+        assert!(parse_args(&args(&["bench", "--runs", "0"])).is_err());
+        assert!(parse_args(&args(&["bench", "--runs"])).is_err());
+        assert!(parse_args(&args(&["verify", "1", "2"])).is_err());
+        assert!(parse_args(&args(&["verify", "--json"])).is_err());
+        assert!(parse_args(&args(&["new"])).is_err());
+        assert!(parse_args(&args(&["new", "13", "14"])).is_err());
+    }
+
+}
@@ -1,5 +1,24 @@
-use std::fmt::Debug;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::io::BufRead;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 use nom::Err;
+use crate::aoc::explain::Trace;
+use crate::nom_helpers::InputError;
+use crate::{
+    day01, day02, day03, day04, day05, day06,
+    day07, day08, day09, day10, day11, day12
+};
+
+pub mod answers;
+pub mod examples;
+pub mod explain;
+pub mod inputs;
+pub mod output;
 
 /// Errors that happens in AOC code.
 ///
@@ -8,7 +27,7 @@ use nom::Err;
 pub enum AocError {
 
     /// We could not parse the input given.
-    ParseInputError(String),
+    ParseInputError(ParseError),
 
     /// Some IO operation failed.
     IoError(String),
@@ -18,13 +37,71 @@ pub enum AocError {
 
 }
 
-impl<'a, I> From<Err<I>> for AocError
-where
-    I: PartialEq + Debug,
-{
-    fn from(error: Err<I>) -> Self {
-        AocError::ParseInputError(format!("Parsing error: {:?}", error))
+impl AocError {
+
+    /// Move the position of a parse error the given number of lines down.
+    ///
+    /// Other errors are returned unchanged.
+    pub fn offset_lines(self, lines: usize) -> AocError {
+        match self {
+            AocError::ParseInputError(error) => {
+                AocError::ParseInputError(error.offset_lines(lines))
+            },
+            other => other
+        }
+    }
+
+}
+
+impl Display for AocError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::ParseInputError(error) => write!(f, "{}", error),
+            AocError::IoError(message) => write!(f, "{}", message),
+            AocError::UnexpectedError(message) => write!(f, "{}", message)
+        }
+    }
+
+}
+
+impl Error for AocError {
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::ParseInputError(error) => Some(error),
+            _ => None
+        }
+    }
+
+}
+
+/// Convert a nom error to an AocError.
+///
+/// The original input is not known here so the error will not have a position.
+/// Use nom_helpers::parse_all to get errors with positions.
+impl From<Err<InputError<'_>>> for AocError {
+
+    fn from(error: Err<InputError<'_>>) -> Self {
+        match error {
+            Err::Error(e) | Err::Failure(e) => {
+                AocError::ParseInputError(ParseError::new(e.describe()))
+            },
+            Err::Incomplete(_) => {
+                AocError::ParseInputError(
+                    ParseError::new("Unexpected end of input."))
+            }
+        }
+    }
+
+}
+
+impl From<ParseError> for AocError {
+
+    fn from(error: ParseError) -> Self {
+        AocError::ParseInputError(error)
     }
+
 }
 
 impl From<std::io::Error> for AocError {
@@ -35,3 +112,477 @@ impl From<std::io::Error> for AocError {
 
 }
 
+/// An error in the puzzle input found while parsing it.
+///
+/// Carries the position of the error in the input when it is known, in which
+/// case it is displayed with the offending line and a caret pointing at the
+/// column, like:
+///
+///    Parse error on line 2, column 4: expected ',', found ';'
+///    2 | 1,2;3
+///      |    ^
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+
+    /// Description of what went wrong.
+    pub message: String,
+
+    /// What the parser expected to find, if known.
+    pub expected: Option<String>,
+
+    /// Where in the input the error occurred, if known.
+    pub location: Option<Location>
+
+}
+
+/// A position in the puzzle input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Location {
+
+    /// Line number starting from 1.
+    pub line: usize,
+
+    /// Column number starting from 1, counted in characters.
+    pub column: usize,
+
+    /// The line of input containing the position, which may be cut down to
+    /// the part around the position on long lines.
+    pub snippet: String,
+
+    /// Column number in the snippet starting from 1, counted in characters.
+    /// The same as the column unless the snippet is cut down.
+    pub snippet_column: usize
+
+}
+
+impl ParseError {
+
+    /// Construct a parse error without a known position.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        return ParseError {
+            message: message.into(),
+            expected: None,
+            location: None
+        };
+    }
+
+    /// Construct a parse error for something expected at the given byte offset
+    /// into the input.
+    ///
+    /// The message will describe both what was expected and what was found.
+    pub fn expected_at(input: &str, offset: usize, expected: impl Into<String>)
+        -> ParseError {
+
+        let expected = expected.into();
+        let found = match input.get(offset..).and_then(|rest| rest.chars().next()) {
+            Some(c) => format!("{:?}", c),
+            None => "end of input".to_string()
+        };
+        return ParseError {
+            message: format!("expected {}, found {}", expected, found),
+            expected: Some(expected),
+            location: Some(Location::find(input, offset))
+        };
+    }
+
+    /// Move the error the given number of lines down.
+    ///
+    /// Useful when a single line is parsed at a time and the error should
+    /// point out the line in the full input.
+    pub fn offset_lines(mut self, lines: usize) -> ParseError {
+        if let Some(location) = self.location.as_mut() {
+            location.line += lines;
+        }
+        return self;
+    }
+
+}
+
+impl Display for ParseError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            None => write!(f, "Parse error: {}", self.message),
+            Some(location) => {
+                let line = location.line.to_string();
+                let gutter = " ".repeat(line.len());
+                writeln!(f, "Parse error on line {}, column {}: {}",
+                    location.line, location.column, self.message)?;
+                writeln!(f, "{} | {}", line, location.snippet)?;
+                let caret = " ".repeat(location.snippet_column - 1);
+                write!(f, "{} | {}^", gutter, caret)
+            }
+        }
+    }
+
+}
+
+impl Error for ParseError {}
+
+impl Location {
+
+    /// Find the location of the given byte offset into the input.
+    ///
+    /// Offsets past the end of the input point just after the last character.
+    pub fn find(input: &str, offset: usize) -> Location {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..].find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+
+        let column = before[line_start..].chars().count() + 1;
+        let snippet = input[line_start..line_end].trim_end_matches('\r');
+        return Location {
+            line: before.matches('\n').count() + 1,
+            column: column,
+            snippet: snippet.to_string(),
+            snippet_column: column
+        };
+    }
+
+}
+
+/// A solution to a single part of a puzzle.
+///
+/// Solving happens in two phases: the puzzle input is parsed into a typed model
+/// which the answer is then computed from. The answer can then be explained on
+/// request.
+pub trait Solution {
+
+    /// Typed model of the puzzle input.
+    type Input;
+
+    /// The answer to the puzzle.
+    type Answer: Display;
+
+    /// Parse the puzzle input read from the given reader.
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError>;
+
+    /// Compute the answer to the puzzle from the parsed input.
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError>;
+
+    /// Explain how the answer is computed from the parsed input by noting
+    /// what is found for each record of the input.
+    ///
+    /// Solutions that do not explain their answer note just that, so the
+    /// explanation is never silently empty.
+    fn explain(_input: &Self::Input, trace: &mut Trace)
+        -> Result<(), AocError> {
+
+        trace.note("answer", "not explained",
+            "the solution notes no reasoning per record");
+        return Ok(());
+    }
+
+}
+
+/// The result of solving a puzzle.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Outcome {
+
+    /// The answer formatted for printing.
+    pub answer: String,
+
+    /// Time spent parsing the input.
+    pub parse_time: Duration,
+
+    /// Time spent solving the puzzle given the parsed input.
+    pub solve_time: Duration
+
+}
+
+/// Parse the input from the reader and solve it using the solution S, noting
+/// the explanation of the answer in the trace if one is given.
+///
+/// Returns the answer formatted for printing together with the time spent in
+/// each of the two phases. The time spent explaining is not counted.
+pub fn run_solution<S: Solution>(reader: &mut dyn BufRead,
+    trace: Option<&mut Trace>) -> Result<Outcome, AocError> {
+
+    let parse_start = Instant::now();
+    let input = S::parse(reader)?;
+    let parse_time = parse_start.elapsed();
+
+    let solve_start = Instant::now();
+    let answer = S::solve(&input)?;
+    let solve_time = solve_start.elapsed();
+
+    if let Some(trace) = trace {
+        S::explain(&input, trace)?;
+    }
+
+    return Ok(Outcome {
+        answer: answer.to_string(),
+        parse_time: parse_time,
+        solve_time: solve_time
+    });
+}
+
+/// Entry point of a single part of a puzzle.
+///
+/// Reads the puzzle input from the given reader and returns the outcome,
+/// noting the explanation of the answer in the trace if one is given.
+pub type Entry = fn(&mut dyn BufRead, Option<&mut Trace>)
+    -> Result<Outcome, AocError>;
+
+/// A part of a puzzle that has a solution.
+pub struct Puzzle {
+
+    /// The day of the puzzle.
+    pub day: u8,
+
+    /// The part of the puzzle, either 1 or 2.
+    pub part: u8,
+
+    /// Entry point solving the puzzle.
+    pub entry: Entry
+
+}
+
+/// All puzzles that have a solution, ordered by day and part.
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle { day: 1, part: 1, entry: run_solution::<day01::part1::Solver> },
+    Puzzle { day: 1, part: 2, entry: run_solution::<day01::part2::Solver> },
+    Puzzle { day: 2, part: 1, entry: run_solution::<day02::part1::Solver> },
+    Puzzle { day: 2, part: 2, entry: run_solution::<day02::part2::Solver> },
+    Puzzle { day: 3, part: 1, entry: run_solution::<day03::part1::Solver> },
+    Puzzle { day: 3, part: 2, entry: run_solution::<day03::part2::Solver> },
+    Puzzle { day: 4, part: 1, entry: run_solution::<day04::part1::Solver> },
+    Puzzle { day: 4, part: 2, entry: run_solution::<day04::part2::Solver> },
+    Puzzle { day: 5, part: 1, entry: run_solution::<day05::part1::Solver> },
+    Puzzle { day: 5, part: 2, entry: run_solution::<day05::part2::Solver> },
+    Puzzle { day: 6, part: 1, entry: run_solution::<day06::part1::Solver> },
+    Puzzle { day: 6, part: 2, entry: run_solution::<day06::part2::Solver> },
+    Puzzle { day: 7, part: 1, entry: run_solution::<day07::part1::Solver> },
+    Puzzle { day: 7, part: 2, entry: run_solution::<day07::part2::Solver> },
+    Puzzle { day: 8, part: 1, entry: run_solution::<day08::part1::Solver> },
+    Puzzle { day: 8, part: 2, entry: run_solution::<day08::part2::Solver> },
+    Puzzle { day: 9, part: 1, entry: run_solution::<day09::part1::Solver> },
+    Puzzle { day: 9, part: 2, entry: run_solution::<day09::part2::Solver> },
+    Puzzle { day: 10, part: 1, entry: run_solution::<day10::part1::Solver> },
+    Puzzle { day: 10, part: 2, entry: run_solution::<day10::part2::Solver> },
+    Puzzle { day: 11, part: 1, entry: run_solution::<day11::part1::Solver> },
+    Puzzle { day: 11, part: 2, entry: run_solution::<day11::part2::Solver> },
+    Puzzle { day: 12, part: 1, entry: run_solution::<day12::part1::Solver> },
+    Puzzle { day: 12, part: 2, entry: run_solution::<day12::part2::Solver> },
+];
+
+/// Find the puzzle for the given day and part.
+pub fn find_puzzle(day: u8, part: u8) -> Option<&'static Puzzle> {
+    return PUZZLES.iter().find(|p| p.day == day && p.part == part);
+}
+
+/// Solve the given day and part of the puzzle on the input from the reader.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if there is no solution for the day and
+/// part, otherwise any error produced by the solution itself.
+pub fn run(day: u8, part: u8, reader: &mut dyn BufRead)
+    -> Result<String, AocError> {
+
+    return Ok(run_timed(day, part, reader)?.answer);
+}
+
+/// Solve the given day and part of the puzzle on the input from the reader,
+/// timing the parse and solve phases.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if there is no solution for the day and
+/// part given.
+pub fn run_timed(day: u8, part: u8, reader: &mut dyn BufRead)
+    -> Result<Outcome, AocError> {
+
+    let puzzle = find_puzzle(day, part)
+        .ok_or(AocError::UnexpectedError(
+                format!("No solution for day {} part {}.", day, part)))?;
+    return (puzzle.entry)(reader, None);
+}
+
+/// Solve the given day and part of the puzzle on the input from the reader,
+/// timing the parse and solve phases and explaining the answer.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if there is no solution for the day and
+/// part given.
+pub fn run_explained(day: u8, part: u8, reader: &mut dyn BufRead)
+    -> Result<(Outcome, Trace), AocError> {
+
+    let puzzle = find_puzzle(day, part)
+        .ok_or(AocError::UnexpectedError(
+                format!("No solution for day {} part {}.", day, part)))?;
+    let mut trace = Trace::new();
+    let outcome = (puzzle.entry)(reader, Some(&mut trace))?;
+    return Ok((outcome, trace));
+}
+
+/// Solve the given day and part on the input from stdin and print the outcome
+/// to stdout.
+///
+/// The outcome is printed in the format chosen by the --format argument or the
+/// environment, see output::Format. With --explain the explanation of the
+/// answer is printed to stderr first, in the same format. Errors are printed
+/// to stderr and reported through the exit code, which makes this suitable as
+/// the body of a main function.
+pub fn main(day: u8, part: u8) -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdin = io::stdin();
+    let mut stdin_lock = stdin.lock();
+    let printed = output::parse_flags(&args).and_then(|flags| {
+        let format = output::Format::choose(flags.format)?;
+        if !flags.explain {
+            let outcome = run_timed(day, part, &mut stdin_lock)?;
+            return Ok(format.render(day, part, &outcome));
+        }
+
+        let (outcome, trace) = run_explained(day, part, &mut stdin_lock)?;
+        for note in trace.notes() {
+            eprintln!("{}", format.render_note(day, part, note));
+        }
+        return Ok(format.render(day, part, &outcome));
+    });
+    match printed {
+        Ok(printed) => {
+            println!("{}", printed);
+            return ExitCode::SUCCESS;
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that locations are found from byte offsets.
+    #[test]
+    fn test_location_find() {
+        let input = "1|2\n3|4\n\n1,2;3\n";
+        assert_eq!(Location::find(input, 0), Location {
+            line: 1,
+            column: 1,
+            snippet: "1|2".to_string(),
+            snippet_column: 1
+        });
+        assert_eq!(Location::find(input, 12), Location {
+            line: 4,
+            column: 4,
+            snippet: "1,2;3".to_string(),
+            snippet_column: 4
+        });
+        assert_eq!(Location::find(input, 100), Location {
+            line: 5,
+            column: 1,
+            snippet: "".to_string(),
+            snippet_column: 1
+        });
+    }
+
+    /// Test that parse errors with a location render a caret diagnostic.
+    #[test]
+    fn test_parse_error_display() {
+        let error = ParseError::expected_at("1|2\n1,2;3\n", 7, "','");
+        assert_eq!(error.expected, Some("','".to_string()));
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 4: expected ',', found ';'\n",
+            "2 | 1,2;3\n",
+            "  |    ^"
+        ));
+        assert_eq!(ParseError::new("Too short.").to_string(),
+            "Parse error: Too short.");
+    }
+
+    /// Test that parse errors compose with other errors through the source.
+    #[test]
+    fn test_aoc_error_source() {
+        let error = AocError::from(ParseError::new("Too short."));
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "Parse error: Too short.");
+        assert!(AocError::IoError("Gone.".to_string()).source().is_none());
+    }
+
+    /// Test that a solution can be called directly through the library.
+    #[test]
+    fn test_solution_parse_and_solve() {
+        let input = day09::part2::parse(&mut "2333133121414131402\n".as_bytes())
+            .unwrap();
+        assert_eq!(Ok(2858), day09::part2::solve(&input));
+        assert_eq!(Ok(2858), day09::part2::Solver::solve(&input));
+    }
+
+    /// Test that running a solution formats the answer.
+    #[test]
+    fn test_run_solution() {
+        let input = "2333133121414131402\n";
+        let outcome = run_solution::<day09::part1::Solver>(
+            &mut input.as_bytes(), None).unwrap();
+        assert_eq!("1928".to_string(), outcome.answer);
+    }
+
+    /// Test that solutions that do not explain their answer say so.
+    #[test]
+    fn test_explain_default() {
+        struct Unexplained;
+
+        impl Solution for Unexplained {
+
+            type Input = ();
+
+            type Answer = u8;
+
+            fn parse(_reader: &mut dyn BufRead) -> Result<(), AocError> {
+                return Ok(());
+            }
+
+            fn solve(_input: &()) -> Result<u8, AocError> {
+                return Ok(1);
+            }
+
+        }
+
+        let mut trace = Trace::new();
+        let outcome = run_solution::<Unexplained>(&mut "".as_bytes(),
+            Some(&mut trace)).unwrap();
+        assert_eq!("1".to_string(), outcome.answer);
+        assert_eq!(trace.notes().len(), 1);
+        assert_eq!(trace.notes()[0].verdict, "not explained");
+    }
+
+    /// Test that all puzzles can be found by their day and part.
+    #[test]
+    fn test_find_puzzle() {
+        for puzzle in PUZZLES.iter() {
+            let found = find_puzzle(puzzle.day, puzzle.part).unwrap();
+            assert_eq!((found.day, found.part), (puzzle.day, puzzle.part));
+        }
+        assert!(find_puzzle(13, 1).is_none());
+        assert!(find_puzzle(1, 3).is_none());
+    }
+
+    /// Test that running a puzzle gives the answer of the solution.
+    #[test]
+    fn test_run() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert_eq!(Ok("11".to_string()), run(1, 1, &mut input.as_bytes()));
+        assert_eq!(Ok("31".to_string()), run(1, 2, &mut input.as_bytes()));
+    }
+
+    /// Test that running a puzzle without a solution gives an error.
+    #[test]
+    fn test_run_missing_puzzle() {
+        let error = run(13, 1, &mut "".as_bytes());
+        assert_eq!(Err(AocError::UnexpectedError(
+            "No solution for day 13 part 1.".to_string())), error);
+    }
+
+}
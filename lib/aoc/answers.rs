@@ -0,0 +1,211 @@
+// Known answers of the puzzle inputs.
+//
+// The answers of the inputs in a directory of inputs are stored next to them
+// in answers.toml, a small subset of TOML with a table per input. The tables of
+// named inputs, see aoc::inputs, have the name after the day:
+//
+//    # Answers of the inputs in this directory.
+//    [day01]
+//    part1 = 2086478
+//    part2 = "an answer that is not a number"
+//
+//    [day01.alt]
+//    part1 = 11
+//
+// Integer answers may be written bare, other answers are quoted and can not
+// contain quotes. Comments start with # and run to the end of the line.
+use std::collections::HashMap;
+use std::path::Path;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while1},
+    character::complete::{char, digit1, space0},
+    combinator::{map, opt, recognize, rest},
+    error::context,
+    sequence::{delimited, pair, preceded, tuple}
+};
+use crate::aoc::{AocError, ParseError};
+use crate::aoc::inputs::read_path;
+use crate::nom_helpers::{key_value, parse_all, parse_int, ParseResult};
+
+/// Name of the file with the answers in a directory of inputs.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// An input of a day, named by the day and the name of the input if it is
+/// not the main input.
+type InputKey = (u8, Option<String>);
+
+/// The known answers of the inputs of the days.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Answers {
+
+    /// The answer of each part of each input.
+    answers: HashMap<(InputKey, u8), String>
+
+}
+
+/// A line of the answers file that is not blank or a comment.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+enum Line<'a> {
+
+    /// A table header starting the answers of an input of a day.
+    Input(u8, Option<&'a str>),
+
+    /// The answer of a part of the day of the table.
+    Answer(u8, &'a str)
+
+}
+
+impl Answers {
+
+    /// Parse the answers from the text of an answers file.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError pointing out the line if a line can
+    /// not be parsed, an answer is given before any day or a part is given
+    /// twice.
+    pub fn parse(text: &str) -> Result<Answers, AocError> {
+        let mut answers = HashMap::new();
+        let mut input: Option<InputKey> = None;
+        for (index, line) in text.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let error = |expected: &str| AocError::ParseInputError(
+                ParseError::expected_at(line, 0, expected)
+                    .offset_lines(index));
+            let parsed = parse_all(parse_line, line)
+                .map_err(|error| error.offset_lines(index))?;
+            match parsed {
+                Line::Input(day, name) => {
+                    input = Some((day, name.map(|name| name.to_string())));
+                },
+                Line::Answer(part, answer) => {
+                    let input = input.clone()
+                        .ok_or_else(|| error("a day like [day01] first"))?;
+                    if answers.insert((input, part), answer.to_string())
+                        .is_some() {
+
+                        return Err(error("a part not answered before"));
+                    }
+                }
+            }
+        }
+
+        return Ok(Answers {
+            answers: answers
+        });
+    }
+
+    /// Read the answers stored in a directory of inputs.
+    ///
+    /// A directory without an answers file has no known answers.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.IoError if the file can not be read and
+    /// AocError.ParseInputError if it can not be parsed.
+    pub fn read_dir(dir: &Path) -> Result<Answers, AocError> {
+        let path = dir.join(ANSWERS_FILE);
+        if !path.is_file() {
+            return Ok(Answers::default());
+        }
+
+        let bytes = read_path(&path)?;
+        let text = String::from_utf8(bytes).map_err(|_| AocError::IoError(
+            format!("{} is not valid UTF-8.", path.display())))?;
+        return Answers::parse(&text);
+    }
+
+    /// The known answer of a part of an input of the day, the main input if
+    /// no name is given.
+    pub fn get(&self, day: u8, name: Option<&str>, part: u8) -> Option<&str> {
+        let key = ((day, name.map(|name| name.to_string())), part);
+        return self.answers.get(&key).map(|answer| answer.as_str());
+    }
+
+}
+
+/// Parse a line of the answers file with an optional comment at the end.
+fn parse_line(input: &str) -> ParseResult<'_, Line<'_>> {
+    let header = delimited(
+        tag("[day"),
+        pair(parse_int::<u8>, opt(preceded(char('.'), parse_name))),
+        char(']')
+    );
+    let answer = key_value(
+        preceded(tag("part"), parse_int::<u8>),
+        char('='),
+        context("an integer or a quoted answer", alt((
+            recognize(pair(opt(char('-')), digit1)),
+            delimited(char('"'), take_till(|c| c == '"'), char('"'))
+        )))
+    );
+    let comment = preceded(char('#'), rest);
+
+    delimited(
+        space0,
+        context("a day like [day01] or an answer like part1 = 42", alt((
+            map(header, |(day, name)| Line::Input(day, name)),
+            map(answer, |(part, answer)| Line::Answer(part, answer))
+        ))),
+        tuple((space0, opt(comment)))
+    )(input)
+}
+
+/// Parse the name of a named input, like score-2.
+fn parse_name(input: &str) -> ParseResult<'_, &str> {
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        (input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that answers are parsed with comments and blank lines.
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(concat!(
+            "# Answers.\n",
+            "[day01]\n",
+            "part1 = 11  # The example.\n",
+            "\n",
+            "  [day12]\n",
+            "part2=\"a # b\"\n",
+            "[day12.score-2]\n",
+            "part2 = 2\n"
+        )).unwrap();
+        assert_eq!(answers.get(1, None, 1), Some("11"));
+        assert_eq!(answers.get(12, None, 2), Some("a # b"));
+        assert_eq!(answers.get(12, Some("score-2"), 2), Some("2"));
+        assert_eq!(answers.get(1, None, 2), None);
+        assert_eq!(answers.get(1, Some("score-2"), 1), None);
+        assert_eq!(Answers::parse(""), Ok(Answers::default()));
+    }
+
+    /// Test that invalid answers files are rejected with the line.
+    #[test]
+    fn test_parse_invalid() {
+        let error = Answers::parse("[day01]\npart1 = x\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 9: expected an integer or a quoted ",
+            "answer, found 'x'\n",
+            "2 | part1 = x\n",
+            "  |         ^"
+        ));
+
+        let error = Answers::parse("part1 = 1\n").unwrap_err();
+        assert!(error.to_string()
+            .starts_with("Parse error on line 1, column 1: expected a day"));
+
+        let error = Answers::parse("[day1]\npart1 = 1\npart1 = 2\n")
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Parse error on line 3"));
+        assert!(Answers::parse("[day1] part1 = 1\n").is_err());
+    }
+
+}
@@ -0,0 +1,147 @@
+// Worked examples of the puzzles.
+//
+// The examples from the puzzle texts are stored once as inputs in
+// inputs/examples, named like any other input, with their answers in the
+// answers file of the directory, see aoc::answers:
+//
+//    inputs/examples/12.txt            the example of the day
+//    inputs/examples/12-small.txt      another example named small
+//    inputs/examples/answers.toml      [day12] and [day12.small] answers
+//
+// Every example is run against each part it has an answer for. The tests of a
+// day are declared with example_tests.
+use std::path::Path;
+use crate::aoc::{find_puzzle, AocError};
+use crate::aoc::answers::Answers;
+use crate::aoc::inputs::InputStore;
+use crate::verify::{check_puzzle, Status};
+
+/// Directory of the worked examples, relative to the root of the repository.
+pub const EXAMPLE_DIR: &str = "inputs/examples";
+
+/// Declare tests running the worked examples of the given day against both
+/// parts, failing if a part has no examples.
+///
+/// Used in the integration tests of a day, like:
+///
+/// ```text
+/// aoc2024::example_tests!(12);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        /// Test that the worked examples give the expected answers of part 1.
+        #[test]
+        fn test_part_1_examples() {
+            $crate::aoc::examples::assert_examples($day, 1);
+        }
+
+        /// Test that the worked examples give the expected answers of part 2.
+        #[test]
+        fn test_part_2_examples() {
+            $crate::aoc::examples::assert_examples($day, 2);
+        }
+    };
+}
+
+/// Run the examples of the day in the directory that have an answer for the
+/// part, describing every example giving another answer.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if there is no solution for the part or
+/// no example has an answer for it, and an error if the answers can not be
+/// read.
+pub fn failed_examples(dir: &Path, day: u8, part: u8)
+    -> Result<Vec<String>, AocError> {
+
+    let puzzle = find_puzzle(day, part).ok_or_else(|| AocError::UnexpectedError(
+        format!("No solution for day {} part {}.", day, part)))?;
+    let store = InputStore::new(vec![dir.to_path_buf()]);
+    let answers = Answers::read_dir(dir)?;
+
+    let mut examples = 0;
+    let mut failed = Vec::new();
+    for name in store.names(day) {
+        let name = Some(name.as_str()).filter(|&name| name != "main");
+        let Some(expected) = answers.get(day, name, part) else {
+            continue;
+        };
+        examples = examples + 1;
+
+        let input = store.read(day, name)?;
+        let check = check_puzzle(puzzle, &input, Some(expected));
+        let file_name = InputStore::file_name(day, name);
+        match (check.status, check.outcome) {
+            (Status::Fail(_), Some(outcome)) => failed.push(format!(
+                "Example {} of part {}: expected {}, got {}.", file_name, part,
+                expected, outcome.answer)),
+            (Status::Error(message), _) => failed.push(format!(
+                "Example {} of part {}: {}", file_name, part, message)),
+            _ => ()
+        }
+    }
+
+    if examples == 0 {
+        return Err(AocError::UnexpectedError(format!(
+            "No examples of day {} with answers for part {} in {}.", day, part,
+            dir.display())));
+    }
+
+    return Ok(failed);
+}
+
+/// Assert that the examples of the day in the example directory give the
+/// expected answers of the part.
+///
+/// # Panics
+///
+/// Panics describing the failed examples if any example fails, or if there
+/// are no examples for the part.
+pub fn assert_examples(day: u8, part: u8) {
+    match failed_examples(Path::new(EXAMPLE_DIR), day, part) {
+        Ok(failed) => assert!(failed.is_empty(), "{}", failed.join("\n")),
+        Err(err) => panic!("{}", err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    /// Test that failing examples are described and others are not.
+    #[test]
+    fn test_failed_examples() {
+        let dir = env::temp_dir()
+            .join(format!("aoc2024-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("09.txt"), "2333133121414131402\n").unwrap();
+        fs::write(dir.join("09-small.txt"), "12345\n").unwrap();
+        fs::write(dir.join("09-broken.txt"), "12x\n").unwrap();
+        fs::write(dir.join("answers.toml"), concat!(
+            "[day09]\npart1 = 1928\npart2 = 2858\n",
+            "[day09.small]\npart1 = 1\n",
+            "[day09.broken]\npart2 = 1\n"
+        )).unwrap();
+
+        let failed = failed_examples(&dir, 9, 1).unwrap();
+        assert_eq!(failed, vec!["Example 09-small.txt of part 1: expected 1, \
+            got 60."]);
+        let failed = failed_examples(&dir, 9, 2).unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].starts_with("Example 09-broken.txt of part 2: "));
+        assert!(failed_examples(&dir, 1, 1).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Test that the examples in the repository pass.
+    #[test]
+    fn test_assert_examples() {
+        assert_examples(12, 2);
+    }
+
+}
@@ -0,0 +1,88 @@
+// Explanations of the answers.
+//
+// A solution can explain its answer by noting what it found for each record of
+// the input, like whether a report is safe and why:
+//
+//    1 2 7 8 9: unsafe, 2 to 7 rises by 5
+//
+// The notes are collected in a Trace while explaining, after the answer has
+// been computed, so explaining never changes the answer or its timings. They
+// are printed to stderr in the output format, see aoc::output.
+use std::fmt::Display;
+
+/// What a solution found for a record of the input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Note {
+
+    /// The record of the input, like a line.
+    pub record: String,
+
+    /// What the record was found to be, like safe or unsafe.
+    pub verdict: String,
+
+    /// Why the record was found to be that, may be empty.
+    pub reason: String
+
+}
+
+/// The notes of an explanation, in the order they were found.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Trace {
+
+    /// The notes so far.
+    notes: Vec<Note>
+
+}
+
+impl Trace {
+
+    /// Create an empty trace.
+    pub fn new() -> Trace {
+        return Trace::default();
+    }
+
+    /// Note what was found for the record, with an empty reason if there is
+    /// nothing more to say.
+    pub fn note(&mut self, record: impl Display, verdict: impl Into<String>,
+        reason: impl Into<String>) {
+
+        self.notes.push(Note {
+            record: record.to_string(),
+            verdict: verdict.into(),
+            reason: reason.into()
+        });
+    }
+
+    /// The notes in the order they were found.
+    pub fn notes(&self) -> &[Note] {
+        return &self.notes;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that notes are kept in the order they were noted.
+    #[test]
+    fn test_note() {
+        let mut trace = Trace::new();
+        trace.note(12, "safe", "");
+        trace.note("1 2 7", "unsafe", format!("{} to {} rises by 5", 2, 7));
+
+        assert_eq!(trace.notes(), &[
+            Note {
+                record: "12".to_string(),
+                verdict: "safe".to_string(),
+                reason: String::new()
+            },
+            Note {
+                record: "1 2 7".to_string(),
+                verdict: "unsafe".to_string(),
+                reason: "2 to 7 rises by 5".to_string()
+            }
+        ]);
+    }
+
+}
@@ -0,0 +1,252 @@
+// Finding the puzzle inputs.
+//
+// The input of a day is stored in a file named after the day, like 12.txt.
+// A day can have more inputs stored next to it with a name after the day,
+// like 12-alt.txt or 12-example-2.txt. The files are looked for in these
+// directories, in order:
+//
+//    $AOC_INPUT_DIR                 if the variable is set
+//    inputs                         relative to the working directory
+//    $XDG_CACHE_HOME/aoc2024        or ~/.cache/aoc2024 if it is not set
+//
+// An input given by path on the command line is read from that path instead.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::aoc::AocError;
+
+/// Environment variable naming a directory to look for inputs in first.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory of the inputs in the repository.
+pub const INPUT_DIR: &str = "inputs";
+
+/// Name of the directory of inputs in the per-user cache directory.
+const CACHE_NAME: &str = "aoc2024";
+
+/// Directories to look for puzzle inputs in.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InputStore {
+
+    /// The directories in the order they are searched.
+    dirs: Vec<PathBuf>
+
+}
+
+impl InputStore {
+
+    /// Create a store looking for inputs in the given directories, in order.
+    pub fn new(dirs: Vec<PathBuf>) -> InputStore {
+        return InputStore {
+            dirs: dirs
+        };
+    }
+
+    /// Create a store looking in the directories described above, using the
+    /// environment of the process.
+    pub fn from_env() -> InputStore {
+        return InputStore::from_vars(|name| env::var_os(name));
+    }
+
+    /// Create a store looking in the directories described above, reading
+    /// environment variables with the given function.
+    fn from_vars<F>(var: F) -> InputStore
+    where
+        F: Fn(&str) -> Option<std::ffi::OsString>,
+    {
+        let mut dirs = Vec::new();
+        if let Some(dir) = var(INPUT_DIR_VAR).filter(|dir| !dir.is_empty()) {
+            dirs.push(PathBuf::from(dir));
+        }
+        dirs.push(PathBuf::from(INPUT_DIR));
+
+        let cache = var("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| {
+                Path::new(&home).join(".cache")
+            }));
+        if let Some(cache) = cache {
+            dirs.push(cache.join(CACHE_NAME));
+        }
+
+        return InputStore::new(dirs);
+    }
+
+    /// The directories searched, in order.
+    pub fn dirs(&self) -> &[PathBuf] {
+        return &self.dirs;
+    }
+
+    /// Name of the file of an input of the day, the main input if no name is
+    /// given.
+    pub fn file_name(day: u8, name: Option<&str>) -> String {
+        match name {
+            Some(name) => format!("{:02}-{}.txt", day, name),
+            None => format!("{:02}.txt", day)
+        }
+    }
+
+    /// Find the path of an input of the day, the main input if no name is
+    /// given.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.IoError listing the directories searched and the
+    /// names of the inputs found for the day if the input is not in any of
+    /// the directories.
+    pub fn find(&self, day: u8, name: Option<&str>)
+        -> Result<PathBuf, AocError> {
+
+        let file_name = InputStore::file_name(day, name);
+        let found = self.dirs.iter()
+            .map(|dir| dir.join(&file_name))
+            .find(|path| path.is_file());
+        if let Some(path) = found {
+            return Ok(path);
+        }
+
+        let searched: Vec<String> = self.dirs.iter()
+            .map(|dir| dir.display().to_string())
+            .collect();
+        let mut message = format!("No input {} for day {}, looked in {}.",
+            file_name, day, searched.join(", "));
+        let names = self.names(day);
+        if !names.is_empty() {
+            message = format!("{} Inputs found for the day: {}.", message,
+                names.join(", "));
+        }
+        message = format!("{} Save the input there or set {}.", message,
+            INPUT_DIR_VAR);
+        return Err(AocError::IoError(message));
+    }
+
+    /// Read an input of the day, the main input if no name is given.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.IoError if the input can not be found or read.
+    pub fn read(&self, day: u8, name: Option<&str>)
+        -> Result<Vec<u8>, AocError> {
+
+        return read_path(&self.find(day, name)?);
+    }
+
+    /// Names of the inputs of the day found in any of the directories.
+    ///
+    /// The main input is named "main". Names are sorted and given once even
+    /// if found in more than one directory.
+    pub fn names(&self, day: u8) -> Vec<String> {
+        let prefix = format!("{:02}", day);
+        let mut names = Vec::new();
+        for dir in self.dirs.iter() {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                let name = file_name.to_str()
+                    .and_then(|file| file.strip_prefix(&prefix))
+                    .and_then(|file| file.strip_suffix(".txt"));
+                match name {
+                    Some("") => names.push("main".to_string()),
+                    Some(name) if name.starts_with('-') && name.len() > 1 => {
+                        names.push(name[1..].to_string());
+                    },
+                    _ => ()
+                }
+            }
+        }
+        names.sort();
+        names.dedup();
+        return names;
+    }
+
+}
+
+/// Read an input from the given path.
+///
+/// # Errors
+///
+/// Will give AocError.IoError naming the path if it can not be read.
+pub fn read_path(path: &Path) -> Result<Vec<u8>, AocError> {
+    return fs::read(path).map_err(|err| AocError::IoError(
+        format!("Could not read {}: {}", path.display(), err)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsString;
+
+    /// Create an empty directory for a test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir()
+            .join(format!("aoc2024-inputs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        return dir;
+    }
+
+    /// Test that the directories are found from the environment.
+    #[test]
+    fn test_from_vars() {
+        let store = InputStore::from_vars(|name| match name {
+            "AOC_INPUT_DIR" => Some(OsString::from("/aoc")),
+            "HOME" => Some(OsString::from("/home/elf")),
+            _ => None
+        });
+        assert_eq!(store.dirs(), &[
+            PathBuf::from("/aoc"),
+            PathBuf::from("inputs"),
+            PathBuf::from("/home/elf/.cache/aoc2024")
+        ]);
+
+        let store = InputStore::from_vars(|name| match name {
+            "XDG_CACHE_HOME" => Some(OsString::from("/cache")),
+            "HOME" => Some(OsString::from("/home/elf")),
+            _ => None
+        });
+        assert_eq!(store.dirs(), &[
+            PathBuf::from("inputs"),
+            PathBuf::from("/cache/aoc2024")
+        ]);
+    }
+
+    /// Test that inputs are found in the first directory having them.
+    #[test]
+    fn test_find() {
+        let first = test_dir("find-first");
+        let second = test_dir("find-second");
+        fs::write(first.join("12-alt.txt"), "first").unwrap();
+        fs::write(second.join("12-alt.txt"), "second").unwrap();
+        fs::write(second.join("12.txt"), "main").unwrap();
+        let store = InputStore::new(vec![first.clone(), second.clone()]);
+
+        assert_eq!(store.find(12, None), Ok(second.join("12.txt")));
+        assert_eq!(store.read(12, Some("alt")), Ok(b"first".to_vec()));
+        assert_eq!(store.names(12), vec!["alt", "main"]);
+        assert_eq!(store.names(1), Vec::<String>::new());
+
+        fs::remove_dir_all(first).unwrap();
+        fs::remove_dir_all(second).unwrap();
+    }
+
+    /// Test that missing inputs are reported with the directories searched.
+    #[test]
+    fn test_find_missing() {
+        let dir = test_dir("missing");
+        fs::write(dir.join("03-example.txt"), "").unwrap();
+        let store = InputStore::new(vec![dir.clone()]);
+
+        let error = store.find(3, Some("alt")).unwrap_err();
+        assert_eq!(error, AocError::IoError(format!(
+            "No input 03-alt.txt for day 3, looked in {}. \
+            Inputs found for the day: example. \
+            Save the input there or set AOC_INPUT_DIR.", dir.display())));
+        assert!(read_path(&dir.join("03.txt")).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+}
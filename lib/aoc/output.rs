@@ -0,0 +1,303 @@
+// Printing the outcome of a puzzle.
+//
+// The outcome can be printed in one of these formats:
+//
+//    text    the answer only, like 6353648390778
+//    json    {"day":9,"part":2,"answer":6353648390778,"parse_ms":0.052,
+//            "solve_ms":81.207} on a single line
+//    tsv     day, part, answer, parse_ms and solve_ms separated by tabs
+//
+// Answers that are integers are numbers in JSON and other answers are
+// strings. Times are in milliseconds with microsecond precision. The format is
+// chosen with --format or the AOC_FORMAT environment variable, the flag taking
+// precedence, and is text if neither is given.
+//
+// The notes explaining an answer, see aoc::explain, are printed in the same
+// format, a line per note:
+//
+//    text    the record, verdict and reason, like 1 2 7 8 9: unsafe, 2 to 7
+//            rises by 5
+//    json    {"day":2,"part":1,"record":"1 2 7 8 9","verdict":"unsafe",
+//            "reason":"2 to 7 rises by 5"} on a single line
+//    tsv     day, part, record, verdict and reason separated by tabs
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+use crate::aoc::{AocError, Outcome};
+use crate::aoc::explain::Note;
+
+/// Environment variable choosing the output format.
+pub const FORMAT_VAR: &str = "AOC_FORMAT";
+
+/// The flags given to a day binary.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub struct Flags<'a> {
+
+    /// The value of --format, if given.
+    pub format: Option<&'a str>,
+
+    /// Whether --explain was given.
+    pub explain: bool
+
+}
+
+/// Format to print the outcome of a puzzle in.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Format {
+
+    /// Only the answer, for people.
+    #[default]
+    Text,
+
+    /// A JSON object with the answer and timings.
+    Json,
+
+    /// A line of tab separated values with the answer and timings.
+    Tsv
+
+}
+
+impl Format {
+
+    /// Choose the format given by the flag if any, by the AOC_FORMAT
+    /// environment variable otherwise, and text if neither is given.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.UnexpectedError if the format is not known.
+    pub fn choose(flag: Option<&str>) -> Result<Format, AocError> {
+        if let Some(flag) = flag {
+            return flag.parse();
+        }
+        match env::var(FORMAT_VAR) {
+            Ok(format) if !format.is_empty() => return format.parse(),
+            _ => return Ok(Format::Text)
+        }
+    }
+
+    /// Render the outcome of the day and part in the format.
+    pub fn render(&self, day: u8, part: u8, outcome: &Outcome) -> String {
+        let parse_ms = millis(outcome.parse_time);
+        let solve_ms = millis(outcome.solve_time);
+        match self {
+            Format::Text => return outcome.answer.clone(),
+            Format::Json => return format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ms\":{},\
+                \"solve_ms\":{}}}", day, part, json_answer(&outcome.answer),
+                parse_ms, solve_ms),
+            Format::Tsv => return format!("{}\t{}\t{}\t{}\t{}", day, part,
+                tsv_field(&outcome.answer), parse_ms, solve_ms)
+        }
+    }
+
+    /// Render a note explaining the answer of the day and part in the format.
+    pub fn render_note(&self, day: u8, part: u8, note: &Note) -> String {
+        match self {
+            Format::Text if note.reason.is_empty() => return format!("{}: {}",
+                note.record, note.verdict),
+            Format::Text => return format!("{}: {}, {}", note.record,
+                note.verdict, note.reason),
+            Format::Json => return format!(
+                "{{\"day\":{},\"part\":{},\"record\":{},\"verdict\":{},\
+                \"reason\":{}}}", day, part, json_string(&note.record),
+                json_string(&note.verdict), json_string(&note.reason)),
+            Format::Tsv => return format!("{}\t{}\t{}\t{}\t{}", day, part,
+                tsv_field(&note.record), tsv_field(&note.verdict),
+                tsv_field(&note.reason))
+        }
+    }
+
+}
+
+impl FromStr for Format {
+
+    type Err = AocError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(AocError::UnexpectedError(format!(
+                "Unknown output format '{}', expected text, json or tsv.",
+                format)))
+        }
+    }
+
+}
+
+/// Parse the --format and --explain flags of a day binary, given in any
+/// order.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError on any other argument, if a flag is
+/// given twice or if --format is missing its value.
+pub fn parse_flags(args: &[String]) -> Result<Flags<'_>, AocError> {
+    let error = || AocError::UnexpectedError(format!(
+        "Unexpected arguments '{}', expected at most --format FORMAT and \
+        --explain.", args.join(" ")));
+
+    let mut flags = Flags::default();
+    let mut rest = args;
+    loop {
+        match rest {
+            [] => return Ok(flags),
+            [flag, format, tail @ ..]
+                if flag == "--format" && flags.format.is_none() => {
+
+                flags.format = Some(format);
+                rest = tail;
+            },
+            [flag, tail @ ..] if flag == "--explain" && !flags.explain => {
+                flags.explain = true;
+                rest = tail;
+            },
+            _ => return Err(error())
+        }
+    }
+}
+
+/// Format a duration in milliseconds with microsecond precision.
+pub fn millis(duration: Duration) -> String {
+    return format!("{:.3}", duration.as_secs_f64() * 1000.0);
+}
+
+/// Write the answer as a JSON number if it is an integer and as a JSON string
+/// otherwise.
+fn json_answer(answer: &str) -> String {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    let integer = !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'));
+    if integer {
+        return answer.to_string();
+    }
+    return json_string(answer);
+}
+
+/// Write the text as a quoted JSON string.
+///
+/// Quotes, backslashes and control characters are escaped, everything else
+/// is written as is.
+pub fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                quoted.push_str(&format!("\\u{:04x}", c as u32));
+            },
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    return quoted;
+}
+
+/// Write the text as a single TSV field.
+fn tsv_field(text: &str) -> String {
+    return text.replace(['\t', '\n', '\r'], " ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An outcome used in the tests.
+    fn outcome(answer: &str) -> Outcome {
+        return Outcome {
+            answer: answer.to_string(),
+            parse_time: Duration::from_micros(52),
+            solve_time: Duration::from_micros(81207)
+        };
+    }
+
+    /// Test that outcomes are rendered in every format.
+    #[test]
+    fn test_render() {
+        let outcome = outcome("6353648390778");
+        assert_eq!(Format::Text.render(9, 2, &outcome), "6353648390778");
+        assert_eq!(Format::Json.render(9, 2, &outcome), concat!(
+            "{\"day\":9,\"part\":2,\"answer\":6353648390778,",
+            "\"parse_ms\":0.052,\"solve_ms\":81.207}"
+        ));
+        assert_eq!(Format::Tsv.render(9, 2, &outcome),
+            "9\t2\t6353648390778\t0.052\t81.207");
+    }
+
+    /// Test that notes are rendered in every format.
+    #[test]
+    fn test_render_note() {
+        let note = Note {
+            record: "1 2 7 8 9".to_string(),
+            verdict: "unsafe".to_string(),
+            reason: "2 to 7 rises by 5".to_string()
+        };
+        assert_eq!(Format::Text.render_note(2, 1, &note),
+            "1 2 7 8 9: unsafe, 2 to 7 rises by 5");
+        assert_eq!(Format::Json.render_note(2, 1, &note), concat!(
+            "{\"day\":2,\"part\":1,\"record\":\"1 2 7 8 9\",",
+            "\"verdict\":\"unsafe\",\"reason\":\"2 to 7 rises by 5\"}"
+        ));
+        assert_eq!(Format::Tsv.render_note(2, 1, &note),
+            "2\t1\t1 2 7 8 9\tunsafe\t2 to 7 rises by 5");
+
+        let note = Note {
+            reason: String::new(),
+            ..note
+        };
+        assert_eq!(Format::Text.render_note(2, 1, &note), "1 2 7 8 9: unsafe");
+    }
+
+    /// Test that answers that are not integers are JSON strings.
+    #[test]
+    fn test_json_answer() {
+        assert_eq!(json_answer("-12"), "-12");
+        assert_eq!(json_answer("0"), "0");
+        assert_eq!(json_answer("007"), "\"007\"");
+        assert_eq!(json_answer("1,2"), "\"1,2\"");
+        assert_eq!(json_answer("a\"b\n"), "\"a\\\"b\\n\"");
+        assert_eq!(json_answer(""), "\"\"");
+    }
+
+    /// Test that quotes, backslashes and control characters are escaped.
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("\r\n\t\u{1}æ"), "\"\\r\\n\\t\\u0001æ\"");
+    }
+
+    /// Test that TSV fields have no tabs or line breaks.
+    #[test]
+    fn test_tsv_field() {
+        assert_eq!(tsv_field("a\tb\r\nc"), "a b  c");
+    }
+
+    /// Test that formats and flags are parsed.
+    #[test]
+    fn test_parse() {
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert!("xml".parse::<Format>().is_err());
+        assert_eq!(Format::choose(Some("json")), Ok(Format::Json));
+
+        let args: Vec<String> = ["--explain", "--format", "json", "--explain"]
+            .iter().map(|arg| arg.to_string()).collect();
+        assert_eq!(parse_flags(&args[1..3]), Ok(Flags {
+            format: Some("json"),
+            explain: false
+        }));
+        assert_eq!(parse_flags(&args[..3]), Ok(Flags {
+            format: Some("json"),
+            explain: true
+        }));
+        assert_eq!(parse_flags(&[]), Ok(Flags::default()));
+        assert!(parse_flags(&args[1..2]).is_err());
+        assert!(parse_flags(&args).is_err());
+    }
+
+}
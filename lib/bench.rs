@@ -0,0 +1,218 @@
+// Benchmarks of the puzzle solutions.
+//
+// Every part of a puzzle is run a number of times on the same input, timing
+// the parse and solve phases separately. The timings are summarized by their
+// mean, minimum and maximum and can be printed as a table or as JSON to
+// compare runs over time.
+use std::fmt::Write;
+use std::time::Duration;
+use crate::aoc::{AocError, Puzzle, PUZZLES};
+use crate::aoc::inputs::InputStore;
+use crate::aoc::output::millis;
+
+/// Summary of a number of timings.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Stats {
+
+    /// Mean of the timings.
+    pub mean: Duration,
+
+    /// Shortest timing.
+    pub min: Duration,
+
+    /// Longest timing.
+    pub max: Duration
+
+}
+
+/// Timings of a single part of a puzzle.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Benchmark {
+
+    /// The day of the puzzle.
+    pub day: u8,
+
+    /// The part of the puzzle.
+    pub part: u8,
+
+    /// Number of times the puzzle was run.
+    pub runs: u32,
+
+    /// Timings of the parse phase.
+    pub parse: Stats,
+
+    /// Timings of the solve phase.
+    pub solve: Stats
+
+}
+
+impl Stats {
+
+    /// Summarize the given timings, there must be at least one.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let min = *samples.iter().min()?;
+        let max = *samples.iter().max()?;
+        let total: Duration = samples.iter().sum();
+
+        return Some(Stats {
+            mean: total / samples.len() as u32,
+            min: min,
+            max: max
+        });
+    }
+
+}
+
+/// Benchmark a part of a puzzle by running it the given number of times on
+/// the input.
+///
+/// # Errors
+///
+/// Fails if the puzzle fails on the input or if runs is 0.
+pub fn bench_puzzle(puzzle: &Puzzle, input: &[u8], runs: u32)
+    -> Result<Benchmark, AocError> {
+
+    let mut parse_times = Vec::new();
+    let mut solve_times = Vec::new();
+    for _ in 0..runs {
+        let outcome = (puzzle.entry)(&mut &input[..], None)?;
+        parse_times.push(outcome.parse_time);
+        solve_times.push(outcome.solve_time);
+    }
+
+    let no_runs = || AocError::UnexpectedError(
+        "Expected at least one run.".to_string());
+    return Ok(Benchmark {
+        day: puzzle.day,
+        part: puzzle.part,
+        runs: runs,
+        parse: Stats::from_samples(&parse_times).ok_or_else(no_runs)?,
+        solve: Stats::from_samples(&solve_times).ok_or_else(no_runs)?
+    });
+}
+
+/// Benchmark both parts of the given day, or of all days if no day is given.
+///
+/// The main input of each day is read from the input store.
+///
+/// # Errors
+///
+/// Will give AocError.IoError if an input is missing and
+/// AocError.UnexpectedError if there is no solution for the day.
+pub fn bench(day: Option<u8>, runs: u32) -> Result<Vec<Benchmark>, AocError> {
+    let puzzles: Vec<&Puzzle> = PUZZLES.iter()
+        .filter(|p| day.is_none() || day == Some(p.day))
+        .collect();
+    if puzzles.is_empty() {
+        return Err(AocError::UnexpectedError(
+                format!("No solution for day {}.", day.unwrap_or(0))));
+    }
+
+    let store = InputStore::from_env();
+    let mut benchmarks = Vec::new();
+    for puzzle in puzzles {
+        let input = store.read(puzzle.day, None)?;
+        benchmarks.push(bench_puzzle(puzzle, &input, runs)?);
+    }
+
+    return Ok(benchmarks);
+}
+
+/// Format the benchmarks as a table with a row per part of a puzzle.
+///
+/// All timings are in milliseconds.
+pub fn to_table(benchmarks: &[Benchmark]) -> String {
+    let mut table = String::new();
+    let _ = writeln!(table, "{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} \
+        {:>10} {:>10}", "day", "part", "runs", "parse mean", "parse min",
+        "parse max", "solve mean", "solve min", "solve max");
+    for b in benchmarks {
+        let _ = writeln!(table, "{:>3} {:>4} {:>5} {:>10} {:>10} {:>10} {:>10} \
+            {:>10} {:>10}", b.day, b.part, b.runs,
+            millis(b.parse.mean), millis(b.parse.min), millis(b.parse.max),
+            millis(b.solve.mean), millis(b.solve.min), millis(b.solve.max));
+    }
+
+    return table;
+}
+
+/// Format the benchmarks as a JSON array with an object per part of a puzzle.
+///
+/// All timings are in milliseconds, like:
+///
+///    [{"day":1,"part":1,"runs":5,
+///      "parse":{"mean_ms":0.412,"min_ms":0.398,"max_ms":0.455},
+///      "solve":{"mean_ms":0.031,"min_ms":0.029,"max_ms":0.036}}]
+pub fn to_json(benchmarks: &[Benchmark]) -> String {
+    let stats = |s: &Stats| format!(
+        "{{\"mean_ms\":{},\"min_ms\":{},\"max_ms\":{}}}",
+        millis(s.mean), millis(s.min), millis(s.max));
+    let objects: Vec<String> = benchmarks.iter()
+        .map(|b| format!(
+            "{{\"day\":{},\"part\":{},\"runs\":{},\"parse\":{},\"solve\":{}}}",
+            b.day, b.part, b.runs, stats(&b.parse), stats(&b.solve)))
+        .collect();
+
+    return format!("[{}]", objects.join(","));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::find_puzzle;
+
+    /// Test that timings are summarized correctly.
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = [
+            Duration::from_millis(3),
+            Duration::from_millis(1),
+            Duration::from_millis(5)
+        ];
+        assert_eq!(Stats::from_samples(&samples), Some(Stats {
+            mean: Duration::from_millis(3),
+            min: Duration::from_millis(1),
+            max: Duration::from_millis(5)
+        }));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    /// Test that benchmarking a puzzle runs it the given number of times.
+    #[test]
+    fn test_bench_puzzle() {
+        let puzzle = find_puzzle(9, 1).unwrap();
+        let benchmark = bench_puzzle(puzzle, b"2333133121414131402\n", 3)
+            .unwrap();
+        assert_eq!((benchmark.day, benchmark.part, benchmark.runs), (9, 1, 3));
+        assert!(benchmark.parse.min <= benchmark.parse.mean);
+        assert!(benchmark.parse.mean <= benchmark.parse.max);
+
+        assert!(bench_puzzle(puzzle, b"2333133121414131402\n", 0).is_err());
+        let puzzle = find_puzzle(1, 1).unwrap();
+        assert!(bench_puzzle(puzzle, b"3   x\n", 1).is_err());
+    }
+
+    /// Test that benchmarks are formatted as JSON.
+    #[test]
+    fn test_to_json() {
+        let stats = Stats {
+            mean: Duration::from_micros(1500),
+            min: Duration::from_millis(1),
+            max: Duration::from_millis(2)
+        };
+        let benchmark = Benchmark {
+            day: 6,
+            part: 2,
+            runs: 2,
+            parse: stats,
+            solve: stats
+        };
+        assert_eq!(to_json(&[benchmark]), concat!(
+            "[{\"day\":6,\"part\":2,\"runs\":2,",
+            "\"parse\":{\"mean_ms\":1.500,\"min_ms\":1.000,\"max_ms\":2.000},",
+            "\"solve\":{\"mean_ms\":1.500,\"min_ms\":1.000,\"max_ms\":2.000}}]"
+        ));
+        assert_eq!(to_json(&[]), "[]");
+    }
+
+}
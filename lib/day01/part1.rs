@@ -0,0 +1,199 @@
+// --- Day 1: Historian Hysteria ---
+//
+// The Chief Historian is always present for the big Christmas sleigh launch,
+// but nobody has seen him in months! Last anyone heard, he was visiting
+// locations that are historically significant to the North Pole; a group of
+// Senior Historians has asked you to accompany them as they check the places
+// they think he was most likely to visit.
+//
+// As each location is checked, they will mark it on their list with a star.
+// They figure the Chief Historian must be in one of the first fifty places
+// they'll look, so in order to save Christmas, you need to help them get fifty
+// stars on their list before Santa takes off on December 25th.
+//
+// Collect stars by solving puzzles. Two puzzles will be made available on each
+// day in the Advent calendar; the second puzzle is unlocked when you complete
+// the first. Each puzzle grants one star. Good luck!
+//
+// You haven't even left yet and the group of Elvish Senior Historians has
+// already hit a problem: their list of locations to check is currently empty.
+// Eventually, someone decides that the best place to check first would be the
+// Chief Historian's office.
+//
+// Upon pouring into the office, everyone confirms that the Chief Historian is
+// indeed nowhere to be found. Instead, the Elves discover an assortment of
+// notes and lists of historically significant locations! This seems to be the
+// planning the Chief Historian was doing before he left. Perhaps these notes
+// can be used to determine which locations to search?
+//
+// Throughout the Chief's office, the historically significant locations are
+// listed not by name but by a unique number called the location ID. To make
+// sure they don't miss anything, The Historians split into two groups, each
+// searching the office and trying to create their own complete list of location
+// IDs.
+//
+// There's just one problem: by holding the two lists up side by side (your
+// puzzle input), it quickly becomes clear that the lists aren't very similar.
+// Maybe you can help The Historians reconcile their lists?
+//
+// For example:
+//
+//    3   4
+//    4   3
+//    2   5
+//    1   3
+//    3   9
+//    3   3
+//
+// Maybe the lists are only off by a small amount! To find out, pair up the
+// numbers and measure how far apart they are. Pair up the smallest number in
+// the left list with the smallest number in the right list, then the
+// second-smallest left number with the second-smallest right number, and so on.
+//
+// Within each pair, figure out how far apart the two numbers are; you'll need
+// to add up all of those distances. For example, if you pair up a 3 from the
+// left list with a 7 from the right list, the distance apart is 4; if you pair
+// up a 9 with a 3, the distance apart is 6.
+//
+// In the example list above, the pairs and distances would be as follows:
+//
+// * The smallest number in the left list is 1, and the smallest number in the
+//   right list is 3. The distance between them is 2.
+// * The second-smallest number in the left list is 2, and the second-smallest
+//   number in the right list is another 3. The distance between them is 1.
+// * The third-smallest number in both lists is 3, so the distance between them
+//   is 0.
+// * The next numbers to pair up are 3 and 4, a distance of 1.
+// * The fifth-smallest numbers in each list are 3 and 5, a distance of 2.
+// * Finally, the largest number in the left list is 4, while the largest number
+//   in the right list is 9; these are a distance 5 apart.
+//
+// To find the total distance between the left list and the right list, add up
+// the distances between all of the pairs you found. In the example above, this
+// is 2 + 1 + 0 + 1 + 2 + 5, a total distance of 11!
+//
+// Your actual left and right lists contain many location IDs. What is the total
+// distance between your lists?
+use std::io::BufRead;
+use std::collections::BinaryHeap;
+use crate::nom_helpers::{lines_of, parse_all, parse_u32, ParseResult};
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use nom::{
+    character::complete::space1,
+    sequence::separated_pair
+};
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<(u32, u32)>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the two lists of location IDs as pairs of IDs, one pair per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<(u32, u32)>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_input(&input);
+}
+
+/// Compute the total distance between the two lists.
+pub fn solve(pairs: &Vec<(u32, u32)>) -> Result<u32, AocError> {
+    let mut ns1 = BinaryHeap::new();
+    let mut ns2 = BinaryHeap::new();
+    for (n1, n2) in pairs {
+        ns1.push(*n1);
+        ns2.push(*n2);
+    }
+
+    let mut difference = 0;
+    loop {
+        let n1_may = ns1.pop();
+        let n2_may = ns2.pop();
+        match (n1_may, n2_may) {
+            (Some(n1), Some(n2)) => {
+                difference = difference + n1.abs_diff(n2);
+            }
+            _ => {
+                return Ok(difference);
+            }
+        }
+    }
+}
+
+/// Parse the pairs of IDs from the full input.
+/// Note the distance of each pair of numbers, paired up from smallest to
+/// largest.
+pub fn explain(pairs: &Vec<(u32, u32)>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    let mut ns1: Vec<u32> = pairs.iter().map(|(n1, _)| *n1).collect();
+    let mut ns2: Vec<u32> = pairs.iter().map(|(_, n2)| *n2).collect();
+    ns1.sort();
+    ns2.sort();
+    for (n1, n2) in ns1.iter().zip(ns2.iter()) {
+        trace.note(format!("{} {}", n1, n2),
+            format!("distance {}", n1.abs_diff(*n2)), "");
+    }
+
+    return Ok(());
+}
+
+fn parse_input(input: &str) -> Result<Vec<(u32, u32)>, AocError> {
+    return parse_all(lines_of(parse_line), input);
+}
+
+/// Parse a line from the input.
+fn parse_line(input: &str) -> ParseResult<'_, (u32, u32)> {
+    separated_pair(parse_u32, space1, parse_u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that parsing a line from the input works.
+    #[test]
+    fn test_parse_line() {
+        let parsed = parse_input("3   4");
+        assert_eq!(parsed, Ok(vec![(3, 4)]));
+        let parsed = parse_input("3   4\n4 3\n");
+        assert_eq!(parsed, Ok(vec![(3, 4), (4, 3)]));
+    }
+
+    /// Test that parsing a line from the input fails if line is not valid.
+    #[test]
+    fn test_parse_line_invalid() {
+        let error = parse_input("3   4whatever").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 1, column 6: expected end of input, found 'w'\n",
+            "1 | 3   4whatever\n",
+            "  |      ^"
+        ));
+
+        let error = parse_input("3   4\n4\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 2: expected a space, found '\\n'\n",
+            "2 | 4\n",
+            "  |  ^"
+        ));
+    }
+
+}
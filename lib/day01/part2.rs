@@ -0,0 +1,164 @@
+// --- Part Two ---
+// Your analysis only confirmed what everyone feared: the two lists of location
+// IDs are indeed very different.
+//
+// Or are they?
+//
+// The Historians can't agree on which group made the mistakes or how to read
+// most of the Chief's handwriting, but in the commotion you notice an
+// interesting detail: a lot of location IDs appear in both lists! Maybe the
+// other numbers aren't location IDs at all but rather misinterpreted
+// handwriting.
+//
+// This time, you'll need to figure out exactly how often each number from the
+// left list appears in the right list. Calculate a total similarity score by
+// adding up each number in the left list after multiplying it by the number of
+// times that number appears in the right list.
+//
+// Here are the same example lists again:
+//
+//    3   4
+//    4   3
+//    2   5
+//    1   3
+//    3   9
+//    3   3
+//
+// For these example lists, here is the process of finding the similarity score:
+//
+// * The first number in the left list is 3. It appears in the right list three
+//   times, so the similarity score increases by 3 * 3 = 9.
+// * The second number in the left list is 4. It appears in the right list once,
+//   so the similarity score increases by 4 * 1 = 4.
+// * The third number in the left list is 2. It does not appear in the right
+//   list, so the similarity score does not increase (2 * 0 = 0).
+// * The fourth number, 1, also does not appear in the right list.
+// * The fifth number, 3, appears in the right list three times; the similarity
+//   score increases by 9.
+// * The last number, 3, appears in the right list three times; the similarity
+//   score again increases by 9.
+//
+// So, for these example lists, the similarity score at the end of this process
+// is 31 (9 + 4 + 0 + 0 + 9 + 9).
+//
+// Once again consider your left and right lists. What is their similarity
+// score?
+use std::io::BufRead;
+use std::collections::HashMap;
+use crate::nom_helpers::{lines_of, parse_all, parse_u32, ParseResult};
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use nom::{
+    character::complete::space1,
+    sequence::separated_pair
+};
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<(u32, u32)>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the two lists of location IDs as pairs of IDs, one pair per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<(u32, u32)>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_input(&input);
+}
+
+/// Compute the similarity score of the two lists.
+pub fn solve(pairs: &Vec<(u32, u32)>) -> Result<u32, AocError> {
+    let mut numbers = Vec::new();
+    let mut counts = HashMap::new();
+    for (n1, n2) in pairs {
+        numbers.push(*n1);
+        counts.entry(*n2).and_modify(|n| *n += 1).or_insert(1);
+    }
+
+    let mut similarity = 0;
+    for n in numbers.iter() {
+        let score = counts.get(n).unwrap_or(&0) * n;
+        similarity = similarity + score;
+    }
+
+    return Ok(similarity);
+}
+
+/// Parse the pairs of IDs from the full input.
+/// Note the score of each number in the left list, its number of appearances
+/// in the right list times the number.
+pub fn explain(pairs: &Vec<(u32, u32)>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    let mut counts = HashMap::new();
+    for (_, n2) in pairs {
+        counts.entry(*n2).and_modify(|n| *n += 1).or_insert(1);
+    }
+
+    for (n, _) in pairs {
+        let count = counts.get(n).unwrap_or(&0);
+        trace.note(n, format!("score {}", count * n),
+            format!("appears {} times in the right list", count));
+    }
+
+    return Ok(());
+}
+
+fn parse_input(input: &str) -> Result<Vec<(u32, u32)>, AocError> {
+    return parse_all(lines_of(parse_line), input);
+}
+
+/// Parse a line from the input.
+fn parse_line(input: &str) -> ParseResult<'_, (u32, u32)> {
+    separated_pair(parse_u32, space1, parse_u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that parsing a line from the input works.
+    #[test]
+    fn test_parse_line() {
+        let parsed = parse_input("3   4");
+        assert_eq!(parsed, Ok(vec![(3, 4)]));
+        let parsed = parse_input("3   4\n4 3\n");
+        assert_eq!(parsed, Ok(vec![(3, 4), (4, 3)]));
+    }
+
+    /// Test that parsing a line from the input fails if line is not valid.
+    #[test]
+    fn test_parse_line_invalid() {
+        let error = parse_input("3   4whatever").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 1, column 6: expected end of input, found 'w'\n",
+            "1 | 3   4whatever\n",
+            "  |      ^"
+        ));
+
+        let error = parse_input("3   4\n4\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 2: expected a space, found '\\n'\n",
+            "2 | 4\n",
+            "  |  ^"
+        ));
+    }
+
+}
@@ -0,0 +1,267 @@
+// --- Day 2: Red-Nosed Reports ---
+//
+// Fortunately, the first location The Historians want to search isn't a long
+// walk from the Chief Historian's office.
+//
+// While the Red-Nosed Reindeer nuclear fusion/fission plant appears to contain
+// no sign of the Chief Historian, the engineers there run up to you as soon as
+// they see you. Apparently, they still talk about the time Rudolph was saved
+// through molecular synthesis from a single electron.
+//
+// They're quick to add that - since you're already here - they'd really
+// appreciate your help analyzing some unusual data from the Red-Nosed reactor.
+// You turn to check if The Historians are waiting for you, but they seem to
+// have already divided into groups that are currently searching every corner of
+// the facility. You offer to help with the unusual data.
+//
+// The unusual data (your puzzle input) consists of many reports, one report per
+// line. Each report is a list of numbers called levels that are separated by
+// spaces. For example:
+//
+//    7 6 4 2 1
+//    1 2 7 8 9
+//    9 7 6 2 1
+//    1 3 2 4 5
+//    8 6 4 4 1
+//    1 3 6 7 9
+//
+// This example data contains six reports each containing five levels.
+//
+// The engineers are trying to figure out which reports are safe. The Red-Nosed
+// reactor safety systems can only tolerate levels that are either gradually
+// increasing or gradually decreasing. So, a report only counts as safe if both
+// of the following are true:
+//
+// * The levels are either all increasing or all decreasing.
+// * Any two adjacent levels differ by at least one and at most three.
+//
+// In the example above, the reports can be found safe or unsafe by checking
+// those rules:
+//
+// * 7 6 4 2 1: Safe because the levels are all decreasing by 1 or 2.
+// * 1 2 7 8 9: Unsafe because 2 7 is an increase of 5.
+// * 9 7 6 2 1: Unsafe because 6 2 is a decrease of 4.
+// * 1 3 2 4 5: Unsafe because 1 3 is increasing but 3 2 is decreasing.
+// * 8 6 4 4 1: Unsafe because 4 4 is neither an increase or a decrease.
+// * 1 3 6 7 9: Safe because the levels are all increasing by 1, 2, or 3.
+//
+// So, in this example, 2 reports are safe.
+//
+// Analyze the unusual data from the engineers. How many reports are safe?
+use std::io::BufRead;
+use itertools::Itertools;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::nom_helpers::{
+    lines_of, parse_all, parse_u32, whitespace_list, ParseResult
+};
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<Vec<u32>>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the reports, one report of levels per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<Vec<u32>>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_input(&input);
+}
+
+/// Count the number of safe reports.
+pub fn solve(reports: &Vec<Vec<u32>>) -> Result<u32, AocError> {
+    let mut safe = 0;
+    for report in reports {
+        if is_safe(report) {
+            safe = safe + 1;
+        }
+    }
+
+    return Ok(safe);
+}
+
+/// Note whether each report is safe, and why not for unsafe reports.
+pub fn explain(reports: &Vec<Vec<u32>>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for report in reports {
+        let record = report.iter().join(" ");
+        match first_violation(report) {
+            None => trace.note(record, "safe", ""),
+            Some(i) => {
+                trace.note(record, "unsafe", describe_violation(report, i));
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Check whether the report is safe.
+///
+/// Reports must be either monotonically increasing or decreasing. Reports must
+/// only rise or fall by at most 3 at a time. Reports must either always rise or
+/// fall.
+fn is_safe(report: &Vec<u32>) -> bool {
+    return first_violation(report).is_none();
+}
+
+/// Find the first level of the report that does not follow the levels before
+/// it in a safe way, if any.
+fn first_violation(report: &Vec<u32>) -> Option<usize> {
+    if report.len() <= 1 {
+        return None;
+    }
+
+    let increase = report[1] > report[0];
+    for i in 1..report.len() {
+        let last = report[i - 1];
+        let next = report[i];
+        let difference = last.abs_diff(next);
+        if difference == 0 || difference > 3 ||
+            (increase && next < last) ||
+            (!increase && next > last) {
+            return Some(i);
+        }
+    }
+
+    return None;
+}
+
+/// Describe why the level at the index does not follow the level before it in
+/// a safe way.
+fn describe_violation(report: &Vec<u32>, i: usize) -> String {
+    let last = report[i - 1];
+    let next = report[i];
+    let change = |from: u32, to: u32| if to > from { "rises" } else { "falls" };
+    if last == next {
+        return format!("{} to {} neither rises nor falls", last, next);
+    }
+    if last.abs_diff(next) > 3 {
+        return format!("{} to {} {} by {}", last, next, change(last, next),
+            last.abs_diff(next));
+    }
+    return format!("{} to {} {} but {} to {} {}", last, next,
+        change(last, next), report[0], report[1], change(report[0], report[1]));
+}
+
+/// Parse the reports from the full input.
+fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, AocError> {
+    return parse_all(lines_of(parse_line), input);
+}
+
+/// Parse a line from the input.
+fn parse_line(input: &str) -> ParseResult<'_, Vec<u32>> {
+    whitespace_list(parse_u32)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that parsing a line from the input works.
+    #[test]
+    fn test_parse_line() {
+        let parsed = parse_input("1 2 3 4 5");
+        assert_eq!(parsed, Ok(vec![vec![1, 2, 3, 4, 5]]));
+        let parsed = parse_input("1 2\n3  4\n");
+        assert_eq!(parsed, Ok(vec![vec![1, 2], vec![3, 4]]));
+    }
+
+    /// Test that parsing a line from the input fails if line is not valid.
+    #[test]
+    fn test_parse_line_invalid() {
+        let error = parse_input("1 2\n3   4whatever").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 6: expected end of input, found 'w'\n",
+            "2 | 3   4whatever\n",
+            "  |      ^"
+        ));
+    }
+
+    /// Test that empty reports are safe.
+    #[test]
+    fn test_is_safe_empty_report() {
+        assert!(is_safe(&vec![]));
+    }
+
+    /// Test that singleton reports are safe.
+    #[test]
+    fn test_is_safe_singleton_report() {
+        assert!(is_safe(&vec![1]));
+    }
+
+    /// Test that reports of 2 elements that are close together are safe.
+    #[test]
+    fn test_is_safe_2_elements_increasing_report() {
+        assert!(is_safe(&vec![1, 2]));
+    }
+
+    /// Test that reports of 2 elements that are close together are safe.
+    #[test]
+    fn test_is_safe_2_elements_decreasing_report() {
+        assert!(is_safe(&vec![3, 0]));
+    }
+
+    /// Test that reports of 2 elements that are too far away are not safe.
+    #[test]
+    fn test_is_safe_2_elements_too_far_report() {
+        assert!(!is_safe(&vec![4, 0]));
+    }
+
+    /// Test that an unsafe report is reported as unsafe.
+    #[test]
+    fn test_is_safe_unsafe() {
+        assert!(!is_safe(&vec![1, 2, 7, 8, 9]));
+    }
+
+    /// Test that an safe report is reported as safe.
+    #[test]
+    fn test_is_safe_safe() {
+        assert!(is_safe(&vec![1, 3, 6, 7, 9]));
+    }
+
+    /// Test that a report is unsafe if a number doesn't rise.
+    #[test]
+    fn test_is_safe_unsafe_if_no_change() {
+        assert!(!is_safe(&vec![1, 3, 6, 7, 7, 9]));
+    }
+
+    /// Test that unsafe reports are explained by the level making them unsafe.
+    #[test]
+    fn test_explain() {
+        let reports = vec![vec![7, 6, 4, 2, 1], vec![1, 2, 7, 8, 9],
+            vec![1, 3, 2, 4, 5], vec![8, 6, 4, 4, 1]];
+        let mut trace = Trace::new();
+        explain(&reports, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("7 6 4 2 1", "safe", ""),
+            ("1 2 7 8 9", "unsafe", "2 to 7 rises by 5"),
+            ("1 3 2 4 5", "unsafe", "3 to 2 falls but 1 to 3 rises"),
+            ("8 6 4 4 1", "unsafe", "4 to 4 neither rises nor falls")
+        ]);
+    }
+
+}
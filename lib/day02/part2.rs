@@ -0,0 +1,252 @@
+// --- Part Two ---
+//
+// The engineers are surprised by the low number of safe reports until they
+// realize they forgot to tell you about the Problem Dampener.
+//
+// The Problem Dampener is a reactor-mounted module that lets the reactor safety
+// systems tolerate a single bad level in what would otherwise be a safe report.
+// It's like the bad level never happened!
+//
+// Now, the same rules apply as before, except if removing a single level from
+// an unsafe report would make it safe, the report instead counts as safe.
+//
+// More of the above example's reports are now safe:
+//
+// * 7 6 4 2 1: Safe without removing any level.
+// * 1 2 7 8 9: Unsafe regardless of which level is removed.
+// * 9 7 6 2 1: Unsafe regardless of which level is removed.
+// * 1 3 2 4 5: Safe by removing the second level, 3.
+// * 8 6 4 4 1: Safe by removing the third level, 4.
+// * 1 3 6 7 9: Safe without removing any level.
+//
+// Thanks to the Problem Dampener, 4 reports are actually safe!
+//
+// Update your analysis by handling situations where the Problem Dampener can
+// remove a single level from unsafe reports. How many reports are now safe?
+use std::io::BufRead;
+use itertools::Itertools;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::nom_helpers::{
+    lines_of, parse_all, parse_int, whitespace_list, ParseResult
+};
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<Vec<i32>>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the reports, one report of levels per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<Vec<i32>>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_input(&input);
+}
+
+/// Count the number of safe reports when the Problem Dampener is used.
+pub fn solve(reports: &Vec<Vec<i32>>) -> Result<u32, AocError> {
+    let mut safe = 0;
+    for report in reports {
+        if is_safe(&mut report.clone()) {
+            safe = safe + 1;
+        }
+    }
+
+    return Ok(safe);
+}
+
+/// Note whether each report is safe, and which level the Problem Dampener
+/// removes to make it safe if it is needed.
+pub fn explain(reports: &Vec<Vec<i32>>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for report in reports {
+        let record = report.iter().join(" ");
+        if !is_safe(&mut report.clone()) {
+            trace.note(record, "unsafe",
+                "regardless of which level is removed");
+            continue;
+        }
+
+        let removed = (0..report.len()).find(|&i| {
+            let mut dampened = report.clone();
+            dampened.remove(i);
+            return is_undampened_safe(&dampened);
+        });
+        match removed {
+            Some(i) if !is_undampened_safe(report) => trace.note(record,
+                "safe", format!("by removing level {}, {}", i + 1, report[i])),
+            _ => trace.note(record, "safe", "without removing any level")
+        }
+    }
+
+    return Ok(());
+}
+
+/// Check whether the report is safe without removing any level.
+fn is_undampened_safe(report: &[i32]) -> bool {
+    let differences: Vec<i32> = report.windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect();
+    return differences.iter().all(|d| (1..=3).contains(d))
+        || differences.iter().all(|d| (-3..=-1).contains(d));
+}
+
+/// Check whether the report is safe.
+///
+/// Reports must be either monotonically increasing or decreasing. Reports must
+/// only rise or fall by at most 3 at a time. Reports must either always rise or
+/// fall. We are allowed to remove a single level from the report to make it
+/// safe.
+fn is_safe(report: &mut Vec<i32>) -> bool {
+    if report.len() < 2 {
+        return true;
+    }
+    if report[0] > report[report.len() - 1] {
+        report.reverse();
+    }
+    let mut has_skipped = false;
+    let mut last = report[0];
+
+    for i in 1..report.len() {
+        let current = report[i];
+        let current_diff = current - last;
+        if current_diff <= 0 || current_diff > 3 {
+            // Check if we already failed.
+            if has_skipped {
+                return false;
+            } else {
+                has_skipped = true;
+            }
+
+            // If this is the last, then we are good.
+            if i == report.len() - 1 {
+                return true;
+            }
+
+            // Check if we can skip current.
+            let next = report[i + 1];
+            let next_diff = next - last;
+            if next_diff > 0 && next_diff <= 3 {
+                continue;
+            }
+
+            // If this is the first check we just skip the first number.
+            if i == 1 {
+                last = current;
+                continue;
+            }
+
+            // Check if we can skip last.
+            let before = report[i - 2];
+            let before_diff = current - before;
+            if before_diff > 0 && before_diff <= 3 {
+                last = current;
+                continue;
+            }
+
+            // We can neither skip current nor last.
+            return false;
+        } else {
+            last = current;
+        }
+    }
+
+    return true;
+}
+
+/// Parse the reports from the full input.
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
+    return parse_all(lines_of(parse_line), input);
+}
+
+/// Parse a line from the input.
+fn parse_line(input: &str) -> ParseResult<'_, Vec<i32>> {
+    whitespace_list(parse_int)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that parsing a line from the input works.
+    #[test]
+    fn test_parse_line() {
+        let parsed = parse_input("1 2 3 4 5");
+        assert_eq!(parsed, Ok(vec![vec![1, 2, 3, 4, 5]]));
+        let parsed = parse_input("1 2\n3  4\n");
+        assert_eq!(parsed, Ok(vec![vec![1, 2], vec![3, 4]]));
+        assert_eq!(parse_input("-1 +2 3"), Ok(vec![vec![-1, 2, 3]]));
+    }
+
+    /// Test that parsing a line from the input fails if line is not valid.
+    #[test]
+    fn test_parse_line_invalid() {
+        let error = parse_input("1 2\n3   4whatever").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 6: expected end of input, found 'w'\n",
+            "2 | 3   4whatever\n",
+            "  |      ^"
+        ));
+    }
+
+    /// Test that an safe report is reported as safe.
+    #[test]
+    fn test_is_safe() {
+        assert!(is_safe(&mut vec![1, 3, 6, 7, 9]));
+        assert!(is_safe(&mut vec![39, 37, 35, 32, 29, 27, 24]));
+        assert!(is_safe(&mut vec![77, 79, 81, 82, 83, 85, 88]));
+        assert!(is_safe(&mut vec![2, 5, 6, 7, 8, 12]));
+        assert!(is_safe(&mut vec![2, 5, 4, 5, 7, 8]));
+        assert!(is_safe(&mut vec![1, 3, 6, 7, 7, 9]));
+        assert!(is_safe(&mut vec![1, 3, 2, 4, 5]));
+        assert!(is_safe(&mut vec![1, 9, 10, 11, 13]));
+        assert!(is_safe(&mut vec![4, 0]));
+        assert!(is_safe(&mut vec![3, 0]));
+        assert!(is_safe(&mut vec![1, 2]));
+        assert!(is_safe(&mut vec![1]));
+        assert!(is_safe(&mut vec![]));
+        assert!(is_safe(&mut vec![43, 41, 45, 47, 49, 52, 55]));
+
+        assert!(!is_safe(&mut vec![1, 2, 7, 8, 9]));
+        assert!(!is_safe(&mut vec![66, 69, 70, 71, 73, 77, 78, 78]));
+        assert!(!is_safe(&mut vec![77, 77, 79, 80, 79, 80]));
+    }
+
+    /// Test that reports are explained by the level removed to make them safe.
+    #[test]
+    fn test_explain() {
+        let reports = vec![vec![7, 6, 4, 2, 1], vec![1, 2, 7, 8, 9],
+            vec![1, 3, 2, 4, 5]];
+        let mut trace = Trace::new();
+        explain(&reports, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str)> = trace.notes().iter()
+            .map(|n| (n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("safe", "without removing any level"),
+            ("unsafe", "regardless of which level is removed"),
+            ("safe", "by removing level 2, 3")
+        ]);
+    }
+
+}
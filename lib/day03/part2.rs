@@ -0,0 +1,314 @@
+// --- Part Two ---
+//
+// As you scan through the corrupted memory, you notice that some of the
+// conditional statements are also still intact. If you handle some of the
+// uncorrupted conditional statements in the program, you might be able to get
+// an even more accurate result.
+//
+// There are two new instructions you'll need to handle:
+//
+//    * The do() instruction enables future mul instructions.
+//    * The don't() instruction disables future mul instructions.
+//
+// Only the most recent do() or don't() instruction applies. At the beginning of
+// the program, mul instructions are enabled.
+//
+// For example:
+//
+//     xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))
+//
+// This corrupted memory is similar to the example from before, but this time
+// the mul(5,5) and mul(11,8) instructions are disabled because there is a
+// don't() instruction before them. The other mul instructions function
+// normally, including the one at the end that gets re-enabled by a do()
+// instruction.
+//
+// This time, the sum of the results is 48 (2*4 + 8*5).
+//
+// Handle the new instructions; what do you get if you add up all of the results
+// of just the enabled multiplications?
+use std::io::BufRead;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::finite_automata::{FiniteAutomata, Pattern};
+use crate::finite_automata::{Tokenizer, TokenizerBuilder};
+
+/// The automata of the tokenizer rules, see Machine.rules, compiled ahead of
+/// time with FiniteAutomata.to_json. Write the JSON of the automata of the
+/// rules to tokenizer.json after changing them, test_tokenizer_json checks
+/// that it is up to date.
+const TOKENIZER_JSON: &str = include_str!("tokenizer.json");
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<u8>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Read the corrupted memory in full.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<u8>, AocError> {
+    let mut memory = Vec::new();
+    reader.read_to_end(&mut memory)?;
+    return Ok(memory);
+}
+
+/// Compute the sum of all multiplications that are enabled.
+pub fn solve(memory: &Vec<u8>) -> Result<u32, AocError> {
+    let mut machine = Machine::build_machine();
+    for byte in memory {
+        machine.transition(*byte);
+    }
+
+    return Ok(machine.sum_of_muls);
+}
+
+/// Note each instruction found and whether each multiplication is added to the
+/// sum or skipped.
+pub fn explain(memory: &Vec<u8>, trace: &mut Trace) -> Result<(), AocError> {
+    let mut machine = Machine::build_machine();
+    for byte in memory {
+        let active = machine.active;
+        match machine.transition(*byte) {
+            Some(Instruction::Mul(n1, n2)) if active => trace.note(
+                format!("mul({},{})", n1, n2), "added",
+                format!("{} * {} = {}", n1, n2, n1 * n2)),
+            Some(Instruction::Mul(n1, n2)) => trace.note(
+                format!("mul({},{})", n1, n2), "skipped",
+                "disabled by don't()"),
+            Some(Instruction::Do) => trace.note("do()", "enabled", ""),
+            Some(Instruction::Dont) => trace.note("don't()", "disabled", ""),
+            None => ()
+        }
+    }
+
+    return Ok(());
+}
+
+/// Instructions found in the corrupted memory.
+#[derive(Debug, PartialEq, Eq)]
+enum Instruction {
+
+    /// Multiply the two numbers.
+    Mul(u32, u32),
+
+    /// Enable future multiplications.
+    Do,
+
+    /// Disable future multiplications.
+    Dont
+
+}
+
+/// State machine that will parse input and compute sum of all activated
+/// multiplications.
+struct Machine {
+
+    /// Tokenizer finding the instructions.
+    tokenizer: Tokenizer<Instruction>,
+
+    /// Whether or not the multiplication is currently active.
+    active: bool,
+
+    /// Running sum of active multiplications.
+    sum_of_muls: u32
+
+}
+
+impl Machine {
+
+    /// Build machine that can parse mul(), do(), and don't() functions.
+    ///
+    /// The automata of the tokenizer is loaded from TOKENIZER_JSON rather
+    /// than compiled from the rules.
+    fn build_machine() -> Machine {
+        let automata = FiniteAutomata::from_json(TOKENIZER_JSON)
+            .expect("The saved tokenizer automata is valid.");
+        let tokenizer = Machine::rules().build_from(&automata)
+            .expect("The saved tokenizer automata is of the rules.");
+
+        return Machine {
+            tokenizer: tokenizer,
+            active: true,
+            sum_of_muls: 0
+        };
+    }
+
+    /// The rules of the tokenizer finding mul(), do(), and don't() functions.
+    fn rules() -> TokenizerBuilder<Instruction> {
+        let mul = Pattern::parse("mul\\((?<a>[0-9]+),(?<b>[0-9]+)\\)")
+            .expect("The mul pattern is valid.");
+        return Tokenizer::builder()
+            .rule("mul", mul,
+                |c| Some(Instruction::Mul(c.parse("a")?, c.parse("b")?)))
+            .rule("do", Pattern::literal(b"do()"), |_| Some(Instruction::Do))
+            .rule("dont", Pattern::literal(b"don't()"),
+                |_| Some(Instruction::Dont));
+    }
+
+    /// Transition the state of the machine based on the input byte, giving
+    /// the instruction found if the byte ends one.
+    fn transition(&mut self, byte: u8) -> Option<Instruction> {
+        let instruction = self.tokenizer.feed(byte);
+        match instruction {
+            Some(Instruction::Mul(n1, n2)) if self.active => {
+                self.sum_of_muls = self.sum_of_muls + n1 * n2;
+            },
+            Some(Instruction::Do) => self.active = true,
+            Some(Instruction::Dont) => self.active = false,
+            _ => ()
+        }
+
+        return instruction;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that we can parse mul() expressions.
+    #[test]
+    pub fn test_build_machine_simple_mul() {
+        let mut machine = Machine::build_machine();
+
+        machine.transition(b'm');
+        machine.transition(b'u');
+        machine.transition(b'l');
+        machine.transition(b'(');
+        machine.transition(b'2');
+        machine.transition(b',');
+        machine.transition(b'5');
+        machine.transition(b'0');
+        machine.transition(b')');
+
+        assert_eq!(machine.sum_of_muls, 100);
+    }
+
+    /// Test that we can parse mul() expressions that doesn't begin right away.
+    #[test]
+    pub fn test_build_machine_mul_starting_different_place() {
+        let mut machine = Machine::build_machine();
+
+        machine.transition(b'm');
+        machine.transition(b'u');
+        machine.transition(b'l');
+        machine.transition(b'(');
+        machine.transition(b'g');
+        machine.transition(b'a');
+        machine.transition(b'r');
+        machine.transition(b'b');
+
+        machine.transition(b'm');
+        machine.transition(b'u');
+        machine.transition(b'l');
+        machine.transition(b'(');
+        machine.transition(b'2');
+        machine.transition(b',');
+        machine.transition(b'5');
+        machine.transition(b'0');
+        machine.transition(b')');
+
+        assert_eq!(machine.sum_of_muls, 100);
+    }
+
+    /// Test that we can parse do() and don't() expressions.
+    #[test]
+    pub fn test_build_machine_do_and_dont() {
+        let mut machine = Machine::build_machine();
+
+        machine.transition(b'm');
+        assert!(machine.active);
+        machine.transition(b'u');
+        assert!(machine.active);
+        machine.transition(b'l');
+        assert!(machine.active);
+        machine.transition(b'(');
+        assert!(machine.active);
+        machine.transition(b'd');
+        assert!(machine.active);
+        machine.transition(b'o');
+        assert!(machine.active);
+        machine.transition(b'n');
+        assert!(machine.active);
+        machine.transition(b'\'');
+        assert!(machine.active);
+        machine.transition(b't');
+        assert!(machine.active);
+        machine.transition(b'(');
+        assert!(machine.active);
+        machine.transition(b')');
+        assert!(!machine.active);
+        machine.transition(b'd');
+        assert!(!machine.active);
+        machine.transition(b'o');
+        assert!(!machine.active);
+        machine.transition(b'(');
+        assert!(!machine.active);
+        machine.transition(b'd');
+        assert!(!machine.active);
+        machine.transition(b'o');
+        assert!(!machine.active);
+        machine.transition(b'(');
+        assert!(!machine.active);
+        machine.transition(b')');
+        assert!(machine.active);
+    }
+
+    /// Test that the instructions are found as tokens.
+    #[test]
+    pub fn test_build_machine_tokens() {
+        let mut machine = Machine::build_machine();
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](undo()";
+        let tokens: Vec<Instruction> = machine.tokenizer.tokens(input.bytes())
+            .collect();
+
+        assert_eq!(tokens, vec![
+            Instruction::Mul(2, 4),
+            Instruction::Dont,
+            Instruction::Mul(5, 5),
+            Instruction::Do
+        ]);
+    }
+
+    /// Test that a larger example computes the correct sum.
+    #[test]
+    pub fn test_build_machine_large_example() {
+        let mut machine = Machine::build_machine();
+        let input = concat![
+            "asdfamul(mul(2,4)dont()mul(3,1)asdfdon't()mul(100,100)dafdodo(do(",
+            ")mul(7,7)asfd"
+        ];
+
+        for byte in input.bytes() {
+            machine.transition(byte);
+        }
+
+        assert_eq!(machine.sum_of_muls, 60);
+    }
+
+    /// Test that the saved tokenizer automata is the one of the rules.
+    #[test]
+    fn test_tokenizer_json() {
+        let json = Machine::rules().automata().to_json().unwrap();
+        assert_eq!(TOKENIZER_JSON, json);
+    }
+
+}
@@ -0,0 +1,334 @@
+// --- Day 4: Ceres Search ---
+//
+// "Looks like the Chief's not here. Next!" One of The Historians pulls out a
+// device and pushes the only button on it. After a brief flash, you recognize
+// the interior of the Ceres monitoring station!
+//
+// As the search for the Chief continues, a small Elf who lives on the station
+// tugs on your shirt; she'd like to know if you could help her with her word
+// search (your puzzle input). She only has to find one word: XMAS.
+//
+// This word search allows words to be horizontal, vertical, diagonal, written
+// backwards, or even overlapping other words. It's a little unusual, though, as
+// you don't merely need to find one instance of XMAS - you need to find all of
+// them. Here are a few ways XMAS might appear, where irrelevant characters have
+// been replaced with .:
+//
+//    ..X...
+//    .SAMX.
+//    .A..A.
+//    XMAS.S
+//    .X....
+//
+// The actual word search will be full of letters instead. For example:
+//
+//    MMMSXXMASM
+//    MSAMXMSMSA
+//    AMXSXMAAMM
+//    MSAMASMSMX
+//    XMASAMXAMM
+//    XXAMMXXAMA
+//    SMSMSASXSS
+//    SAXAMASAAA
+//    MAMMMXMMMM
+//    MXMXAXMASX
+//
+// In this word search, XMAS occurs a total of 18 times; here's the same word
+// search again, but where letters not involved in any XMAS have been replaced
+// with .:
+//
+//    ....XXMAS.
+//    .SAMXMS...
+//    ...S..A...
+//    ..A.A.MS.X
+//    XMASAMX.MM
+//    X.....XA.A
+//    S.S.S.S.SS
+//    .A.A.A.A.A
+//    ..M.M.M.MM
+//    .X.X.XMASX
+//
+// Take a look at the little Elf's word search. How many times does XMAS appear?
+use std::io::BufRead;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::Grid;
+use crate::finite_automata::{CompiledAutomata, FiniteAutomata};
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Grid<u8>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the word search as a 2D array of bytes.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Grid<u8>, AocError> {
+    return Grid::parse(reader);
+}
+
+/// Count the occurences of XMAS in the word search.
+pub fn solve(array: &Grid<u8>) -> Result<u32, AocError> {
+    return Ok(count_xmas(array));
+}
+
+/// Explain the count by noting every row, column, diagonal and anti-diagonal
+/// with XMAS in it and how many times it occurs there.
+pub fn explain(array: &Grid<u8>, trace: &mut Trace) -> Result<(), AocError> {
+    explain_lines(trace, "row", array.rows());
+    explain_lines(trace, "column", array.columns());
+    explain_lines(trace, "diagonal", array.diagonals());
+    explain_lines(trace, "anti-diagonal", array.anti_diagonals());
+    return Ok(());
+}
+
+/// Count instances of the word "XMAS" appearing horizontally, vertically or
+/// diagonally either forwards or backwards in the given 2D array.
+fn count_xmas(array: &Grid<u8>) -> u32 {
+    let mut sum = 0;
+    sum = sum + count_xmas_lines(array.rows());
+    sum = sum + count_xmas_lines(array.columns());
+    sum = sum + count_xmas_lines(array.diagonals());
+    sum = sum + count_xmas_lines(array.anti_diagonals());
+    return sum;
+}
+
+/// Count instances of the word "XMAS" forwards or backwards in the lines.
+fn count_xmas_lines<'a, L, I>(lines: L) -> u32
+where
+    L: Iterator<Item=I>,
+    I: Iterator<Item=&'a u8>,
+{
+    let mut automata = xmas_automata();
+
+    let mut sum = 0;
+    for line in lines {
+        sum = sum + count_xmas_line(&mut automata, line);
+    }
+    return sum;
+}
+
+/// Note the lines of the direction with XMAS forwards or backwards in them,
+/// numbered from 0.
+fn explain_lines<'a, L, I>(trace: &mut Trace, direction: &str, lines: L)
+where
+    L: Iterator<Item=I>,
+    I: Iterator<Item=&'a u8>,
+{
+    let mut automata = xmas_automata();
+
+    for (index, line) in lines.enumerate() {
+        let count = count_xmas_line(&mut automata, line);
+        if count > 0 {
+            trace.note(format!("{} {}", direction, index),
+                format!("{} XMAS", count), "");
+        }
+    }
+}
+
+/// Create an automata finding "XMAS" forwards or backwards.
+fn xmas_automata() -> CompiledAutomata {
+    return FiniteAutomata::from_keywords(&[b"XMAS", b"SAMX"]).compile();
+}
+
+/// Count instances of the word "XMAS" forwards or backwards in the line.
+fn count_xmas_line<'a, I>(automata: &mut CompiledAutomata, line: I) -> u32
+where
+    I: Iterator<Item=&'a u8>,
+{
+    automata.reset();
+    let mut sum = 0;
+    for byte in line {
+        automata.transition(*byte)
+            .expect("Keyword automatas have a transition on every byte.");
+        sum = sum + automata.keywords().len() as u32;
+    }
+    return sum;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that counting horizontal XMAS works.
+    #[test]
+    fn test_count_xmas_horizontal() {
+        let input = Grid::from_rows(vec![
+            "..XMAS".bytes().collect(),
+            "......".bytes().collect(),
+            ".A..A.".bytes().collect(),
+            "X.AS.S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting double horizontal XMAS + SAMX works.
+    #[test]
+    fn test_count_xmas_double_horizontal() {
+        let input = Grid::from_rows(vec![
+            "..XMASAMX".bytes().collect(),
+            ".........".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            "X.AS.S...".bytes().collect(),
+            ".X.......".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 2);
+    }
+
+    /// Test that counting horizontal SAMX works.
+    #[test]
+    fn test_count_samx_horizontal() {
+        let input = Grid::from_rows(vec![
+            "..SAMX".bytes().collect(),
+            "......".bytes().collect(),
+            ".A..A.".bytes().collect(),
+            "X.AS.S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting vertical SAMX works.
+    #[test]
+    fn test_count_samx_vertical() {
+        let input = Grid::from_rows(vec![
+            "..XS.S".bytes().collect(),
+            "...A..".bytes().collect(),
+            ".A.MA.".bytes().collect(),
+            "X.AX.S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting diagonal down XMAS works.
+    #[test]
+    fn test_count_xmas_diagonal_down() {
+        let input = Grid::from_rows(vec![
+            "..XS.S".bytes().collect(),
+            "...M..".bytes().collect(),
+            ".A.MA.".bytes().collect(),
+            "X.AX.S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting diagonal up SAMX works.
+    #[test]
+    fn test_count_samx_diagonal_up() {
+        let input = Grid::from_rows(vec![
+            "...X.S".bytes().collect(),
+            "..M...".bytes().collect(),
+            ".A.M..".bytes().collect(),
+            "S.A..S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting diagonal up XMAS works.
+    #[test]
+    fn test_count_xmas_diagonal_up() {
+        let input = Grid::from_rows(vec![
+            "...X.S".bytes().collect(),
+            ".....S".bytes().collect(),
+            ".A.MA.".bytes().collect(),
+            "S.AM.S".bytes().collect(),
+            ".XX...".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 1);
+    }
+
+    /// Test that counting 'XMAS' and 'SAMX' produces correct count.
+    #[test]
+    fn test_count_xmas_1() {
+        let input = Grid::from_rows(vec![
+            "..X...".bytes().collect(),
+            ".SAMX.".bytes().collect(),
+            ".A..A.".bytes().collect(),
+            "XMAS.S".bytes().collect(),
+            ".X....".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 4);
+    }
+
+    /// Test that counting 'XMAS' and 'SAMX' produces correct count.
+    #[test]
+    fn test_count_xmas_2() {
+        let input = Grid::from_rows(vec![
+            "MMMSXXMASM".bytes().collect(),
+            "MSAMXMSMSA".bytes().collect(),
+            "AMXSXMAAMM".bytes().collect(),
+            "MSAMASMSMX".bytes().collect(),
+            "XMASAMXAMM".bytes().collect(),
+            "XXAMMXXAMA".bytes().collect(),
+            "SMSMSASXSS".bytes().collect(),
+            "SAXAMASAAA".bytes().collect(),
+            "MAMMMXMMMM".bytes().collect(),
+            "MXMXAXMASX".bytes().collect()
+        ]).unwrap();
+        let xmass = count_xmas(&input);
+
+        assert_eq!(xmass, 18);
+    }
+
+    /// Test that the lines with XMAS in them are explained.
+    #[test]
+    fn test_explain() {
+        let input = Grid::from_rows(vec![
+            "XMAS".bytes().collect(),
+            "M...".bytes().collect(),
+            "A...".bytes().collect(),
+            "S...".bytes().collect()
+        ]).unwrap();
+        let mut trace = Trace::new();
+        explain(&input, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("row 0", "1 XMAS", ""),
+            ("column 0", "1 XMAS", "")
+        ]);
+    }
+
+}
@@ -0,0 +1,180 @@
+// --- Part Two ---
+// The Elf looks quizzically at you. Did you misunderstand the assignment?
+
+// Looking for the instructions, you flip over the word search to find that this
+// isn't actually an XMAS puzzle; it's an X-MAS puzzle in which you're supposed
+// to find two MAS in the shape of an X. One way to achieve that is like this:
+//
+//   M.S
+//   .A.
+//   M.S
+//
+// Irrelevant characters have again been replaced with . in the above diagram.
+// Within the X, each MAS can be written forwards or backwards.
+//
+// Here's the same example from before, but this time all of the X-MASes have
+// been kept instead:
+//
+//    .M.S......
+//    ..A..MSMS.
+//    .M.S.MAA..
+//    ..A.ASMSM.
+//    .M.S.M....
+//    ..........
+//    S.S.S.S.S.
+//    .A.A.A.A..
+//    M.M.M.M.M.
+//    ..........
+//
+// In this example, an X-MAS appears 9 times.
+//
+// Flip the word search from the instructions back over to the word search side
+// and try again. How many times does an X-MAS appear?
+use std::io::BufRead;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::Grid;
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Grid<u8>;
+
+    type Answer = u32;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the word search as a 2D array of bytes.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Grid<u8>, AocError> {
+    return Grid::parse(reader);
+}
+
+/// Count the occurences of X-MAS in the word search.
+pub fn solve(array: &Grid<u8>) -> Result<u32, AocError> {
+    return Ok(count_x_mas(array));
+}
+
+/// Explain the count by noting the center of every X-MAS and the strings on
+/// its two diagonals.
+pub fn explain(array: &Grid<u8>, trace: &mut Trace) -> Result<(), AocError> {
+    for (x, y) in array.positions() {
+        if let Some((diagonal, anti_diagonal)) = x_mas(array, (x, y)) {
+            trace.note(format!("{},{}", x, y), "X-MAS", format!("{} and {}",
+                String::from_utf8_lossy(&diagonal),
+                String::from_utf8_lossy(&anti_diagonal)));
+        }
+    }
+    return Ok(());
+}
+
+/// Count instances of the MAS strings in an X shape. Like this:
+///
+///    M.S
+///    .A.
+///    M.S
+fn count_x_mas(array: &Grid<u8>) -> u32 {
+    let mut count = 0;
+    for position in array.positions() {
+        if x_mas(array, position).is_some() {
+            count = count + 1;
+        }
+    }
+
+    return count;
+}
+
+/// Give the strings on the diagonal and the anti-diagonal through the
+/// position if both are MAS forwards or backwards, None otherwise.
+fn x_mas(array: &Grid<u8>, (x, y): (usize, usize))
+    -> Option<([u8; 3], [u8; 3])> {
+
+    if x == 0 || y == 0 {
+        return None;
+    }
+    let corners = (
+        array.get((x - 1, y - 1)),
+        array.get((x + 1, y + 1)),
+        array.get((x - 1, y + 1)),
+        array.get((x + 1, y - 1))
+    );
+    if let (Some(&c1), Some(&c2), Some(&c3), Some(&c4)) = corners {
+        let center = array[(x, y)];
+        if is_sam(c1, center, c2) && is_sam(c3, center, c4) {
+            return Some(([c1, center, c2], [c3, center, c4]));
+        }
+    }
+    return None;
+}
+
+fn is_sam(c1: u8, c2: u8, c3: u8) -> bool {
+    return (c1 == b'M' && c2 == b'A' && c3 == b'S') ||
+        (c1 == b'S' && c2 == b'A' && c3 == b'M');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that counting single X-MAS works.
+    #[test]
+    fn test_count_x_mas_single() {
+        let input = Grid::from_rows(vec![
+            "M.S\n".bytes().collect(),
+            ".A.\n".bytes().collect(),
+            "M.S\n".bytes().collect()
+        ]).unwrap();
+
+        assert_eq!(count_x_mas(&input), 1);
+    }
+
+    /// Test that counting multiple X-MAS works.
+    #[test]
+    fn test_count_x_mas_multiple() {
+        let input = Grid::from_rows(vec![
+            ".M.S......\n".bytes().collect(),
+            "..A..MSMS.\n".bytes().collect(),
+            ".M.S.MAA..\n".bytes().collect(),
+            "..A.ASMSM.\n".bytes().collect(),
+            ".M.S.M....\n".bytes().collect(),
+            "..........\n".bytes().collect(),
+            "S.S.S.S.S.\n".bytes().collect(),
+            ".A.A.A.A..\n".bytes().collect(),
+            "M.M.M.M.M.\n".bytes().collect(),
+            "..........\n".bytes().collect()
+        ]).unwrap();
+
+        assert_eq!(count_x_mas(&input), 9);
+    }
+
+    /// Test that X-MAS are explained by their center and diagonals.
+    #[test]
+    fn test_explain() {
+        let input = Grid::from_rows(vec![
+            "M.S.".bytes().collect(),
+            ".A.A".bytes().collect(),
+            "M.S.".bytes().collect()
+        ]).unwrap();
+        let mut trace = Trace::new();
+        explain(&input, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![("1,1", "X-MAS", "MAS and MAS")]);
+    }
+
+}
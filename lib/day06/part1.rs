@@ -0,0 +1,468 @@
+// --- Day 6: Guard Gallivant ---
+//
+// The Historians use their fancy device again, this time to whisk you all away
+// to the North Pole prototype suit manufacturing lab... in the year 1518! It
+// turns out that having direct access to history is very convenient for a group
+// of historians.
+//
+// You still have to be careful of time paradoxes, and so it will be important
+// to avoid anyone from 1518 while The Historians search for the Chief.
+// Unfortunately, a single guard is patrolling this part of the lab.
+//
+// Maybe you can work out where the guard will go ahead of time so that The
+// Historians can search safely?
+//
+// You start by making a map (your puzzle input) of the situation. For example:
+//
+//    ....#.....
+//    .........#
+//    ..........
+//    ..#.......
+//    .......#..
+//    ..........
+//    .#..^.....
+//    ........#.
+//    #.........
+//    ......#...
+//
+// The map shows the current position of the guard with ^ (to indicate the guard
+// is currently facing up from the perspective of the map). Any obstructions -
+// crates, desks, alchemical reactors, etc. - are shown as #.
+//
+// Lab guards in 1518 follow a very strict patrol protocol which involves
+// repeatedly following these steps:
+//
+// * If there is something directly in front of you, turn right 90 degrees.
+// * Otherwise, take a step forward.
+//
+// Following the above protocol, the guard moves up several times until she
+// reaches an obstacle (in this case, a pile of failed suit prototypes):
+//
+//    ....#.....
+//    ....^....#
+//    ..........
+//    ..#.......
+//    .......#..
+//    ..........
+//    .#........
+//    ........#.
+//    #.........
+//    ......#...
+//
+// Because there is now an obstacle in front of the guard, she turns right
+// before continuing straight in her new facing direction:
+//
+//    ....#.....
+//    ........>#
+//    ..........
+//    ..#.......
+//    .......#..
+//    ..........
+//    .#........
+//    ........#.
+//    #.........
+//    ......#...
+//
+// Reaching another obstacle (a spool of several very long polymers), she turns
+// right again and continues downward:
+//
+//    ....#.....
+//    .........#
+//    ..........
+//    ..#.......
+//    .......#..
+//    ..........
+//    .#......v.
+//    ........#.
+//    #.........
+//    ......#...
+//
+// This process continues for a while, but the guard eventually leaves the
+// mapped area (after walking past a tank of universal solvent):
+//
+//    ....#.....
+//    .........#
+//    ..........
+//    ..#.......
+//    .......#..
+//    ..........
+//    .#........
+//    ........#.
+//    #.........
+//    ......#v..
+//
+// By predicting the guard's route, you can determine which specific positions
+// in the lab will be in the patrol path. Including the guard's starting
+// position, the positions visited by the guard before leaving the area are
+// marked with an X:
+//
+//    ....#.....
+//    ....XXXXX#
+//    ....X...X.
+//    ..#.X...X.
+//    ..XXXXX#X.
+//    ..X.X.X.X.
+//    .#XXXXXXX.
+//    .XXXXXXX#.
+//    #XXXXXXX..
+//    ......#X..
+//
+// In this example, the guard will visit 41 distinct positions on your map.
+//
+// Predict the path of the guard. How many distinct positions will the guard
+// visit before leaving the mapped area?
+use std::io::BufRead;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::{Grid, Position};
+use crate::nom_helpers::{grid_with, parse_all};
+use nom::{
+    character::complete::{char, one_of},
+    combinator::{map, value},
+    error::context
+};
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Lab;
+
+    type Answer = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the map of the lab.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Lab, AocError> {
+    return read_lab(reader);
+}
+
+/// Count the distinct positions the guard visits before leaving the lab.
+pub fn solve(lab: &Lab) -> Result<usize, AocError> {
+    let mut lab = lab.clone();
+    let mut positions = HashSet::from([lab.guard_position]);
+    while move_lab(&mut lab) {
+        positions.insert(lab.guard_position);
+    }
+    return Ok(positions.len());
+}
+
+/// Explain the path of the guard by noting every leg walked in a direction,
+/// where it ended and how many steps it took.
+pub fn explain(lab: &Lab, trace: &mut Trace) -> Result<(), AocError> {
+    let mut lab = lab.clone();
+    let mut start = lab.guard_position;
+    let mut direction = lab.guard_direction;
+    let mut steps = 0;
+    let leg = |start: Position, direction: Direction| {
+        return format!("from {},{} {}", start.0, start.1, name(direction));
+    };
+    while move_lab(&mut lab) {
+        if lab.guard_direction == direction {
+            steps = steps + 1;
+            continue;
+        }
+        let (x, y) = lab.guard_position;
+        trace.note(leg(start, direction), format!("turned {} at {},{}",
+            name(lab.guard_direction), x, y), format!("walked {}", steps));
+        start = lab.guard_position;
+        direction = lab.guard_direction;
+        steps = 0;
+    }
+    trace.note(leg(start, direction), "left the lab",
+        format!("walked {}", steps));
+    return Ok(());
+}
+
+/// A lab consists of a lab boundary, a set of obstructions, and a single guard.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Lab {
+
+    /// Map of the lab where obstructions to the guards path are true.
+    map: Grid<bool>,
+
+    /// The current position of the guard.
+    guard_position: Position,
+
+    /// The guards current direction of movement.
+    guard_direction: Direction
+
+}
+
+/// Directions that can be moved in.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Direction {
+    North, East, South, West
+}
+
+/// Move the guard in the lab according to the rules.
+///
+/// Returns false if the guard leaves the lab and true otherwise.
+fn move_lab(lab: &mut Lab) -> bool {
+    let step = step(lab.guard_direction);
+    match lab.map.offset(lab.guard_position, step) {
+        None => return false,
+        Some(new_pos) if lab.map[new_pos] => {
+            lab.guard_direction = turn_right(lab.guard_direction);
+        },
+        Some(new_pos) => {
+            lab.guard_position = new_pos;
+        }
+    }
+    return true;
+}
+
+/// The name of the direction, like north.
+fn name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "north",
+        Direction::East => "east",
+        Direction::South => "south",
+        Direction::West => "west"
+    }
+}
+
+/// The step taken when moving in the direction given.
+fn step(direction: Direction) -> (isize, isize) {
+    match direction {
+        Direction::North => (0, -1),
+        Direction::East => (1, 0),
+        Direction::South => (0, 1),
+        Direction::West => (-1, 0)
+    }
+}
+
+/// Turn right of the given direction.
+fn turn_right(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North
+    }
+}
+
+/// Read a lab state from the given reader or fail if given something useless.
+fn read_lab(reader: &mut dyn BufRead) -> Result<Lab, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let guard = value((false, ()), char('^'));
+    let cell = map(one_of(".#"), |c| c == '#');
+    let parser = grid_with(guard, context("'.', '#' or '^'", cell));
+    let (map, guards) = parse_all(parser, &input)?;
+    let guard = guards.first().map(|&(position, _)| position);
+
+    let guard_position = guard
+        .ok_or(AocError::UnexpectedError("Did not find a guard.".to_string()))?;
+    return Ok(Lab {
+        map: map,
+        guard_position: guard_position,
+        guard_direction: Direction::North
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions of all obstructions in the lab.
+    fn obstructions(lab: &Lab) -> HashSet<Position> {
+        return lab.map.iter()
+            .filter(|(_, &obstructed)| obstructed)
+            .map(|(position, _)| position)
+            .collect();
+    }
+
+    /// Test that reading a lab returns error if not given a guard position.
+    #[test]
+    fn test_read_lab_1() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let error = read_lab(&mut input.as_bytes()).err().unwrap();
+        assert_eq!(
+            AocError::UnexpectedError("Did not find a guard.".to_string()),
+            error);
+    }
+
+    /// Test that reading a lab works when a guard position is given.
+    #[test]
+    fn test_read_lab_2() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(lab.guard_position, (4, 6));
+        assert_eq!(lab.guard_direction, Direction::North);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that moving a lab a step forwards work.
+    #[test]
+    fn test_move_lab_1() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(move_lab(&mut lab));
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(lab.guard_position, (4, 5));
+        assert_eq!(lab.guard_direction, Direction::North);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that moving a lab a step forwards work.
+    #[test]
+    fn test_move_lab_2() {
+        let input = concat![
+            "....#.....\n",
+            "....^....#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(move_lab(&mut lab));
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(lab.guard_position, (4, 1));
+        assert_eq!(lab.guard_direction, Direction::East);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that moving the guard tells whether she stays in the lab.
+    #[test]
+    fn test_guard_in_bounds_1() {
+        let input = concat![
+            "....#.....\n",
+            "....^....#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(move_lab(&mut lab));
+    }
+
+    /// Test that moving the guard tells whether she stays in the lab.
+    #[test]
+    fn test_guard_in_bounds_2() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "^.....#...\n"
+        ];
+        let mut lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(move_lab(&mut lab));
+    }
+
+    /// Test that moving the guard tells whether she stays in the lab.
+    #[test]
+    fn test_guard_in_bounds_3() {
+        let input = concat![
+            "^...#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut lab = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(!move_lab(&mut lab));
+    }
+
+    /// Test that every leg of the guards path is explained.
+    #[test]
+    fn test_explain() {
+        let input = concat![
+            ".#..\n",
+            "...#\n",
+            ".^..\n"
+        ];
+        let lab = read_lab(&mut input.as_bytes()).unwrap();
+        let mut trace = Trace::new();
+        explain(&lab, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("from 1,2 north", "turned east at 1,1", "walked 1"),
+            ("from 1,1 east", "turned south at 2,1", "walked 1"),
+            ("from 2,1 south", "left the lab", "walked 1")
+        ]);
+    }
+
+}
@@ -0,0 +1,593 @@
+// --- Part Two ---
+//
+// While The Historians begin working around the guard's patrol route, you
+// borrow their fancy device and step outside the lab. From the safety of a
+// supply closet, you time travel through the last few months and record the
+// nightly status of the lab's guard post on the walls of the closet.
+//
+// Returning after what seems like only a few seconds to The Historians, they
+// explain that the guard's patrol area is simply too large for them to safely
+// search the lab without getting caught.
+//
+// Fortunately, they are pretty sure that adding a single new obstruction won't
+// cause a time paradox. They'd like to place the new obstruction in such a way
+// that the guard will get stuck in a loop, making the rest of the lab safe to
+// search.
+//
+// To have the lowest chance of creating a time paradox, The Historians would
+// like to know all of the possible positions for such an obstruction. The new
+// obstruction can't be placed at the guard's starting position - the guard is
+// there right now and would notice.
+//
+// In the above example, there are only 6 different positions where a new
+// obstruction would cause the guard to get stuck in a loop. The diagrams of
+// these six situations use O to mark the new obstruction, | to show a position
+// where the guard moves up/down, - to show a position where the guard moves
+// left/right, and + to show a position where the guard moves both up/down and
+// left/right.
+//
+// Option one, put a printing press next to the guard's starting position:
+//
+//    ....#.....
+//    ....+---+#
+//    ....|...|.
+//    ..#.|...|.
+//    ....|..#|.
+//    ....|...|.
+//    .#.O^---+.
+//    ........#.
+//    #.........
+//    ......#...
+//
+// Option two, put a stack of failed suit prototypes in the bottom right
+// quadrant of the mapped area:
+//
+//    ....#.....
+//    ....+---+#
+//    ....|...|.
+//    ..#.|...|.
+//    ..+-+-+#|.
+//    ..|.|.|.|.
+//    .#+-^-+-+.
+//    ......O.#.
+//    #.........
+//    ......#...
+// Option three, put a crate of chimney-squeeze prototype fabric next to the
+// standing desk in the bottom right quadrant:
+//
+//    ....#.....
+//    ....+---+#
+//    ....|...|.
+//    ..#.|...|.
+//    ..+-+-+#|.
+//    ..|.|.|.|.
+//    .#+-^-+-+.
+//    .+----+O#.
+//    #+----+...
+//    ......#...
+//
+// Option four, put an alchemical retroencabulator near the bottom left corner:
+//
+//    ....#.....
+//       ....+---+#
+//       ....|...|.
+//       ..#.|...|.
+//       ..+-+-+#|.
+//       ..|.|.|.|.
+//       .#+-^-+-+.
+//       ..|...|.#.
+//       #O+---+...
+//       ......#...
+//
+// Option five, put the alchemical retroencabulator a bit to the right instead:
+//
+//    ....#.....
+//    ....+---+#
+//    ....|...|.
+//    ..#.|...|.
+//    ..+-+-+#|.
+//    ..|.|.|.|.
+//    .#+-^-+-+.
+//    ....|.|.#.
+//    #..O+-+...
+//    ......#...
+//
+// Option six, put a tank of sovereign glue right next to the tank of universal
+// solvent:
+//
+//    ....#.....
+//    ....+---+#
+//    ....|...|.
+//    ..#.|...|.
+//    ..+-+-+#|.
+//    ..|.|.|.|.
+//    .#+-^-+-+.
+//    .+----++#.
+//    #+----++..
+//    ......#O..
+//
+// It doesn't really matter what you choose to use as an obstacle so long as you
+// and The Historians can put it into position without the guard noticing. The
+// important thing is having enough options that you can find one that minimizes
+// time paradoxes, and in this example, there are 6 different positions you
+// could choose.
+//
+// You need to get the guard stuck in a loop by adding a single new obstruction.
+// How many different positions could you choose for this obstruction?
+use std::io::BufRead;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::{Grid, Position};
+use crate::nom_helpers::{grid_with, parse_all};
+use nom::{
+    character::complete::{char, one_of},
+    combinator::{map, value},
+    error::context
+};
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Input;
+
+    type Answer = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the map of the lab and the starting position of the guard.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Input, AocError> {
+    return read_lab(reader);
+}
+
+/// Count the positions where a new obstruction makes the guard loop.
+pub fn solve(input: &Input) -> Result<usize, AocError> {
+    let obstructions = find_obstructions_producing_cycles(&mut input.clone());
+    return Ok(obstructions.len());
+}
+
+/// Explain the count by noting every position where a new obstruction makes
+/// the guard loop, in reading order.
+pub fn explain(input: &Input, trace: &mut Trace) -> Result<(), AocError> {
+    let obstructions = find_obstructions_producing_cycles(&mut input.clone());
+    let mut obstructions: Vec<Position> = obstructions.into_iter().collect();
+    obstructions.sort_by_key(|&(x, y)| (y, x));
+    for (x, y) in obstructions {
+        trace.note(format!("{},{}", x, y), "loops", "");
+    }
+    return Ok(());
+}
+
+/// Input of the problem.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Input {
+
+    /// The lab.
+    lab: Lab,
+
+    /// The guards position in/out of the lab.
+    guard: Guard
+
+}
+
+/// A lab consists of a map of obstructions.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Lab {
+
+    /// Map of the lab where obstructions to the guards path are true.
+    map: Grid<bool>,
+
+    /// An extra obstruction to prevent mutation of the above set.
+    extra_obstruction: Option<Position>
+
+}
+
+/// A guard has a position and a direction of movement.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+struct Guard {
+
+    /// The current position of the guard.
+    guard_position: Position,
+
+    /// The guards current direction of movement.
+    guard_direction: Direction,
+
+}
+
+/// Directions that can be moved in.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+enum Direction {
+    North, East, South, West
+}
+
+/// An action taken by the guard.
+#[derive(Debug, Eq, PartialEq)]
+enum Action {
+    Turn, Move, Leave
+}
+
+/// Find a list of possible obstructions that will generate a loop.
+///
+/// Runs the given lab simulation, in each location the guard visits we will
+/// place an obstruction and run another simulation. If the guard loops the
+/// position is added to the returned set.
+pub fn find_obstructions_producing_cycles(input: &mut Input) -> HashSet<Position> {
+    let lab = &mut input.lab;
+    let initial_guard = &input.guard;
+    let mut guard = initial_guard.clone();
+    let mut obstructions = HashSet::new();
+    loop {
+        match move_guard(lab, &mut guard) {
+            Action::Leave => {
+                return obstructions;
+            },
+            Action::Move => {
+                lab.extra_obstruction = Some(guard.guard_position);
+                if detect_loop(lab, &mut initial_guard.clone()) {
+                    obstructions.insert(guard.guard_position);
+                }
+                lab.extra_obstruction = None;
+            }
+            Action::Turn => { },
+        }
+    }
+}
+
+/// Detects whether or not there is a loop in the guards path.
+fn detect_loop(lab: &Lab, guard: &mut Guard) -> bool {
+    let mut turns = HashSet::new();
+    loop {
+        match move_guard(lab, guard) {
+            Action::Turn => {
+                if turns.contains(guard) {
+                    return true;
+                } else {
+                    turns.insert(guard.clone());
+                }
+            },
+            Action::Move => { },
+            Action::Leave => {
+                return false;
+            }
+        }
+    }
+}
+
+/// Move the guard in the lab according to the rules.
+///
+/// Will return the action taken by the guard. The guard is left where she is
+/// if she leaves the lab.
+fn move_guard(lab: &Lab, guard: &mut Guard) -> Action {
+    let step = step(guard.guard_direction);
+    let new_pos = match lab.map.offset(guard.guard_position, step) {
+        Some(new_pos) => new_pos,
+        None => return Action::Leave
+    };
+    if lab.extra_obstruction == Some(new_pos) || lab.map[new_pos] {
+        guard.guard_direction = turn_right(guard.guard_direction);
+        return Action::Turn;
+    } else {
+        guard.guard_position = new_pos;
+        return Action::Move;
+    }
+}
+
+/// The step taken when moving in the direction given.
+fn step(direction: Direction) -> (isize, isize) {
+    match direction {
+        Direction::North => (0, -1),
+        Direction::East => (1, 0),
+        Direction::South => (0, 1),
+        Direction::West => (-1, 0)
+    }
+}
+
+/// Turn right of the given direction.
+fn turn_right(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North
+    }
+}
+
+/// Read a lab state from the given reader or fail if given something useless.
+fn read_lab(reader: &mut dyn BufRead) -> Result<Input, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let guard = value((false, ()), char('^'));
+    let cell = map(one_of(".#"), |c| c == '#');
+    let parser = grid_with(guard, context("'.', '#' or '^'", cell));
+    let (map, guards) = parse_all(parser, &input)?;
+    let guard = guards.first().map(|&(position, _)| position);
+
+    let guard_position = guard
+        .ok_or(AocError::UnexpectedError("Did not find a guard.".to_string()))?;
+    return Ok(Input {
+        lab: Lab {
+            map: map,
+            extra_obstruction: None
+        },
+        guard: Guard {
+            guard_position: guard_position,
+            guard_direction: Direction::North
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions of all obstructions in the lab.
+    fn obstructions(lab: &Lab) -> HashSet<Position> {
+        return lab.map.iter()
+            .filter(|(_, &obstructed)| obstructed)
+            .map(|(position, _)| position)
+            .collect();
+    }
+
+    /// Test that reading a lab returns error if not given a guard position.
+    #[test]
+    fn test_read_lab_1() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let error = read_lab(&mut input.as_bytes()).err().unwrap();
+        assert_eq!(
+            AocError::UnexpectedError("Did not find a guard.".to_string()),
+            error);
+    }
+
+    /// Test that reading a lab works when a guard position is given.
+    #[test]
+    fn test_read_lab_2() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let input = read_lab(&mut input.as_bytes()).unwrap();
+        let lab = input.lab;
+        let guard = input.guard;
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(guard.guard_position, (4, 6));
+        assert_eq!(guard.guard_direction, Direction::North);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that moving a lab a step forwards work.
+    #[test]
+    fn test_move_guard_1() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        let lab = input.lab;
+        let guard = &mut input.guard;
+        assert_eq!(move_guard(&lab, guard), Action::Move);
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(guard.guard_position, (4, 5));
+        assert_eq!(guard.guard_direction, Direction::North);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that moving a lab a step forwards work.
+    #[test]
+    fn test_move_guard_2() {
+        let input = concat![
+            "....#.....\n",
+            "....^....#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        let lab = input.lab;
+        let guard = &mut input.guard;
+        assert_eq!(move_guard(&lab, guard), Action::Turn);
+        assert_eq!(lab.map.width(), 10);
+        assert_eq!(lab.map.height(), 10);
+        assert_eq!(guard.guard_position, (4, 1));
+        assert_eq!(guard.guard_direction, Direction::East);
+        assert_eq!(obstructions(&lab), HashSet::from([
+            (4, 0), (9, 1), (2, 3), (7, 4), (1, 6), (8, 7), (0, 8), (6, 9)
+        ]));
+    }
+
+    /// Test that verifying position of guard works.
+    #[test]
+    fn test_in_bounds_1() {
+        let input = concat![
+            "....#.....\n",
+            "....^....#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let input = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(input.lab.map.in_bounds((4, 1)));
+    }
+
+    /// Test that verifying position of guard works.
+    #[test]
+    fn test_in_bounds_2() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "^.....#...\n"
+        ];
+        let input = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(input.lab.map.in_bounds((0, 9)));
+    }
+
+    /// Test that the guard leaves the lab when walking past the edge.
+    #[test]
+    fn test_in_bounds_3() {
+        let input = concat![
+            "^...#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#........\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        assert_eq!(move_guard(&input.lab, &mut input.guard), Action::Leave);
+        assert_eq!(input.guard.guard_position, (0, 0));
+    }
+
+    /// Test that running a simulation of a guard that leaves the lab returns
+    /// false.
+    #[test]
+    fn test_detect_loop_1() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(!detect_loop(&input.lab, &mut input.guard));
+    }
+
+    /// Test that running a simulation of a guard that doesn't leave the lab
+    /// returns true.
+    #[test]
+    fn test_detect_loop_2() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "......#.#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        assert!(detect_loop(&input.lab, &mut input.guard));
+    }
+
+    /// Test that finding obstructions returns the correct list.
+    #[test]
+    fn test_find_obstructions_producing_cycles() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let mut input = read_lab(&mut input.as_bytes()).unwrap();
+        let obstructions = find_obstructions_producing_cycles(&mut input);
+        assert_eq!(obstructions.len(), 6);
+    }
+
+    /// Test that the obstructions making the guard loop are explained.
+    #[test]
+    fn test_explain() {
+        let input = concat![
+            "....#.....\n",
+            ".........#\n",
+            "..........\n",
+            "..#.......\n",
+            ".......#..\n",
+            "..........\n",
+            ".#..^.....\n",
+            "........#.\n",
+            "#.........\n",
+            "......#...\n"
+        ];
+        let input = read_lab(&mut input.as_bytes()).unwrap();
+        let mut trace = Trace::new();
+        explain(&input, &mut trace).unwrap();
+
+        let records: Vec<&str> = trace.notes().iter()
+            .map(|n| n.record.as_str())
+            .collect();
+        assert_eq!(records, vec!["3,6", "6,7", "7,7", "1,8", "3,8", "7,9"]);
+    }
+
+}
@@ -0,0 +1,342 @@
+// --- Day 7: Bridge Repair ---
+//
+// The Historians take you to a familiar rope bridge over a river in the middle
+// of a jungle. The Chief isn't on this side of the bridge, though; maybe he's
+// on the other side?
+//
+// When you go to cross the bridge, you notice a group of engineers trying to
+// repair it. (Apparently, it breaks pretty frequently.) You won't be able to
+// cross until it's fixed.
+//
+// You ask how long it'll take; the engineers tell you that it only needs final
+// calibrations, but some young elephants were playing nearby and stole all the
+// operators from their calibration equations! They could finish the
+// calibrations if only someone could determine which test values could possibly
+// be produced by placing any combination of operators into their calibration
+// equations (your puzzle input).
+//
+// For example:
+//
+//    190: 10 19
+//    3267: 81 40 27
+//    83: 17 5
+//    156: 15 6
+//    7290: 6 8 6 15
+//    161011: 16 10 13
+//    192: 17 8 14
+//    21037: 9 7 18 13
+//    292: 11 6 16 20
+//
+// Each line represents a single equation. The test value appears before the
+// colon on each line; it is your job to determine whether the remaining numbers
+// can be combined with operators to produce the test value.
+//
+// Operators are always evaluated left-to-right, not according to precedence
+// rules. Furthermore, numbers in the equations cannot be rearranged. Glancing
+// into the jungle, you can see elephants holding two different types of
+// operators: add (+) and multiply (*).
+//
+// Only three of the above equations can be made true by inserting operators:
+//
+// * 190: 10 19 has only one position that accepts an operator: between 10 and
+//   19. Choosing + would give 29, but choosing * would give the test value (10
+//   * 19 = 190).
+// * 3267: 81 40 27 has two positions for operators. Of the four possible
+//   configurations of the operators, two cause the right side to match the test
+//   value: 81 + 40 * 27 and 81 * 40 + 27 both equal 3267 (when evaluated
+//   left-to-right)!
+// * 292: 11 6 16 20 can be solved in exactly one way: 11 + 6 * 16 + 20.
+//
+// The engineers just need the total calibration result, which is the sum of the
+// test values from just the equations that could possibly be true. In the above
+// example, the sum of the test values for the three equations listed above is
+// 3749.
+//
+// Determine which equations could possibly be true. What is their total
+// calibration result?
+use std::io::BufRead;
+use std::fmt::Debug;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::nom_helpers::{
+    key_value, lines_of, parse_all, parse_u64, whitespace_list, ParseResult
+};
+use nom::character::complete::char;
+use itertools::Itertools;
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<Equation>;
+
+    type Answer = u64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the calibration equations, one equation per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<Equation>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_all(lines_of(do_parse_equation), &input);
+}
+
+/// Sum the results of the equations that can be made true.
+pub fn solve(equations: &Vec<Equation>) -> Result<u64, AocError> {
+    let mut sum = 0;
+    for equation in equations {
+        if equation.has_solution()? {
+            sum = sum + equation.result;
+        }
+    }
+
+    return Ok(sum);
+}
+
+/// Note which equations can be made true and with which operators.
+pub fn explain(equations: &Vec<Equation>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for equation in equations {
+        let record = format!("{}: {}", equation.result,
+            equation.operands.iter().join(" "));
+        match equation.find_solution()? {
+            Some(mask) => trace.note(record, "solvable",
+                equation.describe(mask)),
+            None => trace.note(record, "unsolvable", "")
+        }
+    }
+
+    return Ok(());
+}
+
+/// An equation without operators.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Equation {
+
+    /// The operands just waiting for operators to operate on them.
+    operands: Vec<u64>,
+
+    /// Supposed result.
+    result: u64
+
+}
+
+impl Equation {
+
+    /// Test whether any assignment of operators can make the equation true.
+    fn has_solution(&self) -> Result<bool, AocError> {
+        return Ok(self.find_solution()?.is_some());
+    }
+
+    /// Find the first mask of operators making the equation true, if any.
+    fn find_solution(&self) -> Result<Option<u32>, AocError> {
+        let operator_count: u32 = (self.operands.len() - 1).try_into().unwrap();
+        if operator_count > 32 {
+            return Err(AocError::UnexpectedError(
+                    "Equation longer than supported length.".to_string()));
+        }
+        for mask in 0..2_u32.pow(operator_count) {
+            if self.result == self.compute_result(mask) {
+                return Ok(Some(mask));
+            }
+        }
+
+        return Ok(None);
+    }
+
+    /// Write the operands with the operators of the mask between them, like
+    /// 81 + 40 * 27.
+    fn describe(&self, mask: u32) -> String {
+        let mut description = self.operands[0].to_string();
+        for i in 1..self.operands.len() {
+            let add = nth_bit(mask, (i - 1).try_into().unwrap());
+            description = format!("{} {} {}", description,
+                if add { "+" } else { "*" }, self.operands[i]);
+        }
+
+        return description;
+    }
+
+    /// Compute the result of the equation using the given mask.
+    ///
+    /// The mask is used to determine whether to use add or multiply as the
+    /// operation.
+    fn compute_result(&self, mask: u32) -> u64 {
+        if self.operands.is_empty() {
+            return 0;
+        }
+        let mut result: u64 = self.operands[0];
+        for i in 1..self.operands.len() {
+            let n: u64 = self.operands[i];
+            if nth_bit(mask, (i - 1).try_into().unwrap()) {
+                result = result + n;
+            } else {
+                result = result * n;
+            }
+        }
+
+        return result;
+    }
+
+}
+
+/// Parse an equation from the given string or error.
+fn do_parse_equation(input: &str) -> ParseResult<'_, Equation> {
+    let (input, (result, ns)) =
+        key_value(parse_u64, char(':'), whitespace_list(parse_u64))(input)?;
+
+    return Ok((input, Equation {
+        operands: ns,
+        result: result
+    }));
+}
+
+/// Test whether the n'th least significant bit is 1.
+fn nth_bit(value: u32, n: u32) -> bool {
+    (value >> n) & 1 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse an equation from the given string or error.
+    fn parse_equation(equation: &str) -> Result<Equation, AocError> {
+        return parse_all(do_parse_equation, equation);
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_1() {
+        let input = "123: 1 2 3";
+        let equation = parse_equation(input).unwrap();
+        let expected = Equation {
+            operands: vec![1, 2, 3],
+            result: 123
+        };
+        assert_eq!(equation, expected);
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_2() {
+        let input = "123: 1 2 3whateverkj;w";
+        let error = parse_equation(input);
+        assert!(error.is_err());
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_3() {
+        let input = "123: 1 21sf 3";
+        let error = parse_equation(input);
+        assert!(error.is_err());
+    }
+
+    /// Test that errors point out the line of the failing equation.
+    #[test]
+    fn test_parse() {
+        let input = "190: 10 19\n83 17 5\n";
+        let error = parse(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 4: expected ':', found '1'\n",
+            "2 | 83 17 5\n",
+            "  |    ^"
+        ));
+    }
+
+    /// Test that equations are explained by the operators making them true.
+    #[test]
+    fn test_explain() {
+        let equations = vec![parse_equation("3267: 81 40 27").unwrap(),
+            parse_equation("83: 17 5").unwrap()];
+        let mut trace = Trace::new();
+        explain(&equations, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("3267: 81 40 27", "solvable", "81 + 40 * 27"),
+            ("83: 17 5", "unsolvable", "")
+        ]);
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_1() {
+        let equation = parse_equation("190: 10 19").unwrap();
+        assert_eq!(Ok(true), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_2() {
+        let equation = parse_equation("3267: 81 40 27").unwrap();
+        assert_eq!(Ok(true), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_3() {
+        let equation = parse_equation("83: 17 5").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_4() {
+        let equation = parse_equation("156: 15 6").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_5() {
+        let equation = parse_equation("7290: 6 8 6 15").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_6() {
+        let equation = parse_equation("161011: 16 10 13").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_7() {
+        let equation = parse_equation("192: 17 8 14").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_8() {
+        let equation = parse_equation("21037: 9 7 18 13").unwrap();
+        assert_eq!(Ok(false), equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_9() {
+        let equation = parse_equation("292: 11 6 16 20").unwrap();
+        assert_eq!(Ok(true), equation.has_solution());
+    }
+
+}
@@ -0,0 +1,326 @@
+// --- Part Two ---
+//
+// The engineers seem concerned; the total calibration result you gave them is
+// nowhere close to being within safety tolerances. Just then, you spot your
+// mistake: some well-hidden elephants are holding a third type of operator.
+//
+// The concatenation operator (||) combines the digits from its left and right
+// inputs into a single number. For example, 12 || 345 would become 12345. All
+// operators are still evaluated left-to-right.
+//
+// Now, apart from the three equations that could be made true using only
+// addition and multiplication, the above example has three more equations that
+// can be made true by inserting operators:
+//
+// * 156: 15 6 can be made true through a single concatenation: 15 || 6 = 156.
+// * 7290: 6 8 6 15 can be made true using 6 * 8 || 6 * 15.
+// * 192: 17 8 14 can be made true using 17 || 8 + 14.
+//
+// Adding up all six test values (the three that could be made before using only
+// + and * plus the new three that can now be made by also using ||) produces
+// the new total calibration result of 11387.
+//
+// Using your new knowledge of elephant hiding spots, determine which equations
+// could possibly be true. What is their total calibration result?
+use std::io::BufRead;
+use std::fmt::Debug;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::nom_helpers::{
+    key_value, lines_of, parse_all, parse_u64, whitespace_list, ParseResult
+};
+use nom::character::complete::char;
+use itertools::Itertools;
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<Equation>;
+
+    type Answer = u64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the calibration equations, one equation per line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<Equation>, AocError> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return parse_all(lines_of(do_parse_equation), &input);
+}
+
+/// Sum the results of the equations that can be made true.
+pub fn solve(equations: &Vec<Equation>) -> Result<u64, AocError> {
+    let mut sum = 0;
+    for equation in equations {
+        if equation.has_solution() {
+            sum = sum + equation.result;
+        }
+    }
+
+    return Ok(sum);
+}
+
+/// Note which equations can be made true and with which operators.
+pub fn explain(equations: &Vec<Equation>, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for equation in equations {
+        let record = format!("{}: {}", equation.result,
+            equation.operands.iter().join(" "));
+        match equation.find_operators() {
+            Some(operators) => {
+                let mut description = equation.operands[0].to_string();
+                for (operator, n) in operators.iter()
+                    .zip(equation.operands[1..].iter()) {
+
+                    description = format!("{} {} {}", description, operator, n);
+                }
+                trace.note(record, "solvable", description);
+            },
+            None => trace.note(record, "unsolvable", "")
+        }
+    }
+
+    return Ok(());
+}
+
+/// An equation without operators.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Equation {
+
+    /// The operands just waiting for operators to operate on them.
+    operands: Vec<u64>,
+
+    /// Supposed result.
+    result: u64
+
+}
+
+impl Equation {
+
+    /// Test whether any solution exist to the equation.
+    fn has_solution(&self) -> bool {
+        if self.operands.is_empty() {
+            return self.result == 0;
+        } else {
+            return self.test(1, self.operands[0]);
+        }
+    }
+
+    /// Recursive helper function for testing whether solutions exist.
+    fn test(&self, i: usize, sum: u64) -> bool {
+        if i == self.operands.len() {
+            return sum == self.result;
+        }
+
+        let n = self.operands[i];
+        return self.test(i + 1, sum + n) ||
+            self.test(i + 1, sum * n) ||
+            self.test(i + 1, concatenate(sum, n));
+    }
+
+    /// Find the first operators making the equation true, if any.
+    ///
+    /// Slower than has_solution as the operators are collected on the way.
+    fn find_operators(&self) -> Option<Vec<&'static str>> {
+        if self.operands.is_empty() {
+            return if self.result == 0 { Some(vec![]) } else { None };
+        }
+
+        let mut operators = Vec::new();
+        if self.collect(1, self.operands[0], &mut operators) {
+            return Some(operators);
+        }
+        return None;
+    }
+
+    /// Recursive helper function for finding operators, pushing the operators
+    /// of a solution found.
+    fn collect(&self, i: usize, sum: u64, operators: &mut Vec<&'static str>)
+        -> bool {
+
+        if i == self.operands.len() {
+            return sum == self.result;
+        }
+
+        let n = self.operands[i];
+        let candidates = [("+", sum + n), ("*", sum * n),
+            ("||", concatenate(sum, n))];
+        for (operator, next) in candidates {
+            operators.push(operator);
+            if self.collect(i + 1, next, operators) {
+                return true;
+            }
+            operators.pop();
+        }
+
+        return false;
+    }
+
+}
+
+/// Parse an equation from the given string or error.
+fn do_parse_equation(input: &str) -> ParseResult<'_, Equation> {
+    let (input, (result, ns)) =
+        key_value(parse_u64, char(':'), whitespace_list(parse_u64))(input)?;
+
+    return Ok((input, Equation {
+        operands: ns,
+        result: result
+    }));
+}
+
+/// Concatenate the second integer behind the first in base 10.
+fn concatenate(a: u64, b: u64) -> u64 {
+    let b_chars = b.to_string().len().try_into().unwrap();
+    return a * 10_u64.pow(b_chars) + b;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse an equation from the given string or error.
+    fn parse_equation(equation: &str) -> Result<Equation, AocError> {
+        return parse_all(do_parse_equation, equation);
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_1() {
+        let input = "123: 1 2 3";
+        let equation = parse_equation(input).unwrap();
+        let expected = Equation {
+            operands: vec![1, 2, 3],
+            result: 123
+        };
+        assert_eq!(equation, expected);
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_2() {
+        let input = "123: 1 2 3whateverkj;w";
+        let error = parse_equation(input);
+        assert!(error.is_err());
+    }
+
+    /// Test that parsing an equation works as expected.
+    #[test]
+    fn test_parse_equation_3() {
+        let input = "123: 1 21sf 3";
+        let error = parse_equation(input);
+        assert!(error.is_err());
+    }
+
+    /// Test that errors point out the line of the failing equation.
+    #[test]
+    fn test_parse() {
+        let input = "190: 10 19\n83 17 5\n";
+        let error = parse(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 4: expected ':', found '1'\n",
+            "2 | 83 17 5\n",
+            "  |    ^"
+        ));
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_1() {
+        let equation = parse_equation("190: 10 19").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_2() {
+        let equation = parse_equation("3267: 81 40 27").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_3() {
+        let equation = parse_equation("83: 17 5").unwrap();
+        assert!(!equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_4() {
+        let equation = parse_equation("156: 15 6").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_5() {
+        let equation = parse_equation("7290: 6 8 6 15").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_6() {
+        let equation = parse_equation("161011: 16 10 13").unwrap();
+        assert!(!equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_7() {
+        let equation = parse_equation("192: 17 8 14").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_8() {
+        let equation = parse_equation("21037: 9 7 18 13").unwrap();
+        assert!(!equation.has_solution());
+    }
+
+    /// Test that evaluating equations works as expected.
+    #[test]
+    fn test_has_solution_9() {
+        let equation = parse_equation("292: 11 6 16 20").unwrap();
+        assert!(equation.has_solution());
+    }
+
+    /// Test that equations are explained by the operators making them true.
+    #[test]
+    fn test_explain() {
+        let equations = vec![parse_equation("156: 15 6").unwrap(),
+            parse_equation("83: 17 5").unwrap()];
+        let mut trace = Trace::new();
+        explain(&equations, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str)> = trace.notes().iter()
+            .map(|n| (n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![("solvable", "15 || 6"), ("unsolvable", "")]);
+    }
+
+    /// Test that concatenating integers works.
+    #[test]
+    fn test_concatenate() {
+        assert_eq!(100, concatenate(0, 100));
+        assert_eq!(123456789, concatenate(1234, 56789));
+    }
+
+}
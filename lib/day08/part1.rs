@@ -0,0 +1,292 @@
+// --- Day 8: Resonant Collinearity ---
+//
+// You find yourselves on the roof of a top-secret Easter Bunny installation.
+//
+// While The Historians do their thing, you take a look at the familiar huge
+// antenna. Much to your surprise, it seems to have been reconfigured to emit a
+// signal that makes people 0.1% more likely to buy Easter Bunny brand Imitation
+// Mediocre Chocolate as a Christmas gift! Unthinkable!
+//
+// Scanning across the city, you find that there are actually many such
+// antennas. Each antenna is tuned to a specific frequency indicated by a single
+// lowercase letter, uppercase letter, or digit. You create a map (your puzzle
+// input) of these antennas. For example:
+//
+//    ............
+//    ........0...
+//    .....0......
+//    .......0....
+//    ....0.......
+//    ......A.....
+//    ............
+//    ............
+//    ........A...
+//    .........A..
+//    ............
+//    ............
+//
+// The signal only applies its nefarious effect at specific antinodes based on
+// the resonant frequencies of the antennas. In particular, an antinode occurs
+// at any point that is perfectly in line with two antennas of the same
+// frequency - but only when one of the antennas is twice as far away as the
+// other. This means that for any pair of antennas with the same frequency,
+// there are two antinodes, one on either side of them.
+//
+// So, for these two antennas with frequency a, they create the two antinodes
+// marked with #:
+//
+//    ..........
+//    ...#......
+//    ..........
+//    ....a.....
+//    ..........
+//    .....a....
+//    ..........
+//    ......#...
+//    ..........
+//    ..........
+//
+// Adding a third antenna with the same frequency creates several more
+// antinodes. It would ideally add four antinodes, but two are off the right
+// side of the map, so instead it adds only two:
+//
+//    ..........
+//    ...#......
+//    #.........
+//    ....a.....
+//    ........a.
+//    .....a....
+//    ..#.......
+//    ......#...
+//    ..........
+//    ..........
+//
+// Antennas with different frequencies don't create antinodes; A and a count as
+// different frequencies. However, antinodes can occur at locations that contain
+// antennas. In this diagram, the lone antenna with frequency capital A creates
+// no antinodes but has a lowercase-a-frequency antinode at its location:
+//
+//    ..........
+//    ...#......
+//    #.........
+//    ....a.....
+//    ........a.
+//    .....a....
+//    ..#.......
+//    ......A...
+//    ..........
+//    ..........
+//
+// The first example has antennas with two different frequencies, so the
+// antinodes they create look like this, plus an antinode overlapping the
+// topmost A-frequency antenna:
+//
+//    ......#....#
+//    ...#....0...
+//    ....#0....#.
+//    ..#....0....
+//    ....0....#..
+//    .#....A.....
+//    ...#........
+//    #......#....
+//    ........A...
+//    .........A..
+//    ..........#.
+//    ..........#.
+//
+// Because the topmost A-frequency antenna overlaps with a 0-frequency antinode,
+// there are 14 total unique locations that contain an antinode within the
+// bounds of the map.
+//
+// Calculate the impact of the signal. How many unique locations within the
+// bounds of the map contain an antinode?
+use std::io::BufRead;
+use std::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::{Grid, Position};
+
+/// Solution of part one of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = AntennaMap;
+
+    type Answer = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the map of antennas.
+pub fn parse(reader: &mut dyn BufRead) -> Result<AntennaMap, AocError> {
+    return read_antennas(reader);
+}
+
+/// Count the unique locations containing an antinode.
+pub fn solve(antenna_map: &AntennaMap) -> Result<usize, AocError> {
+    return Ok(antenna_map.signal_impact());
+}
+
+/// Explain the impact by noting how many antinodes the antennas of each
+/// frequency have. Antinodes of different frequencies may coincide, so the
+/// counts can add up to more than the impact.
+pub fn explain(antenna_map: &AntennaMap, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for (frequency, antennas) in antenna_map.antennas.iter()
+        .sorted_by_key(|(&frequency, _)| frequency) {
+
+        trace.note(format!("frequency {}", *frequency as char),
+            format!("{} antinodes", antenna_map.antinodes(antennas).len()),
+            format!("from {} antennas", antennas.len()));
+    }
+    return Ok(());
+}
+
+/// A map containing antennas.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AntennaMap {
+
+    /// Mapping from frequency to set of antennas of that frequency.
+    antennas: HashMap<u8, HashSet<Position>>,
+
+    /// The full map, used for its bounds.
+    map: Grid<u8>
+
+}
+
+impl AntennaMap {
+
+    /// Compute the impact of the signal.
+    ///
+    /// The impact is the number of antinodes of the antennas.
+    fn signal_impact(&self) -> usize {
+        let mut antinodes = HashSet::new();
+
+        for (_, antennas) in self.antennas.iter() {
+            antinodes.extend(self.antinodes(antennas));
+        }
+
+        return antinodes.len();
+    }
+
+    /// Compute the antinodes of antennas of the same frequency.
+    fn antinodes(&self, antennas: &HashSet<Position>) -> HashSet<Position> {
+        let mut antinodes = HashSet::new();
+
+        for (&(x1, y1), &(x2, y2)) in antennas.iter().tuple_combinations() {
+            let (x1, y1) = (x1 as isize, y1 as isize);
+            let (x2, y2) = (x2 as isize, y2 as isize);
+            let diff_x = x1 - x2;
+            let diff_y = y1 - y2;
+            let antinode_1 = self.map.position(x1 + diff_x, y1 + diff_y);
+            let antinode_2 = self.map.position(x2 - diff_x, y2 - diff_y);
+            antinodes.extend(antinode_1);
+            antinodes.extend(antinode_2);
+        }
+
+        return antinodes;
+    }
+
+
+}
+
+/// Read antennas from the given reader.
+fn read_antennas(reader: &mut dyn BufRead) -> Result<AntennaMap, AocError> {
+    let map = Grid::parse(reader)?;
+    let mut antennas: HashMap<u8, HashSet<Position>> = HashMap::new();
+    for (position, &c) in map.iter() {
+        if c != b'.' {
+            antennas.entry(c).or_default().insert(position);
+        }
+    }
+
+    return Ok(AntennaMap {
+        antennas: antennas,
+        map: map
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that reading antenna map works.
+    #[test]
+    fn test_read_antennas() {
+        let input = concat![
+            "............\n",
+            "........0...\n",
+            ".....0......\n",
+            ".......0....\n",
+            "....0.......\n",
+            "......A.....\n",
+            "............\n",
+            "............\n",
+            "........A...\n",
+            ".........A..\n",
+            "............\n",
+            "............\n"
+        ];
+        let antennas = read_antennas(&mut input.as_bytes()).unwrap();
+
+        assert_eq!(antennas.antennas.len(), 2);
+        assert_eq!(antennas.antennas[&b'0'],
+            HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        assert_eq!(antennas.antennas[&b'A'],
+            HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        assert_eq!(antennas.map.width(), 12);
+        assert_eq!(antennas.map.height(), 12);
+    }
+
+    /// Test that computing the signal impact works.
+    #[test]
+    fn test_signal_impact() {
+        let mut antennas = HashMap::new();
+        antennas.insert(b'0', HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        antennas.insert(b'A', HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        let antenna_map = AntennaMap {
+            antennas: antennas,
+            map: Grid::from_rows(vec![vec![b'.'; 12]; 12]).unwrap()
+        };
+
+        assert_eq!(antenna_map.signal_impact(), 14);
+    }
+
+    /// Test that the antinodes of each frequency are explained.
+    #[test]
+    fn test_explain() {
+        let mut antennas = HashMap::new();
+        antennas.insert(b'0', HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        antennas.insert(b'A', HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        let antenna_map = AntennaMap {
+            antennas: antennas,
+            map: Grid::from_rows(vec![vec![b'.'; 12]; 12]).unwrap()
+        };
+        let mut trace = Trace::new();
+        explain(&antenna_map, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("frequency 0", "10 antinodes", "from 4 antennas"),
+            ("frequency A", "5 antinodes", "from 3 antennas")
+        ]);
+    }
+
+}
@@ -0,0 +1,245 @@
+// --- Part Two ---
+//
+// Watching over your shoulder as you work, one of The Historians asks if you
+// took the effects of resonant harmonics into your calculations.
+//
+// Whoops!
+//
+// After updating your model, it turns out that an antinode occurs at any grid
+// position exactly in line with at least two antennas of the same frequency,
+// regardless of distance. This means that some of the new antinodes will occur
+// at the position of each antenna (unless that antenna is the only one of its
+// frequency).
+//
+// So, these three T-frequency antennas now create many antinodes:
+//
+//    T....#....
+//    ...T......
+//    .T....#...
+//    .........#
+//    ..#.......
+//    ..........
+//    ...#......
+//    ..........
+//    ....#.....
+//    ..........
+//
+// In fact, the three T-frequency antennas are all exactly in line with two
+// antennas, so they are all also antinodes! This brings the total number of
+// antinodes in the above example to 9.
+//
+// The original example now has 34 antinodes, including the antinodes that
+// appear on every antenna:
+//
+//    ##....#....#
+//    .#.#....0...
+//    ..#.#0....#.
+//    ..##...0....
+//    ....0....#..
+//    .#...#A....#
+//    ...#..#.....
+//    #....#.#....
+//    ..#.....A...
+//    ....#....A..
+//    .#........#.
+//    ...#......##
+//
+// Calculate the impact of the signal using this updated model. How many unique
+// locations within the bounds of the map contain an antinode?
+use std::io::BufRead;
+use std::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::{Grid, Position};
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = AntennaMap;
+
+    type Answer = usize;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the map of antennas.
+pub fn parse(reader: &mut dyn BufRead) -> Result<AntennaMap, AocError> {
+    return read_antennas(reader);
+}
+
+/// Count the unique locations containing an antinode.
+pub fn solve(antenna_map: &AntennaMap) -> Result<usize, AocError> {
+    return Ok(antenna_map.signal_impact());
+}
+
+/// Explain the impact by noting how many antinodes the antennas of each
+/// frequency have. Antinodes of different frequencies may coincide, so the
+/// counts can add up to more than the impact.
+pub fn explain(antenna_map: &AntennaMap, trace: &mut Trace)
+    -> Result<(), AocError> {
+
+    for (frequency, antennas) in antenna_map.antennas.iter()
+        .sorted_by_key(|(&frequency, _)| frequency) {
+
+        trace.note(format!("frequency {}", *frequency as char),
+            format!("{} antinodes", antenna_map.antinodes(antennas).len()),
+            format!("from {} antennas", antennas.len()));
+    }
+    return Ok(());
+}
+
+/// A map containing antennas.
+#[derive(Debug, PartialEq, Eq)]
+pub struct AntennaMap {
+
+    /// Mapping from frequency to set of antennas of that frequency.
+    antennas: HashMap<u8, HashSet<Position>>,
+
+    /// The full map, used for its bounds.
+    map: Grid<u8>
+
+}
+
+impl AntennaMap {
+
+    /// Compute the impact of the signal.
+    ///
+    /// The impact is the number of antinodes of the antennas.
+    fn signal_impact(&self) -> usize {
+        let mut antinodes = HashSet::new();
+
+        for (_, antennas) in self.antennas.iter() {
+            antinodes.extend(self.antinodes(antennas));
+        }
+
+        return antinodes.len();
+    }
+
+    /// Compute the antinodes of antennas of the same frequency, every
+    /// position in line with two of them.
+    fn antinodes(&self, antennas: &HashSet<Position>) -> HashSet<Position> {
+        let mut antinodes = HashSet::new();
+
+        for (&(x1, y1), &(x2, y2)) in antennas.iter().tuple_combinations() {
+            let diff_x = x1 as isize - x2 as isize;
+            let diff_y = y1 as isize - y2 as isize;
+            for antinode in self.line((x1, y1), (diff_x, diff_y)) {
+                antinodes.insert(antinode);
+            }
+        }
+
+        return antinodes;
+    }
+
+
+    /// Compute a line starting at the start point going in both directions
+    /// applying the diff given. The vector might contain duplicate points.
+    fn line(&self, start: Position, diff: (isize, isize)) -> Vec<Position> {
+        let (diff_x, diff_y) = diff;
+        let forwards = self.map.walk(start, (diff_x, diff_y));
+        let backwards = self.map.walk(start, (-diff_x, -diff_y));
+        return forwards.chain(backwards).collect();
+    }
+
+}
+
+/// Read antennas from the given reader.
+fn read_antennas(reader: &mut dyn BufRead) -> Result<AntennaMap, AocError> {
+    let map = Grid::parse(reader)?;
+    let mut antennas: HashMap<u8, HashSet<Position>> = HashMap::new();
+    for (position, &c) in map.iter() {
+        if c != b'.' {
+            antennas.entry(c).or_default().insert(position);
+        }
+    }
+
+    return Ok(AntennaMap {
+        antennas: antennas,
+        map: map
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that reading antenna map works.
+    #[test]
+    fn test_read_antennas() {
+        let input = concat![
+            "............\n",
+            "........0...\n",
+            ".....0......\n",
+            ".......0....\n",
+            "....0.......\n",
+            "......A.....\n",
+            "............\n",
+            "............\n",
+            "........A...\n",
+            ".........A..\n",
+            "............\n",
+            "............\n"
+        ];
+        let antennas = read_antennas(&mut input.as_bytes()).unwrap();
+
+        assert_eq!(antennas.antennas.len(), 2);
+        assert_eq!(antennas.antennas[&b'0'],
+            HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        assert_eq!(antennas.antennas[&b'A'],
+            HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        assert_eq!(antennas.map.width(), 12);
+        assert_eq!(antennas.map.height(), 12);
+    }
+
+    /// Test that computing the signal impact works.
+    #[test]
+    fn test_signal_impact() {
+        let mut antennas = HashMap::new();
+        antennas.insert(b'0', HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        antennas.insert(b'A', HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        let antenna_map = AntennaMap {
+            antennas: antennas,
+            map: Grid::from_rows(vec![vec![b'.'; 12]; 12]).unwrap()
+        };
+
+        assert_eq!(antenna_map.signal_impact(), 34);
+    }
+
+    /// Test that the antinodes of each frequency are explained.
+    #[test]
+    fn test_explain() {
+        let mut antennas = HashMap::new();
+        antennas.insert(b'0', HashSet::from([(8, 1), (5, 2), (7, 3), (4, 4)]));
+        antennas.insert(b'A', HashSet::from([(6, 5), (8, 8), (9, 9)]));
+        let antenna_map = AntennaMap {
+            antennas: antennas,
+            map: Grid::from_rows(vec![vec![b'.'; 12]; 12]).unwrap()
+        };
+        let mut trace = Trace::new();
+        explain(&antenna_map, &mut trace).unwrap();
+
+        let notes: Vec<(&str, &str, &str)> = trace.notes().iter()
+            .map(|n| (n.record.as_str(), n.verdict.as_str(), n.reason.as_str()))
+            .collect();
+        assert_eq!(notes, vec![
+            ("frequency 0", "21 antinodes", "from 4 antennas"),
+            ("frequency A", "16 antinodes", "from 3 antennas")
+        ]);
+    }
+
+}
@@ -0,0 +1,97 @@
+// Parsing of the stones, shared by both parts.
+pub mod part1;
+pub mod part2;
+
+use std::io::BufRead;
+use crate::aoc::{AocError, ParseError};
+use crate::nom_helpers::{
+    records, Expected, InputError, IntParser, ParseResult
+};
+use nom::{
+    Err,
+    Needed,
+    branch::alt,
+    character::streaming::{char, line_ending},
+    combinator::value,
+    sequence::pair
+};
+
+/// Parse the numbers engraved on the stones.
+///
+/// The numbers are separated by single spaces on one line, which may end
+/// with a line ending. There is at least one stone.
+///
+/// # Errors
+///
+/// Will give AocError.ParseInputError if there are no stones, if a number
+/// does not fit in u64 or on anything else in the input.
+pub fn parse_stones(reader: &mut dyn BufRead) -> Result<Vec<u64>, AocError> {
+    let stones: Vec<u64> = records(reader, stone_parser())
+        .collect::<Result<_, _>>()?;
+    if stones.is_empty() {
+        let error = ParseError::expected_at("", 0, "a number");
+        return Err(AocError::ParseInputError(error));
+    }
+    return Ok(stones);
+}
+
+/// Create a parser of the number on a stone and the space or line ending
+/// after it. Nothing may follow the line ending, more input is asked for
+/// after it to make sure.
+fn stone_parser() -> impl FnMut(&str) -> ParseResult<'_, u64> {
+    let mut ended = false;
+    return move |input: &str| {
+        if ended && input.is_empty() {
+            return Err(Err::Incomplete(Needed::Unknown));
+        }
+        if ended {
+            return Err(Err::Error(InputError {
+                input: input,
+                expected: Expected::Context("the end of the stones")
+            }));
+        }
+        let space = value(false, char(' '));
+        let separator = alt((space, value(true, line_ending)));
+        let (input, (stone, line_ended)) =
+            pair(IntParser::new().streaming(true), separator)(input)?;
+        ended = line_ended;
+        return Ok((input, stone));
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that stones on a single line are parsed with any line ending.
+    #[test]
+    fn test_parse_stones() {
+        let parse = |input: &str| parse_stones(&mut input.as_bytes());
+        assert_eq!(parse("125 17\n"), Ok(vec![125, 17]));
+        assert_eq!(parse("125 17\r\n"), Ok(vec![125, 17]));
+        assert_eq!(parse("0 1"), Ok(vec![0, 1]));
+
+        let error = parse("1 99999999999999999999\n");
+        assert!(error.unwrap_err().to_string()
+            .contains("expected a number that fits in u64"));
+    }
+
+    /// Test that empty input, stones on several lines and extra spaces are
+    /// errors.
+    #[test]
+    fn test_parse_stones_error() {
+        let parse = |input: &str| parse_stones(&mut input.as_bytes());
+        assert_eq!(parse("").unwrap_err().to_string(), concat!(
+            "Parse error on line 1, column 1: ",
+            "expected a number, found end of input\n",
+            "1 | \n",
+            "  | ^"
+        ));
+        assert!(parse("\n").is_err());
+        assert!(parse("1 2\n3\n").is_err());
+        assert!(parse("1 2\n\n").is_err());
+        assert!(parse("1  2\n").is_err());
+        assert!(parse("1 2 \n").is_err());
+    }
+
+}
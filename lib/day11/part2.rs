@@ -0,0 +1,114 @@
+// --- Part Two ---
+//
+// The Historians sure are taking a long time. To be fair, the infinite
+// corridors are very large.
+//
+// How many stones would you have after blinking a total of 75 times?
+use std::io::BufRead;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::day11::parse_stones;
+use std::collections::HashMap;
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Vec<u64>;
+
+    type Answer = u64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the numbers engraved on the stones.
+///
+/// The numbers are separated by single spaces on one line.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Vec<u64>, AocError> {
+    return parse_stones(reader);
+}
+
+/// Count the number of stones after blinking 75 times.
+pub fn solve(stones: &Vec<u64>) -> Result<u64, AocError> {
+    let mut cache = HashMap::new();
+    let mut count = 0;
+    for stone in stones {
+        count += blink(*stone, 75, &mut cache);
+    }
+
+    return Ok(count);
+}
+
+/// Note the number of stones each stone becomes after blinking.
+pub fn explain(stones: &Vec<u64>, trace: &mut Trace) -> Result<(), AocError> {
+    let mut cache = HashMap::new();
+    for stone in stones {
+        trace.note(stone, format!("{} stones", blink(*stone, 75, &mut cache)),
+            "after 75 blinks");
+    }
+
+    return Ok(());
+}
+
+/// Cache of intermediate results.
+pub type Cache = HashMap<(u64, u64), u64>;
+
+/// Count the number of stones after blinking 'count' times.
+pub fn blink(stone: u64, count: u64, cache: &mut Cache) -> u64 {
+    let stone_count = if count == 0 {
+        1
+    } else if let Some(sc) = cache.get(&(stone, count)) {
+        *sc
+    } else if stone == 0 {
+        blink(1, count - 1, cache)
+    } else {
+        let str_stone = stone.to_string();
+        let len = str_stone.len();
+        if len.is_multiple_of(2) {
+            let left = str_stone[0..len / 2].parse().unwrap();
+            let right = str_stone[len / 2..].parse().unwrap();
+            blink(left, count - 1, cache) + blink(right, count - 1, cache)
+        } else {
+            blink(stone * 2024, count - 1, cache)
+        }
+    };
+
+    cache.insert((stone, count), stone_count);
+    return stone_count;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that blinking works as expected.
+    #[test]
+    fn test_blink() {
+        assert_eq!(blink(0, 1, &mut HashMap::new()), 1);
+        assert_eq!(blink(1, 1, &mut HashMap::new()), 1);
+        assert_eq!(blink(1, 2, &mut HashMap::new()), 2);
+        assert_eq!(blink(99, 2, &mut HashMap::new()), 2);
+        assert_eq!(blink(1299, 1, &mut HashMap::new()), 2);
+        assert_eq!(blink(1299, 2, &mut HashMap::new()), 4);
+        assert_eq!(blink(1299, 3, &mut HashMap::new()), 4);
+        assert_eq!(blink(1299, 4, &mut HashMap::new()), 6);
+        assert_eq!(blink(1299, 5, &mut HashMap::new()), 12);
+        assert_eq!(blink(125, 6, &mut HashMap::new())
+                   + blink(17, 6, &mut HashMap::new()), 22);
+        assert_eq!(blink(125, 25, &mut HashMap::new())
+                   + blink(17, 25, &mut HashMap::new()), 55312);
+    }
+
+}
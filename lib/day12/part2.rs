@@ -0,0 +1,406 @@
+// --- Part Two ---
+//
+// Fortunately, the Elves are trying to order so much fence that they qualify
+// for a bulk discount!
+//
+// Under the bulk discount, instead of using the perimeter to calculate the
+// price, you need to use the number of sides each region has. Each straight
+// section of fence counts as a side, regardless of how long it is.
+//
+// Consider this example again:
+//
+//    AAAA
+//    BBCD
+//    BBCC
+//    EEEC
+//
+// The region containing type A plants has 4 sides, as does each of the regions
+// containing plants of type B, D, and E. However, the more complex region
+// containing the plants of type C has 8 sides!
+//
+// Using the new method of calculating the per-region price by multiplying the
+// region's area by its number of sides, regions A through E have prices 16, 16,
+// 32, 4, and 12, respectively, for a total price of 80.
+//
+// The second example above (full of type X and O plants) would have a total
+// price of 436.
+//
+// Here's a map that includes an E-shaped region full of type E plants:
+//
+//    EEEEE
+//    EXXXX
+//    EEEEE
+//    EXXXX
+//    EEEEE
+//
+// The E-shaped region has an area of 17 and 12 sides for a price of 204.
+// Including the two regions full of type X plants, this map has a total price
+// of 236.
+//
+// This map has a total price of 368:
+//
+//    AAAAAA
+//    AAABBA
+//    AAABBA
+//    ABBAAA
+//    ABBAAA
+//    AAAAAA
+//
+// It includes two regions full of type B plants (each with 4 sides) and a
+// single region full of type A plants (with 4 sides on the outside and 8 more
+// sides on the inside, a total of 12 sides). Be especially careful when
+// counting the fence around regions like the one full of type A plants; in
+// particular, each section of fence has an in-side and an out-side, so the
+// fence does not connect across the middle of the region (where the two B
+// regions touch diagonally). (The Elves would have used the Möbius Fencing
+// Company instead, but their contract terms were too one-sided.)
+//
+// The larger example from before now has the following updated prices:
+//
+// * A region of R plants with price 12 * 10 = 120.
+// * A region of I plants with price 4 * 4 = 16.
+// * A region of C plants with price 14 * 22 = 308.
+// * A region of F plants with price 10 * 12 = 120.
+// * A region of V plants with price 13 * 10 = 130.
+// * A region of J plants with price 11 * 12 = 132.
+// * A region of C plants with price 1 * 4 = 4.
+// * A region of E plants with price 13 * 8 = 104.
+// * A region of I plants with price 14 * 16 = 224.
+// * A region of M plants with price 5 * 6 = 30.
+// * A region of S plants with price 3 * 6 = 18.
+//
+// Adding these together produces its new total price of 1206.
+//
+// What is the new total price of fencing all regions on your map?
+use std::io::BufRead;
+use crate::aoc::{AocError, Solution};
+use crate::aoc::explain::Trace;
+use crate::grid::Grid;
+
+/// Solution of part two of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {
+
+    type Input = Plots;
+
+    type Answer = u64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse(reader)
+    }
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {
+        solve(input)
+    }
+
+    fn explain(input: &Self::Input, trace: &mut Trace) -> Result<(), AocError> {
+        explain(input, trace)
+    }
+
+}
+
+/// Parse the map of garden plots.
+pub fn parse(reader: &mut dyn BufRead) -> Result<Plots, AocError> {
+    return Plots::parse(reader);
+}
+
+/// Compute the total price of fencing all regions with the bulk discount.
+pub fn solve(plots: &Plots) -> Result<u64, AocError> {
+    let mut plots = plots.clone();
+    let mut cost = 0;
+    for field in plots.find_fields() {
+        cost += field.sides * field.area;
+    }
+    return Ok(cost);
+}
+
+/// Note the price of fencing each region, its area times its number of sides.
+pub fn explain(plots: &Plots, trace: &mut Trace) -> Result<(), AocError> {
+    let mut plots = plots.clone();
+    for (i, field) in plots.find_fields().iter().enumerate() {
+        trace.note(format!("region {} ({})", i + 1, field.crop as char),
+            format!("price {}", field.sides * field.area),
+            format!("area {} * sides {}", field.area, field.sides));
+    }
+
+    return Ok(());
+}
+
+/// A field consists of an area and the number of sides of the field.
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Field {
+
+    /// Which crop is grown in the field.
+    crop: u8,
+
+    /// The area of the field.
+    area: u64,
+
+    /// Number of sides in the field.
+    sides: u64,
+
+}
+
+impl Field {
+
+    /// Construct a new field.
+    fn new() -> Self {
+        return Field {
+            crop: 0,
+            area: 0,
+            sides: 0,
+        };
+    }
+}
+
+/// A plot is part of a field and grows something.
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Plot {
+
+    /// Which crop is grown.
+    crop: u8,
+
+    /// If this plot is a part of a field, this is the ID of that field.
+    field_id: Option<usize>,
+
+}
+
+impl Plot {
+
+    /// Construct a new plot.
+    fn new(crop: u8) -> Self {
+        Plot {
+            crop: crop,
+            field_id: None,
+        }
+    }
+
+}
+
+/// Represents a grid of fields, a field is just a character representing some
+/// type of food.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Plots {
+
+    /// Fields in a 2D grid.
+    plots: Grid<Plot>
+
+}
+
+impl Plots {
+
+    /// Parse plots from the reader given.
+    fn parse(reader: &mut dyn BufRead) -> Result<Plots, AocError> {
+        return Ok(Plots {
+            plots: Grid::parse_with(reader, |c| Some(Plot::new(c)))?
+        });
+    }
+
+    /// Loop through all plots and annotate each one as belonging to a field.
+    ///
+    /// Returns the number of fields found.
+    fn annotate_plots(&mut self) -> usize {
+        let mut field_n = 0;
+        for (x, y) in self.plots.positions() {
+            if self.plots[(x, y)].field_id.is_none() {
+                let crop = self.plots[(x, y)].crop;
+                self.annotate_plot_rec(x, y, field_n, crop);
+                field_n += 1;
+            }
+        }
+
+        return field_n;
+    }
+
+    /// Annotate the given plot position as field field_id if crop matches. Do
+    /// so recursively for plot neighbours in order to color the whole field.
+    fn annotate_plot_rec(&mut self, x: usize, y: usize, field_id: usize,
+            crop: u8) {
+
+        let plot = &mut self.plots[(x, y)];
+        if plot.field_id.is_none() && plot.crop == crop {
+            plot.field_id = Some(field_id);
+            let neighbours: Vec<_> = self.plots.neighbours_4((x, y)).collect();
+            for (n_x, n_y) in neighbours {
+                self.annotate_plot_rec(n_x, n_y, field_id, crop);
+            }
+        }
+    }
+
+    /// Annotate all plots as part of a field and return the list of computed
+    /// fields.
+    fn find_fields(&mut self) -> Vec<Field> {
+        let field_count = self.annotate_plots();
+        if field_count == 0 {
+            return Vec::new();
+        }
+        let mut fields = vec![Field::new(); field_count];
+
+        for (x, y) in self.plots.positions() {
+            let field_id = self.plots[(x, y)].field_id
+                .expect("Should have been annotated before.");
+            let field: &mut Field = &mut fields[field_id];
+            field.crop = self.plots[(x, y)].crop;
+            field.area += 1;
+            field.sides += self.count_corners(x, y);
+        }
+
+        return fields;
+    }
+
+    /// Count the number of corners in the given position.
+    ///
+    /// Every corner of a field is also a side of it. A corner of the plot is an
+    /// outer corner of the field if neither plot next to it is part of the
+    /// field, and an inner corner if both are but the diagonal plot is not.
+    fn count_corners(&self, x: usize, y: usize) -> u64 {
+        let field_id = self.plots[(x, y)].field_id.unwrap();
+        let is_field = |step| self.plots.offset((x, y), step)
+            .is_some_and(|p| self.plots[p].field_id == Some(field_id));
+
+        let mut corners = 0;
+        for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+            let horizontal = is_field((dx, 0));
+            let vertical = is_field((0, dy));
+            if !horizontal && !vertical {
+                corners += 1;
+            }
+            if horizontal && vertical && !is_field((dx, dy)) {
+                corners += 1;
+            }
+        }
+
+        return corners;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that parsing plots works as expected.
+    #[test]
+    fn test_parse_valid() {
+        let input = concat![
+            "AAABB\n",
+            "AABBB\n",
+            "AABCC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "AAAAB\n",
+        ];
+        let plots = Plots::parse(&mut input.as_bytes()).unwrap();
+
+        assert_eq!(plots.plots.width(), 5);
+        assert_eq!(plots.plots.height(), 7);
+        assert_eq!(plots.plots.row(0).cloned().collect::<Vec<_>>(), vec![
+            Plot {
+                crop: b'A',
+                field_id: None,
+            },
+            Plot {
+                crop: b'A',
+                field_id: None,
+            },
+            Plot {
+                crop: b'A',
+                field_id: None,
+            },
+            Plot {
+                crop: b'B',
+                field_id: None,
+            },
+            Plot {
+                crop: b'B',
+                field_id: None,
+            },
+        ]);
+        assert_eq!(plots.plots.row(3).cloned().collect::<Vec<_>>(), vec![
+            Plot {
+                crop: b'C',
+                field_id: None,
+            },
+            Plot {
+                crop: b'C',
+                field_id: None,
+            },
+            Plot {
+                crop: b'C',
+                field_id: None,
+            },
+            Plot {
+                crop: b'C',
+                field_id: None,
+            },
+            Plot {
+                crop: b'C',
+                field_id: None,
+            },
+        ]);
+    }
+
+    /// Test that parsing where one line is shorter gives error.
+    #[test]
+    fn test_parse_too_short_line() {
+        let input = concat![
+            "AAABB\n",
+            "AABBB\n",
+            "AABC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "AAAAB\n",
+        ];
+        let err = Plots::parse(&mut input.as_bytes());
+        assert!(err.is_err());
+    }
+
+    /// Test that parsing where one line is longer gives error.
+    #[test]
+    fn test_parse_too_long_line() {
+        let input = concat![
+            "AAABB\n",
+            "AABBB\n",
+            "AABCB\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "AAAABC\n",
+        ];
+        let err = Plots::parse(&mut input.as_bytes());
+        assert!(err.is_err());
+    }
+
+    /// Test that finding fields works as expected.
+    #[test]
+    fn test_find_fields() {
+        let input = concat![
+            "AAABB\n",
+            "AABBB\n",
+            "AABCC\n",
+            "CCDCC\n",
+            "CCCCC\n",
+            "CCCCC\n",
+            "AAAAB\n",
+        ];
+        let mut plots = Plots::parse(&mut input.as_bytes()).unwrap();
+        let fields = plots.find_fields();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[0].area, 7);
+        assert_eq!(fields[0].sides, 6);
+        assert_eq!(fields[1].area, 6);
+        assert_eq!(fields[1].sides, 8);
+        assert_eq!(fields[2].area, 16);
+        assert_eq!(fields[2].sides, 8);
+        assert_eq!(fields[3].area, 1);
+        assert_eq!(fields[3].sides, 4);
+        assert_eq!(fields[4].area, 4);
+        assert_eq!(fields[4].sides, 4);
+        assert_eq!(fields[5].area, 1);
+        assert_eq!(fields[5].sides, 4);
+    }
+
+}
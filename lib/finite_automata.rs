@@ -1,3 +1,24 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod byte_set;
+mod compiled;
+mod keywords;
+mod language;
+mod nfa;
+mod pattern;
+mod render;
+mod search;
+mod serialize;
+mod tokenizer;
+
+pub use byte_set::ByteSet;
+pub use compiled::CompiledAutomata;
+pub use nfa::Nfa;
+pub use pattern::Pattern;
+pub use search::{FindIter, Match, MatchKind, Searcher};
+pub use tokenizer::{Captures, Tokenizer, TokenizerBuilder};
+
 /// A reference to a state in the finite state machine.
 pub type StateRef = usize;
 
@@ -22,11 +43,9 @@ impl FiniteAutomata {
     ///
     /// Will set the current_state as the given initial_state.
     pub fn new() -> FiniteAutomata {
-        let mut states = Vec::new();
-        states.push(State::new(0));
         return FiniteAutomata {
             current_state: 0,
-            states: states
+            states: vec![State::new(0)]
         };
     }
 
@@ -35,6 +54,41 @@ impl FiniteAutomata {
         self.current_state
     }
 
+    /// Go back to the initial state.
+    pub fn reset(&mut self) {
+        self.current_state = 0;
+    }
+
+    /// Test whether the current state is accepting.
+    pub fn accepts(&self) -> bool {
+        return self.states[self.current_state].accepting;
+    }
+
+    /// Test whether the current state belongs to the named group.
+    pub fn in_group(&self, name: &str) -> bool {
+        return self.states[self.current_state].in_group(name);
+    }
+
+    /// Get the indices of the keywords ending at the last byte read.
+    pub fn keywords(&self) -> &[usize] {
+        return self.states[self.current_state].keywords();
+    }
+
+    /// Mark a state as accepting or not accepting.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if state_ref could not
+    /// be found on the finite automata.
+    pub fn set_accepting(&mut self, state_ref: StateRef, accepting: bool)
+        -> Result<(), FiniteAutomataError> {
+
+        let state = self.find_state_mut(state_ref)
+            .ok_or(FiniteAutomataError::MissingState)?;
+        state.accepting = accepting;
+        return Ok(());
+    }
+
     /// Get list of states in the finite automata.
     pub fn states(&self) -> &Vec<State> {
         &self.states
@@ -63,6 +117,22 @@ impl FiniteAutomata {
         p: &'static dyn Fn(u8) -> bool, resulting_state_ref: StateRef)
         -> Result<(), FiniteAutomataError> {
 
+        return self.add_predicate(state_ref, p, resulting_state_ref);
+    }
+
+    /// Add a transition to a state taking any kind of predicate.
+    ///
+    /// Works like FiniteAutomata.add_transition but the predicate can also be
+    /// a ByteSet or a boxed closure capturing data only known at runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if either state_ref or
+    /// resulting_state_ref could not be found on the finite automata.
+    pub fn add_predicate(&mut self, state_ref : StateRef,
+        predicate: impl Into<Predicate>, resulting_state_ref: StateRef)
+        -> Result<(), FiniteAutomataError> {
+
         let state_count = self.states.len();
         if state_ref >= state_count || resulting_state_ref >= state_count {
             return Err(FiniteAutomataError::MissingState);
@@ -72,12 +142,34 @@ impl FiniteAutomata {
                 Err(FiniteAutomataError::MissingState)
             },
             Some(state) => {
-                state.add_transition(p, resulting_state_ref);
+                state.add_predicate(predicate, resulting_state_ref);
                 Ok(())
             }
         }
     }
 
+    /// Add a transition to a state with a label describing the predicate.
+    ///
+    /// Works like FiniteAutomata.add_predicate, the label is shown when the
+    /// automata is printed instead of the one derived from the predicate.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if either state_ref or
+    /// resulting_state_ref could not be found on the finite automata.
+    pub fn add_labelled_transition(&mut self, state_ref : StateRef,
+        predicate: impl Into<Predicate>, label: &str,
+        resulting_state_ref: StateRef) -> Result<(), FiniteAutomataError> {
+
+        if resulting_state_ref >= self.states.len() {
+            return Err(FiniteAutomataError::MissingState);
+        }
+        let state = self.find_state_mut(state_ref)
+            .ok_or(FiniteAutomataError::MissingState)?;
+        state.add_labelled_predicate(predicate, label, resulting_state_ref);
+        return Ok(());
+    }
+
     /// Add a transition to all states.
     ///
     /// Will add a transition to state resulting_state_ref from all state states
@@ -92,12 +184,28 @@ impl FiniteAutomata {
     pub fn add_transition_all(&mut self, p: &'static dyn Fn(u8) -> bool,
         resulting_state_ref: StateRef) -> Result<(), FiniteAutomataError> {
 
+        return self.add_predicate_all(p, resulting_state_ref);
+    }
+
+    /// Add a transition to all states taking any kind of predicate.
+    ///
+    /// Works like FiniteAutomata.add_transition_all but the predicate can also
+    /// be a ByteSet or a boxed closure capturing data only known at runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if resulting_state_ref
+    /// could not be found on the finite automata.
+    pub fn add_predicate_all(&mut self, predicate: impl Into<Predicate>,
+        resulting_state_ref: StateRef) -> Result<(), FiniteAutomataError> {
+
         let state_count = self.states.len();
         if resulting_state_ref >= state_count {
             return Err(FiniteAutomataError::MissingState);
         }
+        let predicate = predicate.into();
         for state in self.states.iter_mut() {
-            state.add_transition(p, resulting_state_ref);
+            state.add_predicate(predicate.clone(), resulting_state_ref);
         }
 
         return Ok(())
@@ -115,7 +223,7 @@ impl FiniteAutomata {
         let state = self.states.get(self.current_state)
             .expect("The current state can never refer to a missing state.");
         for transition in state.transitions.iter() {
-            if (transition.predicate)(byte) {
+            if transition.predicate.matches(byte) {
                 self.current_state = transition.result_state_ref;
                 return Ok(());
             }
@@ -124,6 +232,29 @@ impl FiniteAutomata {
         return Err(FiniteAutomataError::MissingTransition);
     }
 
+    /// Compile the automata into a minimal automata with a transition table.
+    ///
+    /// See CompiledAutomata.new.
+    pub fn compile(&self) -> CompiledAutomata {
+        return CompiledAutomata::new(self);
+    }
+
+    /// Evaluate the transitions of every state on all bytes.
+    ///
+    /// Gives the state reached from every state on every byte, or None if
+    /// there is no matching transition.
+    fn transition_table(&self) -> Vec<[Option<StateRef>; 256]> {
+        return self.states.iter().map(|state| {
+            let mut row = [None; 256];
+            for byte in 0..=u8::MAX {
+                row[byte as usize] = state.transitions.iter()
+                    .find(|t| t.predicate.matches(byte))
+                    .map(|t| t.result_state_ref);
+            }
+            row
+        }).collect();
+    }
+
     /// Find the state with the given state_ref.
     pub fn find_state(&self, state_ref: StateRef) -> Option<&State> {
         return self.states.get(state_ref);
@@ -138,6 +269,14 @@ impl FiniteAutomata {
 
 }
 
+impl Default for FiniteAutomata {
+
+    fn default() -> Self {
+        FiniteAutomata::new()
+    }
+
+}
+
 /// A state has a reference and a list of transitions to other states.
 pub struct State {
 
@@ -145,7 +284,16 @@ pub struct State {
     state_ref: StateRef,
 
     /// Transitions to other states.
-    transitions: Vec<Transition>
+    transitions: Vec<Transition>,
+
+    /// Whether the state is accepting.
+    accepting: bool,
+
+    /// Names of the groups the state belongs to.
+    groups: Vec<String>,
+
+    /// Indices of the keywords found when entering the state.
+    keywords: Vec<usize>
 
 }
 
@@ -155,7 +303,10 @@ impl State {
     pub fn new(state_ref: StateRef) -> State {
         return State {
             state_ref: state_ref,
-            transitions: Vec::new()
+            transitions: Vec::new(),
+            accepting: false,
+            groups: Vec::new(),
+            keywords: Vec::new()
         };
     }
 
@@ -164,6 +315,33 @@ impl State {
         self.state_ref
     }
 
+    /// Test whether the state is accepting.
+    pub fn is_accepting(&self) -> bool {
+        self.accepting
+    }
+
+    /// Get the names of the groups the state belongs to.
+    ///
+    /// States built from a pattern belong to the named groups that matched the
+    /// byte leading to the state.
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// Test whether the state belongs to the named group.
+    pub fn in_group(&self, name: &str) -> bool {
+        return self.groups.iter().any(|group| group == name);
+    }
+
+    /// Get the indices of the keywords ending when the state is entered.
+    ///
+    /// Only states of automatas built by FiniteAutomata.from_keywords, where
+    /// the longest keyword comes first, or by Pattern::compile_search_all,
+    /// where the keywords are the indices of the patterns, have keywords.
+    pub fn keywords(&self) -> &[usize] {
+        &self.keywords
+    }
+
     /// Add another transition to this state.
     ///
     /// The transition is added to the end of the list of transitions and will
@@ -171,26 +349,170 @@ impl State {
     pub fn add_transition(&mut self, p: &'static dyn Fn(u8) -> bool,
         result_state_ref: StateRef) {
 
+        self.add_predicate(p, result_state_ref);
+    }
+
+    /// Add another transition to this state taken when the predicate matches.
+    ///
+    /// Like State.add_transition the transition is only tried if all other
+    /// transitions fail matching.
+    pub fn add_predicate(&mut self, predicate: impl Into<Predicate>,
+        result_state_ref: StateRef) {
+
+        let predicate = predicate.into();
+        let label = predicate.label();
+        self.add_labelled_predicate(predicate, &label, result_state_ref);
+    }
+
+    /// Add another transition to this state with a label describing the
+    /// predicate.
+    pub fn add_labelled_predicate(&mut self, predicate: impl Into<Predicate>,
+        label: &str, result_state_ref: StateRef) {
+
         let transition = Transition {
-            predicate: p,
+            predicate: predicate.into(),
+            label: label.to_string(),
             result_state_ref: result_state_ref
         };
         self.transitions.push(transition);
     }
 
+    /// Get the transitions of the state in the order they are tried.
+    pub fn transitions(&self) -> &[Transition] {
+        &self.transitions
+    }
+
 }
 
 /// A transition is a predicate matching a byte and a result state if matching.
-struct Transition {
+pub struct Transition {
 
     /// Predicate matching whether the transition should be taken.
-    predicate : &'static dyn Fn(u8) -> bool,
+    predicate : Predicate,
+
+    /// Human readable description of the predicate.
+    label: String,
 
     /// The state to transition to on success.
     result_state_ref : StateRef
 
 }
 
+impl Transition {
+
+    /// Test whether the transition is taken on the byte.
+    pub fn matches(&self, byte: u8) -> bool {
+        return self.predicate.matches(byte);
+    }
+
+    /// Get the human readable description of the predicate.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the state the transition goes to.
+    pub fn result_state_ref(&self) -> StateRef {
+        self.result_state_ref
+    }
+
+}
+
+/// Decides which bytes a transition is taken on.
+///
+/// Can be made from a static function reference, a boxed closure or a ByteSet.
+/// Closures made with Predicate.function or boxed as Send and Sync can be
+/// shared with other threads, other functions can not.
+#[derive(Clone)]
+pub enum Predicate {
+
+    /// Taken on bytes the function returns true for.
+    Function(Arc<dyn Fn(u8) -> bool + Send + Sync>),
+
+    /// Taken on bytes the function returns true for, for functions that are
+    /// not Send and Sync, like static references and boxed closures.
+    Local(Rc<dyn Fn(u8) -> bool>),
+
+    /// Taken on bytes in the set.
+    Bytes(ByteSet)
+
+}
+
+impl Predicate {
+
+    /// Construct a predicate from a closure, which may capture runtime data.
+    pub fn function(f: impl Fn(u8) -> bool + Send + Sync + 'static)
+        -> Predicate {
+
+        return Predicate::Function(Arc::new(f));
+    }
+
+    /// Test whether the predicate matches the byte.
+    pub fn matches(&self, byte: u8) -> bool {
+        match self {
+            Predicate::Function(f) => f(byte),
+            Predicate::Local(f) => f(byte),
+            Predicate::Bytes(set) => set.contains(byte)
+        }
+    }
+
+    /// Describe the bytes matched by the predicate.
+    ///
+    /// A set of one byte is shown as 'u', the set of all bytes as *, a set of
+    /// more than half the bytes as the negated class [^...] and other sets as
+    /// a class like [0-9]. Functions can not be inspected and are shown as fn.
+    pub fn label(&self) -> String {
+        let set = match self {
+            Predicate::Function(_) | Predicate::Local(_) => {
+                return "fn".to_string();
+            },
+            Predicate::Bytes(set) => set
+        };
+        if set.len() == 256 {
+            return "*".to_string();
+        } else if set.len() == 1 {
+            let byte = set.iter().next().expect("The set has a byte.");
+            return format!("'{}'", (byte as char).escape_default());
+        } else if set.len() > 128 {
+            return format!("{:?}", set.complement()).replacen('[', "[^", 1);
+        } else {
+            return format!("{:?}", set);
+        }
+    }
+
+}
+
+impl From<&'static dyn Fn(u8) -> bool> for Predicate {
+
+    fn from(f: &'static dyn Fn(u8) -> bool) -> Self {
+        return Predicate::Local(Rc::new(f));
+    }
+
+}
+
+impl From<Box<dyn Fn(u8) -> bool>> for Predicate {
+
+    fn from(f: Box<dyn Fn(u8) -> bool>) -> Self {
+        return Predicate::Local(Rc::from(f));
+    }
+
+}
+
+impl From<Box<dyn Fn(u8) -> bool + Send + Sync>> for Predicate {
+
+    fn from(f: Box<dyn Fn(u8) -> bool + Send + Sync>) -> Self {
+        return Predicate::Function(Arc::from(f));
+    }
+
+}
+
+impl From<ByteSet> for Predicate {
+
+    fn from(set: ByteSet) -> Self {
+        return Predicate::Bytes(set);
+    }
+
+}
+
 /// Errors returned by finite automatas.
 #[derive(Debug, PartialEq)]
 pub enum FiniteAutomataError {
@@ -200,12 +522,20 @@ pub enum FiniteAutomataError {
 
     /// Error given when given a byte that doesn't match any transition in the
     /// current state.
-    MissingTransition
+    MissingTransition,
+
+    /// Error given when saving an automata with a function as predicate.
+    FunctionPredicate,
+
+    /// Error given when building a tokenizer from an automata finding the
+    /// keyword of a rule the tokenizer does not have.
+    MissingRule
 
 }
 
 #[cfg(test)]
 mod tests {
+    use std::thread;
     use super::*;
 
     /// Test that adding a state works as expected.
@@ -253,7 +583,6 @@ mod tests {
     #[test]
     pub fn test_add_transition_from_missing_state() {
         let mut automata = FiniteAutomata::new();
-        automata.current_state;
         let state_2 = automata.add_state();
         let predicate = &|c| c == 0x78;
         let error = automata.add_transition(44, predicate, state_2);
@@ -315,4 +644,84 @@ mod tests {
         assert_eq!(state_3, automata.current_state);
     }
 
+    /// Test that accepting states are reported.
+    #[test]
+    pub fn test_accepts() {
+        let mut automata = FiniteAutomata::new();
+        let state_1 = automata.current_state;
+        let state_2 = automata.add_state();
+        automata.add_transition(state_1, &|_| true, state_2)
+            .expect("Both states exist, this should be fine.");
+        automata.set_accepting(state_2, true)
+            .expect("The state exists, this should be fine.");
+
+        assert!(!automata.accepts());
+        automata.transition(b'x').expect("We have a rule matching 'x'.");
+        assert!(automata.accepts());
+        automata.reset();
+        assert!(!automata.accepts());
+        assert_eq!(state_1, automata.current_state);
+
+        let error = automata.set_accepting(44, true);
+        assert_eq!(Err(FiniteAutomataError::MissingState), error);
+    }
+
+    /// Test that predicates can be built from data only known at runtime.
+    #[test]
+    pub fn test_add_predicate() {
+        let word = String::from("ab");
+        let mut automata = FiniteAutomata::new();
+        let mut state = automata.current_state;
+        for byte in word.bytes() {
+            let next = automata.add_state();
+            let predicate: Box<dyn Fn(u8) -> bool> =
+                Box::new(move |c| c == byte);
+            automata.add_predicate(state, predicate, next)
+                .expect("Both states exist, this should be fine.");
+            state = next;
+        }
+        automata.add_predicate_all(ByteSet::byte(b'.'), 0)
+            .expect("The state exists, this should be fine.");
+
+        automata.transition(b'a').expect("We have a rule matching 'a'.");
+        automata.transition(b'b').expect("We have a rule matching 'b'.");
+        assert_eq!(state, automata.current_state);
+        automata.transition(b'.').expect("We have a rule matching '.'.");
+        assert_eq!(0, automata.current_state);
+        let error = automata.transition(b'b');
+        assert_eq!(error, Err(FiniteAutomataError::MissingTransition));
+    }
+
+    /// Test that closures of predicates made with Predicate.function can be
+    /// sent to other threads, as can compiled automatas using them.
+    #[test]
+    pub fn test_predicate_threads() {
+        let byte = b'a';
+        let predicate = Predicate::function(move |c| c == byte);
+        let Predicate::Function(f) = predicate.clone() else {
+            panic!("Closures are Send and Sync functions.");
+        };
+        let matches = thread::spawn(move || f(b'a'))
+            .join().expect("The thread should not panic.");
+        assert!(matches);
+
+        let boxed: Box<dyn Fn(u8) -> bool + Send + Sync> =
+            Box::new(|c| c == b'b');
+        assert!(matches!(Predicate::from(boxed), Predicate::Function(_)));
+
+        let mut automata = FiniteAutomata::new();
+        let state = automata.add_state();
+        automata.add_predicate(0, predicate, state)
+            .expect("Both states exist, this should be fine.");
+        automata.set_accepting(state, true)
+            .expect("The state exists, this should be fine.");
+        let compiled = automata.compile();
+        let accepts = thread::spawn(move || {
+            let mut compiled = compiled;
+            compiled.transition(b'a').expect("We have a rule matching 'a'.");
+            compiled.accepts()
+        }).join().expect("The thread should not panic.");
+        assert!(accepts);
+    }
+
 }
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A set of bytes.
+///
+/// Used as the label of transitions in finite automatas. Stored as a bitmap
+/// with one bit per possible byte value so tests and set operations are cheap.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByteSet {
+
+    /// Bit b % 64 of word b / 64 is set if byte b is in the set.
+    words: [u64; 4]
+
+}
+
+impl ByteSet {
+
+    /// Construct the empty set.
+    pub fn empty() -> ByteSet {
+        return ByteSet { words: [0; 4] };
+    }
+
+    /// Construct the set of all bytes.
+    pub fn all() -> ByteSet {
+        return ByteSet { words: [u64::MAX; 4] };
+    }
+
+    /// Construct a set containing a single byte.
+    pub fn byte(byte: u8) -> ByteSet {
+        let mut set = ByteSet::empty();
+        set.insert(byte);
+        return set;
+    }
+
+    /// Construct a set containing all bytes in the range.
+    pub fn range(range: RangeInclusive<u8>) -> ByteSet {
+        return range.collect();
+    }
+
+    /// Construct the set of bytes matching the predicate.
+    pub fn from_predicate(predicate: &dyn Fn(u8) -> bool) -> ByteSet {
+        return (0..=u8::MAX).filter(|&b| predicate(b)).collect();
+    }
+
+    /// Add a byte to the set.
+    pub fn insert(&mut self, byte: u8) {
+        self.words[(byte / 64) as usize] |= 1 << (byte % 64);
+    }
+
+    /// Remove a byte from the set.
+    pub fn remove(&mut self, byte: u8) {
+        self.words[(byte / 64) as usize] &= !(1 << (byte % 64));
+    }
+
+    /// Test whether the byte is in the set.
+    pub fn contains(&self, byte: u8) -> bool {
+        return self.words[(byte / 64) as usize] & (1 << (byte % 64)) != 0;
+    }
+
+    /// Test whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        return self.words.iter().all(|&w| w == 0);
+    }
+
+    /// Number of bytes in the set.
+    pub fn len(&self) -> usize {
+        return self.words.iter().map(|w| w.count_ones() as usize).sum();
+    }
+
+    /// The set of bytes in either set.
+    pub fn union(&self, other: &ByteSet) -> ByteSet {
+        return self.combine(other, |a, b| a | b);
+    }
+
+    /// The set of bytes in both sets.
+    pub fn intersection(&self, other: &ByteSet) -> ByteSet {
+        return self.combine(other, |a, b| a & b);
+    }
+
+    /// The set of bytes in this set but not in the other.
+    pub fn difference(&self, other: &ByteSet) -> ByteSet {
+        return self.combine(other, |a, b| a & !b);
+    }
+
+    /// The set of bytes not in this set.
+    pub fn complement(&self) -> ByteSet {
+        return self.combine(&ByteSet::all(), |a, b| !a & b);
+    }
+
+    /// The set as the shortest list of increasing ranges of bytes.
+    pub fn ranges(&self) -> Vec<RangeInclusive<u8>> {
+        let mut ranges = Vec::new();
+        let mut bytes = self.iter().peekable();
+        while let Some(start) = bytes.next() {
+            let mut end = start;
+            while bytes.peek() == Some(&end.wrapping_add(1)) {
+                end = bytes.next().unwrap();
+            }
+            ranges.push(start..=end);
+        }
+        return ranges;
+    }
+
+    /// Iterate over the bytes in the set in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item=u8> + '_ {
+        return (0..=u8::MAX).filter(|&b| self.contains(b));
+    }
+
+    /// Combine the words of two sets using the given operation.
+    fn combine(&self, other: &ByteSet, op: fn(u64, u64) -> u64) -> ByteSet {
+        let words = std::array::from_fn(|i| op(self.words[i], other.words[i]));
+        return ByteSet { words: words };
+    }
+
+}
+
+impl FromIterator<u8> for ByteSet {
+
+    fn from_iter<I: IntoIterator<Item=u8>>(iter: I) -> Self {
+        let mut set = ByteSet::empty();
+        for byte in iter {
+            set.insert(byte);
+        }
+        return set;
+    }
+
+}
+
+impl From<&[u8]> for ByteSet {
+
+    fn from(bytes: &[u8]) -> Self {
+        return bytes.iter().copied().collect();
+    }
+
+}
+
+/// Debug formatting shows the set as ranges of bytes, like [0-9a-f].
+impl fmt::Debug for ByteSet {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for range in self.ranges() {
+            let (start, end) = (*range.start(), *range.end());
+            write!(f, "{}", (start as char).escape_default())?;
+            if end > start {
+                if end > start + 1 {
+                    write!(f, "-")?;
+                }
+                write!(f, "{}", (end as char).escape_default())?;
+            }
+        }
+        write!(f, "]")
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that bytes can be added to and removed from a set.
+    #[test]
+    fn test_insert_remove() {
+        let mut set = ByteSet::empty();
+        assert!(set.is_empty());
+        set.insert(b'a');
+        set.insert(200);
+        assert!(set.contains(b'a'));
+        assert!(set.contains(200));
+        assert!(!set.contains(b'b'));
+        assert_eq!(set.len(), 2);
+        set.remove(b'a');
+        assert!(!set.contains(b'a'));
+        assert_eq!(set.iter().collect::<Vec<u8>>(), vec![200]);
+    }
+
+    /// Test that set operations work as expected.
+    #[test]
+    fn test_operations() {
+        let digits = ByteSet::range(b'0'..=b'9');
+        let low = ByteSet::range(b'0'..=b'4');
+        assert_eq!(digits.len(), 10);
+        assert_eq!(low.union(&digits), digits);
+        assert_eq!(low.intersection(&digits), low);
+        assert_eq!(digits.difference(&low), ByteSet::range(b'5'..=b'9'));
+        assert_eq!(digits.complement().len(), 246);
+        assert_eq!(digits.complement().complement(), digits);
+        assert_eq!(ByteSet::all().len(), 256);
+        assert_eq!(ByteSet::from_predicate(&|c| c.is_ascii_digit()), digits);
+        assert_eq!(digits.union(&ByteSet::byte(b'a')).ranges(),
+            vec![b'0'..=b'9', b'a'..=b'a']);
+    }
+
+    /// Test that sets are shown as ranges.
+    #[test]
+    fn test_debug() {
+        let set: ByteSet = b"0123456789ab\n".as_slice().into();
+        assert_eq!(format!("{:?}", set), "[\\n0-9ab]");
+        assert_eq!(format!("{:?}", ByteSet::all()), "[\\u{0}-\\u{ff}]");
+    }
+
+}
@@ -0,0 +1,348 @@
+use std::collections::VecDeque;
+use std::mem;
+use super::{FiniteAutomata, FiniteAutomataError, StateRef};
+
+/// A minimal deterministic automata with a transition table.
+///
+/// Built from a FiniteAutomata by FiniteAutomata::compile. Every state has a
+/// table with the next state for each of the 256 bytes so a transition is a
+/// single lookup instead of trying predicates one by one. States that can not
+/// be told apart by any input are merged, so the automata has the fewest
+/// states possible.
+pub struct CompiledAutomata {
+
+    /// Reference of the current state.
+    current_state: StateRef,
+
+    /// The next state for every state and byte.
+    table: Vec<[StateRef; 256]>,
+
+    /// Whether each state is accepting.
+    accepting: Vec<bool>,
+
+    /// Names of the groups each state belongs to.
+    groups: Vec<Vec<String>>,
+
+    /// Indices of the keywords found when entering each state.
+    keywords: Vec<Vec<usize>>,
+
+    /// State reached on a byte the original automata had no transition for,
+    /// if there are any such bytes.
+    missing: Option<StateRef>
+
+}
+
+impl CompiledAutomata {
+
+    /// Compile the automata into a minimal table driven automata.
+    ///
+    /// The predicates of every state are evaluated on all bytes, using the
+    /// first matching transition like FiniteAutomata.transition does. Missing
+    /// transitions go to an extra state only reached that way. Unreachable
+    /// states are dropped and the rest are minimised with Hopcroft's algorithm
+    /// keeping accepting states and states of different groups or keywords
+    /// apart.
+    pub fn new(automata: &FiniteAutomata) -> CompiledAutomata {
+        let states = automata.states();
+        let missing = states.len();
+        let mut table: Vec<[StateRef; 256]> = automata.transition_table()
+            .iter()
+            .map(|row| row.map(|next| next.unwrap_or(missing)))
+            .collect();
+        table.push([missing; 256]);
+
+        let reachable = reachable(&table);
+        let key = |state: StateRef| match states.get(state) {
+            Some(state) => {
+                let mut groups = state.groups.clone();
+                groups.sort();
+                Some((state.accepting, groups, state.keywords.clone()))
+            },
+            None => None
+        };
+        let classes = minimise(&table, &reachable, key);
+
+        // Number the classes in the order they are first reached so the
+        // initial state stays 0.
+        let mut refs = vec![None; table.len()];
+        let mut representatives = Vec::new();
+        for &state in reachable.iter() {
+            if refs[classes[state]].is_none() {
+                refs[classes[state]] = Some(representatives.len());
+                representatives.push(state);
+            }
+        }
+        let new_ref = |state: StateRef| refs[classes[state]]
+            .expect("All reachable states have been numbered.");
+
+        return CompiledAutomata {
+            current_state: 0,
+            table: representatives.iter()
+                .map(|&state| table[state].map(new_ref))
+                .collect(),
+            accepting: representatives.iter()
+                .map(|&state| states.get(state).is_some_and(|s| s.accepting))
+                .collect(),
+            groups: representatives.iter()
+                .map(|&state| states.get(state)
+                    .map_or(Vec::new(), |s| s.groups.clone()))
+                .collect(),
+            keywords: representatives.iter()
+                .map(|&state| states.get(state)
+                    .map_or(Vec::new(), |s| s.keywords.clone()))
+                .collect(),
+            missing: reachable.contains(&missing).then(|| new_ref(missing))
+        };
+    }
+
+    /// Get the current state of the automata.
+    pub fn current_state(&self) -> StateRef {
+        self.current_state
+    }
+
+    /// Get the number of states in the automata.
+    pub fn state_count(&self) -> usize {
+        return self.table.len();
+    }
+
+    /// Get the state reached from the given state on the byte.
+    pub fn next_state(&self, state_ref: StateRef, byte: u8) -> StateRef {
+        return self.table[state_ref][byte as usize];
+    }
+
+    /// Go back to the initial state.
+    pub fn reset(&mut self) {
+        self.current_state = 0;
+    }
+
+    /// Transition from the current state on the given byte.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingTransition if the automata
+    /// this was compiled from had no transition matching the byte. The current
+    /// state is left unchanged in that case.
+    pub fn transition(&mut self, byte: u8) -> Result<(), FiniteAutomataError> {
+        let next = self.next_state(self.current_state, byte);
+        if Some(next) == self.missing {
+            return Err(FiniteAutomataError::MissingTransition);
+        }
+
+        self.current_state = next;
+        return Ok(());
+    }
+
+    /// Test whether the current state is accepting.
+    pub fn accepts(&self) -> bool {
+        return self.accepting[self.current_state];
+    }
+
+    /// Get the names of the groups the given state belongs to.
+    pub fn groups(&self, state_ref: StateRef) -> &[String] {
+        &self.groups[state_ref]
+    }
+
+    /// Get the indices of the keywords ending at the last byte read.
+    pub fn keywords(&self) -> &[usize] {
+        &self.keywords[self.current_state]
+    }
+
+    /// Test whether the current state belongs to the named group.
+    pub fn in_group(&self, name: &str) -> bool {
+        return self.groups[self.current_state].iter()
+            .any(|group| group == name);
+    }
+
+}
+
+/// Find the states reachable from state 0 in breadth first order.
+fn reachable(table: &[[StateRef; 256]]) -> Vec<StateRef> {
+    let mut seen = vec![false; table.len()];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([0]);
+    seen[0] = true;
+    while let Some(state) = queue.pop_front() {
+        order.push(state);
+        for &next in table[state].iter() {
+            if !seen[next] {
+                seen[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    return order;
+}
+
+/// Partition the given states into classes of equivalent states using
+/// Hopcroft's algorithm, giving the class of every state.
+///
+/// States start out in the same class if they have the same key. Classes are
+/// then split until all states in a class go to the same class on every byte.
+/// A class is split in time proportional to its states going to the splitter,
+/// keeping the O(n log n) bound of the algorithm.
+fn minimise<K: PartialEq>(table: &[[StateRef; 256]], states: &[StateRef],
+    key: impl Fn(StateRef) -> K) -> Vec<usize> {
+
+    let mut class = vec![usize::MAX; table.len()];
+    // Position of every state in the list of its class.
+    let mut index = vec![0; table.len()];
+    let mut classes: Vec<Vec<StateRef>> = Vec::new();
+    let mut keys: Vec<K> = Vec::new();
+    for &state in states {
+        let k = key(state);
+        match keys.iter().position(|other| *other == k) {
+            Some(i) => {
+                class[state] = i;
+                index[state] = classes[i].len();
+                classes[i].push(state);
+            },
+            None => {
+                class[state] = classes.len();
+                index[state] = 0;
+                classes.push(vec![state]);
+                keys.push(k);
+            }
+        }
+    }
+
+    // Predecessors of every state on every byte.
+    let mut inverse = vec![vec![Vec::new(); 256]; table.len()];
+    for &state in states {
+        for byte in 0..256 {
+            inverse[table[state][byte]][byte].push(state);
+        }
+    }
+
+    let mut waiting: Vec<usize> = (0..classes.len()).collect();
+    let mut is_waiting = vec![true; classes.len()];
+    // The predecessors on the current byte, per class, and the classes with
+    // any. Cleared after every byte.
+    let mut marked = vec![false; table.len()];
+    let mut marked_in: Vec<Vec<StateRef>> = vec![Vec::new(); classes.len()];
+    let mut touched = Vec::new();
+    while let Some(splitter) = waiting.pop() {
+        is_waiting[splitter] = false;
+        let splitter_states = classes[splitter].clone();
+        #[allow(clippy::needless_range_loop,
+            reason = "every byte splits, indexing the predecessors per state")]
+        for byte in 0..256 {
+            for &state in splitter_states.iter() {
+                for &previous in inverse[state][byte].iter() {
+                    if !marked[previous] {
+                        marked[previous] = true;
+                        if marked_in[class[previous]].is_empty() {
+                            touched.push(class[previous]);
+                        }
+                        marked_in[class[previous]].push(previous);
+                    }
+                }
+            }
+
+            for split in touched.drain(..) {
+                let inside = mem::take(&mut marked_in[split]);
+                for &state in inside.iter() {
+                    marked[state] = false;
+                }
+                if inside.len() == classes[split].len() {
+                    continue;
+                }
+
+                // Move the marked states out of the class, filling their
+                // places with the last states of the class.
+                let new_class = classes.len();
+                for &state in inside.iter() {
+                    let i = index[state];
+                    classes[split].swap_remove(i);
+                    if let Some(&moved) = classes[split].get(i) {
+                        index[moved] = i;
+                    }
+                    class[state] = new_class;
+                }
+                for (i, &state) in inside.iter().enumerate() {
+                    index[state] = i;
+                }
+                // A class already waiting is replaced by both halves, else
+                // waiting for the smaller half is enough.
+                let in_smaller = inside.len() <= classes[split].len();
+                let smaller = if is_waiting[split] || in_smaller {
+                    new_class
+                } else {
+                    split
+                };
+                classes.push(inside);
+                marked_in.push(Vec::new());
+                is_waiting.push(false);
+                if !is_waiting[smaller] {
+                    is_waiting[smaller] = true;
+                    waiting.push(smaller);
+                }
+            }
+        }
+    }
+
+    return class;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finite_automata::Pattern;
+
+    /// Test that the compiled automata behaves like the original.
+    #[test]
+    fn test_compile() {
+        let pattern = Pattern::parse("(?<n>[0-9]+)(,(?<n>[0-9]+))*").unwrap();
+        let mut automata = pattern.compile();
+        let mut compiled = automata.compile();
+        for byte in "12,3,,4".bytes() {
+            automata.transition(byte).unwrap();
+            compiled.transition(byte).unwrap();
+            assert_eq!(automata.accepts(), compiled.accepts());
+            assert_eq!(automata.in_group("n"), compiled.in_group("n"));
+        }
+    }
+
+    /// Test that equivalent states are merged.
+    #[test]
+    fn test_minimise() {
+        let automata = Pattern::parse("a(b|c)d|e[bc]d").unwrap().compile();
+        let compiled = automata.compile();
+        assert!(compiled.state_count() < automata.states().len());
+        // Initial, after a or e, after the class, accepting and dead.
+        assert_eq!(compiled.state_count(), 5);
+        assert_eq!(compiled.next_state(0, b'a'), compiled.next_state(0, b'e'));
+    }
+
+    /// Test that classes split many times end up minimal.
+    #[test]
+    fn test_minimise_splits() {
+        let automata = Pattern::parse("[ab]*a[ab][ab][ab]").unwrap().compile();
+        let mut compiled = automata.compile();
+        // The last four bytes read and the dead state.
+        assert_eq!(compiled.state_count(), 17);
+        for byte in "babaabbbaab".bytes() {
+            compiled.transition(byte).unwrap();
+        }
+        assert!(!compiled.accepts());
+        compiled.transition(b'a').unwrap();
+        assert!(compiled.accepts());
+    }
+
+    /// Test that missing transitions are still reported.
+    #[test]
+    fn test_missing_transition() {
+        let mut automata = FiniteAutomata::new();
+        let state = automata.add_state();
+        automata.add_transition(0, &|c| c == b'x', state).unwrap();
+        let mut compiled = automata.compile();
+
+        let error = compiled.transition(b'y');
+        assert_eq!(error, Err(FiniteAutomataError::MissingTransition));
+        assert_eq!(compiled.current_state(), 0);
+        let next = compiled.next_state(0, b'x');
+        compiled.transition(b'x').unwrap();
+        assert_eq!(compiled.current_state(), next);
+        assert_eq!(compiled.state_count(), 3);
+    }
+
+}
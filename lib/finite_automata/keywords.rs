@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use super::{ByteSet, FiniteAutomata, StateRef};
+
+impl FiniteAutomata {
+
+    /// Build an automata finding all occurrences of the keywords using the
+    /// Aho-Corasick algorithm.
+    ///
+    /// The keywords are put in a trie where every state is the prefix of a
+    /// keyword. Each state has a failure link to the state of the longest
+    /// proper suffix of its prefix that is also in the trie, which is where the
+    /// search continues when the next byte does not extend the prefix. The
+    /// failure links are resolved up front so the automata has a transition
+    /// on every byte from every state.
+    ///
+    /// After reading a byte the current state is accepting if any keyword ends
+    /// at the byte and FiniteAutomata.keywords gives the indices of those
+    /// keywords. Matches can overlap, both "he" and "she" are found in "she".
+    ///
+    /// Empty keywords are skipped and never found, as they would match at every
+    /// position. The indices of the other keywords are left as given.
+    pub fn from_keywords(keywords: &[&[u8]]) -> FiniteAutomata {
+        // The trie, missing children are None.
+        let mut children: Vec<[Option<StateRef>; 256]> = vec![[None; 256]];
+        let mut found: Vec<Vec<usize>> = vec![Vec::new()];
+        for (i, keyword) in keywords.iter().enumerate() {
+            if keyword.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for &byte in keyword.iter() {
+                state = match children[state][byte as usize] {
+                    Some(child) => child,
+                    None => {
+                        children.push([None; 256]);
+                        found.push(Vec::new());
+                        let child = children.len() - 1;
+                        children[state][byte as usize] = Some(child);
+                        child
+                    }
+                };
+            }
+            found[state].push(i);
+        }
+
+        // Visit the states breadth first so the failure link of a state and
+        // all transitions of that state are known when they are needed.
+        let mut fail = vec![0; children.len()];
+        let mut next = vec![[0; 256]; children.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            let mut keywords = found[fail[state]].clone();
+            if state != 0 {
+                found[state].append(&mut keywords);
+            }
+            for byte in 0..256 {
+                match children[state][byte] {
+                    Some(child) => {
+                        fail[child] = if state == 0 {
+                            0
+                        } else {
+                            next[fail[state]][byte]
+                        };
+                        next[state][byte] = child;
+                        queue.push_back(child);
+                    },
+                    None if state == 0 => next[state][byte] = 0,
+                    None => next[state][byte] = next[fail[state]][byte]
+                }
+            }
+        }
+
+        let mut automata = FiniteAutomata::new();
+        for _ in 1..children.len() {
+            automata.add_state();
+        }
+        for (state, targets) in next.iter().enumerate() {
+            let mut sets: Vec<(StateRef, ByteSet)> = Vec::new();
+            for byte in 0..=u8::MAX {
+                let target = targets[byte as usize];
+                match sets.iter_mut().find(|(t, _)| *t == target) {
+                    Some((_, set)) => set.insert(byte),
+                    None => sets.push((target, ByteSet::byte(byte)))
+                }
+            }
+            let state = &mut automata.states[state];
+            for (target, set) in sets {
+                state.add_predicate(set, target);
+            }
+            state.accepting = !found[state.state_ref].is_empty();
+            state.keywords = found[state.state_ref].clone();
+        }
+
+        return automata;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the automata on the input, giving the keywords ending at each byte.
+    fn keywords(automata: &mut FiniteAutomata, input: &str)
+        -> Vec<Vec<usize>> {
+
+        return input.bytes().map(|byte| {
+            automata.transition(byte).expect("Keyword automatas are total.");
+            automata.keywords().to_vec()
+        }).collect();
+    }
+
+    /// Test that overlapping keywords are found through failure links.
+    #[test]
+    fn test_from_keywords() {
+        let mut automata = FiniteAutomata::from_keywords(
+            &[b"he", b"she", b"his", b"hers"]);
+        assert_eq!(keywords(&mut automata, "ushers"), vec![
+            vec![],
+            vec![],
+            vec![],
+            vec![1, 0],
+            vec![],
+            vec![3]
+        ]);
+        assert!(automata.accepts());
+    }
+
+    /// Test that keywords inside other keywords are found.
+    #[test]
+    fn test_from_keywords_nested() {
+        let mut automata = FiniteAutomata::from_keywords(
+            &[b"XMAS", b"SAMX", b"A"]);
+        let found: Vec<usize> = keywords(&mut automata, "XMASAMX").iter()
+            .map(|keywords| keywords.len())
+            .collect();
+        assert_eq!(found, vec![0, 0, 1, 1, 1, 0, 1]);
+    }
+
+    /// Test that empty keywords are skipped without shifting the indices of
+    /// the other keywords.
+    #[test]
+    fn test_from_keywords_empty() {
+        let mut automata = FiniteAutomata::from_keywords(&[b"", b"ab", b""]);
+        assert!(!automata.accepts());
+        assert_eq!(keywords(&mut automata, "xab"), vec![
+            vec![],
+            vec![],
+            vec![1]
+        ]);
+
+        let mut automata = FiniteAutomata::from_keywords(&[b""]);
+        assert_eq!(keywords(&mut automata, "ab"), vec![vec![], vec![]]);
+        assert_eq!(automata.states.len(), 1);
+    }
+
+}
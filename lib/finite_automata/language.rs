@@ -0,0 +1,208 @@
+use std::collections::{HashMap, VecDeque};
+use super::{ByteSet, FiniteAutomata, StateRef};
+
+/// Operations on the languages accepted by automatas.
+///
+/// The language of an automata is the set of byte strings taking it from the
+/// initial state to an accepting state. A missing transition rejects the
+/// string. The automatas built here have a transition on every byte from
+/// every state, and do not keep the groups or keywords of the automatas they
+/// are built from.
+impl FiniteAutomata {
+
+    /// Build an automata accepting the strings accepted by either automata.
+    pub fn union(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        return self.product(other, |a, b| a || b);
+    }
+
+    /// Build an automata accepting the strings accepted by both automatas.
+    pub fn intersection(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        return self.product(other, |a, b| a && b);
+    }
+
+    /// Build an automata accepting the strings accepted by this automata but
+    /// not by the other.
+    pub fn difference(&self, other: &FiniteAutomata) -> FiniteAutomata {
+        return self.product(other, |a, b| a && !b);
+    }
+
+    /// Build an automata accepting exactly the byte strings this automata
+    /// rejects.
+    ///
+    /// Missing transitions are first completed with a dead state, so strings
+    /// rejected that way are accepted by the complement.
+    pub fn complement(&self) -> FiniteAutomata {
+        return self.product(&FiniteAutomata::new(), |a, _| !a);
+    }
+
+    /// Test whether the automata accepts no strings at all.
+    pub fn is_empty(&self) -> bool {
+        return self.shortest_accepted().is_none();
+    }
+
+    /// Find the shortest string accepted by the automata.
+    ///
+    /// Of the shortest strings the one with the smallest bytes is given.
+    pub fn shortest_accepted(&self) -> Option<Vec<u8>> {
+        let table = self.transition_table();
+        let mut previous: Vec<Option<(StateRef, u8)>> = vec![None; table.len()];
+        let mut seen = vec![false; table.len()];
+        let mut queue = VecDeque::from([0]);
+        seen[0] = true;
+
+        while let Some(state) = queue.pop_front() {
+            if self.states[state].accepting {
+                let mut string = Vec::new();
+                let mut current = state;
+                while let Some((before, byte)) = previous[current] {
+                    string.push(byte);
+                    current = before;
+                }
+                string.reverse();
+                return Some(string);
+            }
+
+            for byte in 0..=u8::MAX {
+                if let Some(next) = table[state][byte as usize] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        previous[next] = Some((state, byte));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        return None;
+    }
+
+    /// Test whether the automatas accept exactly the same strings.
+    pub fn is_equivalent(&self, other: &FiniteAutomata) -> bool {
+        return self.counterexample(other).is_none();
+    }
+
+    /// Find the shortest string accepted by one automata but not the other,
+    /// or None if they accept the same strings.
+    pub fn counterexample(&self, other: &FiniteAutomata) -> Option<Vec<u8>> {
+        return self.product(other, |a, b| a != b).shortest_accepted();
+    }
+
+    /// Run both automatas side by side.
+    ///
+    /// Every state of the result is a pair of states of the two automatas,
+    /// where None is the dead state reached on a missing transition. A pair
+    /// is accepting if accept gives true for whether its states are.
+    fn product(&self, other: &FiniteAutomata, accept: fn(bool, bool) -> bool)
+        -> FiniteAutomata {
+
+        let tables = [self.transition_table(), other.transition_table()];
+        let automatas = [self, other];
+        let accepting = |pair: [Option<StateRef>; 2]| accept(
+            pair[0].is_some_and(|s| automatas[0].states[s].accepting),
+            pair[1].is_some_and(|s| automatas[1].states[s].accepting));
+
+        let mut automata = FiniteAutomata::new();
+        let initial = [Some(0), Some(0)];
+        let mut pairs = vec![initial];
+        let mut refs = HashMap::from([(initial, 0)]);
+
+        let mut current = 0;
+        while current < pairs.len() {
+            let pair = pairs[current];
+            automata.states[current].accepting = accepting(pair);
+
+            // Bytes leading to the same pair share a transition.
+            let mut targets: Vec<([Option<StateRef>; 2], ByteSet)> = Vec::new();
+            for byte in 0..=u8::MAX {
+                let next = [0, 1].map(|i| pair[i]
+                    .and_then(|state| tables[i][state][byte as usize]));
+                match targets.iter_mut().find(|(t, _)| *t == next) {
+                    Some((_, set)) => set.insert(byte),
+                    None => targets.push((next, ByteSet::byte(byte)))
+                }
+            }
+
+            for (target, set) in targets {
+                let target_ref = match refs.get(&target) {
+                    Some(&target_ref) => target_ref,
+                    None => {
+                        let target_ref = automata.add_state();
+                        refs.insert(target, target_ref);
+                        pairs.push(target);
+                        target_ref
+                    }
+                };
+                automata.states[current].add_predicate(set, target_ref);
+            }
+
+            current = current + 1;
+        }
+
+        return automata;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finite_automata::Pattern;
+
+    /// Compile the pattern.
+    fn compile(pattern: &str) -> FiniteAutomata {
+        return Pattern::parse(pattern).unwrap().compile();
+    }
+
+    /// Test whether the automata accepts the string.
+    fn accepts(automata: &FiniteAutomata, string: &str) -> bool {
+        let mut automata = automata.product(&FiniteAutomata::new(), |a, _| a);
+        for byte in string.bytes() {
+            automata.transition(byte).unwrap();
+        }
+        return automata.accepts();
+    }
+
+    /// Test that the products accept the right strings.
+    #[test]
+    fn test_union_intersection_complement() {
+        let a = compile("a+b?");
+        let b = compile("ab|b");
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+        let complement = a.complement();
+
+        for (string, in_a, in_b) in [("a", true, false), ("ab", true, true),
+            ("b", false, true), ("", false, false), ("ba", false, false)] {
+
+            assert_eq!(accepts(&union, string), in_a || in_b);
+            assert_eq!(accepts(&intersection, string), in_a && in_b);
+            assert_eq!(accepts(&difference, string), in_a && !in_b);
+            assert_eq!(accepts(&complement, string), !in_a);
+        }
+    }
+
+    /// Test that empty languages are detected.
+    #[test]
+    fn test_is_empty() {
+        assert!(FiniteAutomata::new().is_empty());
+        assert!(compile("a+").intersection(&compile("b+")).is_empty());
+        assert!(!compile("a+").is_empty());
+        assert_eq!(compile("(ab)+c|x?[yz]").shortest_accepted(),
+            Some(b"y".to_vec()));
+    }
+
+    /// Test that equivalent automatas are recognised and that counterexamples
+    /// are given for automatas that differ.
+    #[test]
+    fn test_is_equivalent() {
+        let mul = compile("mul\\([0-9]+,[0-9]+\\)");
+        assert!(mul.is_equivalent(&compile("mul\\([0-9][0-9]*,[0-9]+\\)")));
+        assert!(mul.is_equivalent(&mul.complement().complement()));
+
+        let loose = compile("mul\\([0-9]*,[0-9]+\\)");
+        assert!(!mul.is_equivalent(&loose));
+        assert_eq!(mul.counterexample(&loose), Some(b"mul(,0)".to_vec()));
+    }
+
+}
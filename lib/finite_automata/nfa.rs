@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use super::{ByteSet, FiniteAutomata, FiniteAutomataError, Predicate, StateRef};
+
+/// A nondeterministic finite automata.
+///
+/// Unlike FiniteAutomata a state can have any number of transitions matching
+/// the same byte, and epsilon transitions that are taken without reading a
+/// byte. The automata is in a set of states at once, every transition matching
+/// a byte is taken from every active state. This makes it possible to match
+/// overlapping patterns without caring about the order of transitions.
+pub struct Nfa {
+
+    /// References of the active states, sorted and closed under epsilon
+    /// transitions.
+    current_states: Vec<StateRef>,
+
+    /// All states.
+    states: Vec<NfaState>
+
+}
+
+/// A state of a nondeterministic finite automata.
+struct NfaState {
+
+    /// Transitions taken when reading a byte.
+    transitions: Vec<(Predicate, StateRef)>,
+
+    /// Transitions taken without reading a byte.
+    epsilon: Vec<StateRef>,
+
+    /// Whether the state is accepting.
+    accepting: bool,
+
+    /// Names of the groups the state belongs to.
+    groups: Vec<String>,
+
+    /// Indices of the keywords found when entering the state.
+    keywords: Vec<usize>
+
+}
+
+impl Nfa {
+
+    /// Create a new nondeterministic automata with a single initial state 0.
+    pub fn new() -> Nfa {
+        return Nfa {
+            current_states: vec![0],
+            states: vec![NfaState::new()]
+        };
+    }
+
+    /// Get the references of the active states in increasing order.
+    pub fn current_states(&self) -> &[StateRef] {
+        &self.current_states
+    }
+
+    /// Add a state to the automata.
+    pub fn add_state(&mut self) -> StateRef {
+        self.states.push(NfaState::new());
+        return self.states.len() - 1;
+    }
+
+    /// Add a transition from state state_ref to state resulting_state_ref
+    /// taken on bytes matching the predicate p.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if either state_ref or
+    /// resulting_state_ref could not be found on the automata.
+    pub fn add_transition(&mut self, state_ref: StateRef,
+        p: &'static dyn Fn(u8) -> bool, resulting_state_ref: StateRef)
+        -> Result<(), FiniteAutomataError> {
+
+        return self.add_predicate(state_ref, p, resulting_state_ref);
+    }
+
+    /// Add a transition from state state_ref to state resulting_state_ref
+    /// taken on bytes matching any kind of predicate, like a ByteSet or a
+    /// boxed closure.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if either state_ref or
+    /// resulting_state_ref could not be found on the automata.
+    pub fn add_predicate(&mut self, state_ref: StateRef,
+        predicate: impl Into<Predicate>, resulting_state_ref: StateRef)
+        -> Result<(), FiniteAutomataError> {
+
+        self.check_state(resulting_state_ref)?;
+        self.check_state(state_ref)?;
+        self.states[state_ref].transitions
+            .push((predicate.into(), resulting_state_ref));
+        return Ok(());
+    }
+
+    /// Add an epsilon transition from state state_ref to state
+    /// resulting_state_ref.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if either state_ref or
+    /// resulting_state_ref could not be found on the automata.
+    pub fn add_epsilon(&mut self, state_ref: StateRef,
+        resulting_state_ref: StateRef) -> Result<(), FiniteAutomataError> {
+
+        self.check_state(resulting_state_ref)?;
+        self.check_state(state_ref)?;
+        self.states[state_ref].epsilon.push(resulting_state_ref);
+        self.current_states = self.closure(self.current_states.clone());
+        return Ok(());
+    }
+
+    /// Mark a state as accepting or not accepting.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if state_ref could not
+    /// be found on the automata.
+    pub fn set_accepting(&mut self, state_ref: StateRef, accepting: bool)
+        -> Result<(), FiniteAutomataError> {
+
+        self.check_state(state_ref)?;
+        self.states[state_ref].accepting = accepting;
+        return Ok(());
+    }
+
+    /// Add a state to the named group.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if state_ref could not
+    /// be found on the automata.
+    pub fn add_group(&mut self, state_ref: StateRef, name: &str)
+        -> Result<(), FiniteAutomataError> {
+
+        self.check_state(state_ref)?;
+        let groups = &mut self.states[state_ref].groups;
+        if !groups.iter().any(|group| group == name) {
+            groups.push(name.to_string());
+        }
+        return Ok(());
+    }
+
+    /// Mark the state as the end of the keyword with the given index.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.MissingState if state_ref could not
+    /// be found on the automata.
+    pub fn add_keyword(&mut self, state_ref: StateRef, keyword: usize)
+        -> Result<(), FiniteAutomataError> {
+
+        self.check_state(state_ref)?;
+        let keywords = &mut self.states[state_ref].keywords;
+        if !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+        return Ok(());
+    }
+
+    /// Go back to the initial state.
+    pub fn reset(&mut self) {
+        self.current_states = self.closure(vec![0]);
+    }
+
+    /// Transition every active state on the given byte.
+    ///
+    /// If no active state has a transition matching the byte the automata
+    /// ends up in no states at all and stays there until it is reset.
+    pub fn transition(&mut self, byte: u8) {
+        self.current_states = self.step(&self.current_states, byte);
+    }
+
+    /// Test whether any active state is accepting.
+    pub fn accepts(&self) -> bool {
+        return self.current_states.iter()
+            .any(|&state| self.states[state].accepting);
+    }
+
+    /// Test whether any active state belongs to the named group.
+    pub fn in_group(&self, name: &str) -> bool {
+        return self.current_states.iter()
+            .any(|&state| self.states[state].groups.iter().any(|g| g == name));
+    }
+
+    /// Convert the automata to a deterministic automata using the subset
+    /// construction.
+    ///
+    /// Every state of the result corresponds to a set of states of this
+    /// automata, state 0 to the set containing the initial state. A state is
+    /// accepting if any state in its set is, belongs to all groups of the
+    /// states in its set and has their keywords in increasing order. The
+    /// result has a transition on every byte from every state, the empty set
+    /// becomes a dead state that is never left.
+    pub fn determinize(&self) -> FiniteAutomata {
+        let mut automata = FiniteAutomata::new();
+        let initial = self.closure(vec![0]);
+        let mut subsets = vec![initial.clone()];
+        let mut refs = HashMap::from([(initial, 0)]);
+
+        let mut current = 0;
+        while current < subsets.len() {
+            let subset = subsets[current].clone();
+            let state = &mut automata.states[current];
+            for &nfa_state in subset.iter() {
+                let nfa_state = &self.states[nfa_state];
+                state.accepting = state.accepting || nfa_state.accepting;
+                for group in nfa_state.groups.iter() {
+                    if !state.in_group(group) {
+                        state.groups.push(group.clone());
+                    }
+                }
+                state.keywords.extend(nfa_state.keywords.iter());
+            }
+            state.keywords.sort();
+            state.keywords.dedup();
+
+            // Bytes leading to the same set of states share a transition.
+            let mut targets: Vec<(Vec<StateRef>, ByteSet)> = Vec::new();
+            for byte in 0..=u8::MAX {
+                let next = self.step(&subset, byte);
+                match targets.iter_mut().find(|(t, _)| *t == next) {
+                    Some((_, set)) => set.insert(byte),
+                    None => targets.push((next, ByteSet::byte(byte)))
+                }
+            }
+
+            for (target, set) in targets {
+                let target_ref = match refs.get(&target) {
+                    Some(&target_ref) => target_ref,
+                    None => {
+                        let target_ref = automata.add_state();
+                        refs.insert(target.clone(), target_ref);
+                        subsets.push(target);
+                        target_ref
+                    }
+                };
+                automata.states[current]
+                    .add_predicate(set, target_ref);
+            }
+
+            current = current + 1;
+        }
+
+        return automata;
+    }
+
+    /// Give an error if the state does not exist.
+    fn check_state(&self, state_ref: StateRef)
+        -> Result<(), FiniteAutomataError> {
+
+        if state_ref >= self.states.len() {
+            return Err(FiniteAutomataError::MissingState);
+        }
+        return Ok(());
+    }
+
+    /// Compute the states reached from the given states on the byte.
+    fn step(&self, states: &[StateRef], byte: u8) -> Vec<StateRef> {
+        let next = states.iter()
+            .flat_map(|&state| self.states[state].transitions.iter())
+            .filter(|(predicate, _)| predicate.matches(byte))
+            .map(|&(_, target)| target)
+            .collect();
+        return self.closure(next);
+    }
+
+    /// Extend the states with all states reachable from them through epsilon
+    /// transitions. The result is sorted and without duplicates.
+    fn closure(&self, mut states: Vec<StateRef>) -> Vec<StateRef> {
+        states.sort();
+        states.dedup();
+        let mut stack = states.clone();
+        while let Some(state) = stack.pop() {
+            for &next in self.states[state].epsilon.iter() {
+                if let Err(i) = states.binary_search(&next) {
+                    states.insert(i, next);
+                    stack.push(next);
+                }
+            }
+        }
+        return states;
+    }
+
+}
+
+impl Default for Nfa {
+
+    fn default() -> Self {
+        Self::new()
+    }
+
+}
+
+impl NfaState {
+
+    /// Create a state without transitions.
+    fn new() -> NfaState {
+        return NfaState {
+            transitions: Vec::new(),
+            epsilon: Vec::new(),
+            accepting: false,
+            groups: Vec::new(),
+            keywords: Vec::new()
+        };
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build automata accepting both XMAS and SAMX anywhere in the input
+    /// without taking care of the order of transitions.
+    fn xmas_samx() -> Nfa {
+        let mut nfa = Nfa::new();
+        nfa.add_transition(0, &|_| true, 0).unwrap();
+        for word in [b"XMAS", b"SAMX"] {
+            let mut state = nfa.add_state();
+            nfa.add_epsilon(0, state).unwrap();
+            for &byte in word.iter() {
+                let next = nfa.add_state();
+                nfa.add_predicate(state, ByteSet::byte(byte), next).unwrap();
+                state = next;
+            }
+            nfa.set_accepting(state, true).unwrap();
+        }
+        return nfa;
+    }
+
+    /// Test that epsilon transitions are followed and that several states can
+    /// be active at once.
+    #[test]
+    fn test_transition() {
+        let mut nfa = Nfa::new();
+        let a = nfa.add_state();
+        let b = nfa.add_state();
+        nfa.add_epsilon(0, a).unwrap();
+        nfa.add_transition(0, &|c| c == b'x', b).unwrap();
+        nfa.add_transition(a, &|c| c == b'x', a).unwrap();
+        nfa.set_accepting(b, true).unwrap();
+        assert_eq!(nfa.current_states(), &[0, a]);
+        assert!(!nfa.accepts());
+
+        nfa.transition(b'x');
+        assert_eq!(nfa.current_states(), &[a, b]);
+        assert!(nfa.accepts());
+        nfa.transition(b'x');
+        assert_eq!(nfa.current_states(), &[a]);
+        nfa.transition(b'y');
+        assert!(nfa.current_states().is_empty());
+        nfa.reset();
+        assert_eq!(nfa.current_states(), &[0, a]);
+
+        let error = nfa.add_epsilon(0, 44);
+        assert_eq!(error, Err(FiniteAutomataError::MissingState));
+    }
+
+    /// Test that overlapping words are all found.
+    #[test]
+    fn test_overlapping() {
+        let mut nfa = xmas_samx();
+        let mut found = 0;
+        for byte in "XMASAMXMAS".bytes() {
+            nfa.transition(byte);
+            if nfa.accepts() {
+                found = found + 1;
+            }
+        }
+        assert_eq!(found, 3);
+    }
+
+    /// Test that the determinized automata accepts the same input.
+    #[test]
+    fn test_determinize() {
+        let mut nfa = xmas_samx();
+        let mut dfa = nfa.determinize();
+        for byte in "..XMASAMXMASSAMXX.SAM".bytes() {
+            nfa.transition(byte);
+            dfa.transition(byte).expect("Determinized automatas are total.");
+            assert_eq!(nfa.accepts(), dfa.accepts());
+        }
+
+        let mut dead = Nfa::new().determinize();
+        dead.transition(b'x').unwrap();
+        assert_eq!(dead.states().len(), 2);
+        assert_eq!(dead.current_state(), 1);
+    }
+
+}
@@ -0,0 +1,509 @@
+use nom::{
+    Err,
+    Parser,
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, none_of, one_of},
+    combinator::{cut, map, opt, value, verify},
+    error::context,
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair},
+};
+use crate::aoc::AocError;
+use crate::nom_helpers::{Expected, InputError, ParseResult, parse_all};
+use super::{ByteSet, FiniteAutomata, FiniteAutomataError, Nfa, StateRef};
+
+/// A regex-like pattern over bytes that can be compiled into a
+/// FiniteAutomata.
+///
+/// Patterns are either built with the constructors below or parsed from a
+/// string with Pattern::parse. States of the compiled automata remember the
+/// named groups that matched the byte leading to them, which is what allows
+/// the automata to tell the parts of a match apart while it is running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+
+    /// Matches the empty string.
+    Empty,
+
+    /// Matches a single byte in the set.
+    Class(ByteSet),
+
+    /// Matches the patterns one after another.
+    Concat(Vec<Pattern>),
+
+    /// Matches any one of the patterns.
+    Alternation(Vec<Pattern>),
+
+    /// Matches the pattern zero or more times.
+    Star(Box<Pattern>),
+
+    /// Matches the pattern one or more times.
+    Plus(Box<Pattern>),
+
+    /// Matches the pattern zero or one time.
+    Optional(Box<Pattern>),
+
+    /// Matches the pattern and names the bytes it matched.
+    Group(String, Box<Pattern>)
+
+}
+
+impl Pattern {
+
+    /// Pattern matching exactly the given bytes.
+    pub fn literal(bytes: &[u8]) -> Pattern {
+        if let [byte] = bytes {
+            return Pattern::Class(ByteSet::byte(*byte));
+        }
+        let bytes = bytes.iter().map(|&b| Pattern::Class(ByteSet::byte(b)));
+        return Pattern::Concat(bytes.collect());
+    }
+
+    /// Pattern matching a single byte in the set.
+    pub fn class(set: ByteSet) -> Pattern {
+        return Pattern::Class(set);
+    }
+
+    /// Pattern matching this pattern followed by the other.
+    pub fn then(self, other: Pattern) -> Pattern {
+        return match self {
+            Pattern::Concat(mut patterns) => {
+                patterns.push(other);
+                Pattern::Concat(patterns)
+            },
+            pattern => Pattern::Concat(vec![pattern, other])
+        };
+    }
+
+    /// Pattern matching either this pattern or the other.
+    pub fn or(self, other: Pattern) -> Pattern {
+        return match self {
+            Pattern::Alternation(mut patterns) => {
+                patterns.push(other);
+                Pattern::Alternation(patterns)
+            },
+            pattern => Pattern::Alternation(vec![pattern, other])
+        };
+    }
+
+    /// Pattern matching this pattern zero or more times.
+    pub fn star(self) -> Pattern {
+        return Pattern::Star(Box::new(self));
+    }
+
+    /// Pattern matching this pattern one or more times.
+    pub fn plus(self) -> Pattern {
+        return Pattern::Plus(Box::new(self));
+    }
+
+    /// Pattern matching this pattern zero or one time.
+    pub fn optional(self) -> Pattern {
+        return Pattern::Optional(Box::new(self));
+    }
+
+    /// Pattern matching this pattern as the named group.
+    pub fn named(self, name: &str) -> Pattern {
+        return Pattern::Group(name.to_string(), Box::new(self));
+    }
+
+    /// Parse a pattern from a string.
+    ///
+    /// The syntax is a small subset of regular expressions:
+    ///
+    ///    abc        the literal bytes a, b and c
+    ///    .          any byte
+    ///    [a-z_]     any byte in the class, [^a-z] any byte not in it
+    ///    \( \n      escaped special character, newline, tab (\t) or CR (\r)
+    ///               where the special characters are \|*+?()[].-^
+    ///    p*         p zero or more times, p+ one or more, p? zero or one
+    ///    p|q        either p or q
+    ///    (p)        grouping
+    ///    (?<name>p) named group, named by letters, digits and _
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError pointing at the offending character
+    /// if the pattern is malformed, uses an unknown escape like \d or has a
+    /// reversed range like [z-a].
+    pub fn parse(pattern: &str) -> Result<Pattern, AocError> {
+        return parse_all(parse_alternation, pattern);
+    }
+
+    /// Build a nondeterministic automata matching the pattern from the start
+    /// of the input.
+    ///
+    /// The automata is in an accepting state after reading a full match.
+    pub fn to_nfa(&self) -> Nfa {
+        let mut nfa = Nfa::new();
+        let initial = 0;
+        let (start, end) = build(&mut nfa, self, &mut Vec::new())
+            .expect("Only states that were just added are referenced.");
+        nfa.add_epsilon(initial, start)
+            .expect("Both states exist, this should be fine.");
+        nfa.set_accepting(end, true)
+            .expect("The state exists, this should be fine.");
+        return nfa;
+    }
+
+    /// Compile the pattern into an automata matching it from the start of the
+    /// input.
+    ///
+    /// The automata has a transition for every byte in every state, once no
+    /// match is possible it stays in a dead state. States reached after
+    /// reading a full match are accepting.
+    pub fn compile(&self) -> FiniteAutomata {
+        return self.to_nfa().determinize();
+    }
+
+    /// Compile the pattern into an automata searching for it anywhere in the
+    /// input.
+    ///
+    /// A new match can start at every byte so the automata is in an accepting
+    /// state exactly when a match ends at the byte just read, this includes
+    /// overlapping matches.
+    pub fn compile_search(&self) -> FiniteAutomata {
+        let mut nfa = self.to_nfa();
+        nfa.add_predicate(0, ByteSet::all(), 0)
+            .expect("The initial state always exists.");
+        return nfa.determinize();
+    }
+
+    /// Compile the patterns into one automata searching for all of them
+    /// anywhere in the input.
+    ///
+    /// Works like Pattern.compile_search for each pattern at once. After
+    /// reading a byte FiniteAutomata.keywords gives the indices of the
+    /// patterns with a match ending at the byte, in increasing order. Unlike
+    /// the groups of a state this only includes patterns that fully matched.
+    pub fn compile_search_all(patterns: &[Pattern]) -> FiniteAutomata {
+        let mut nfa = Nfa::new();
+        nfa.add_predicate(0, ByteSet::all(), 0)
+            .expect("The initial state always exists.");
+        for (i, pattern) in patterns.iter().enumerate() {
+            let (start, end) = build(&mut nfa, pattern, &mut Vec::new())
+                .expect("Only states that were just added are referenced.");
+            nfa.add_epsilon(0, start)
+                .and_then(|_| nfa.set_accepting(end, true))
+                .and_then(|_| nfa.add_keyword(end, i))
+                .expect("Both states exist, this should be fine.");
+        }
+        return nfa.determinize();
+    }
+
+}
+
+/// Add states matching the pattern to the automata, returning the start and
+/// end state.
+///
+/// This is Thompson's construction. The end state of a byte transition is only
+/// ever entered through that transition, it is added to the groups the
+/// transition is inside.
+fn build(nfa: &mut Nfa, pattern: &Pattern, groups: &mut Vec<String>)
+    -> Result<(StateRef, StateRef), FiniteAutomataError> {
+
+    match pattern {
+        Pattern::Empty => {
+            let state = nfa.add_state();
+            return Ok((state, state));
+        },
+        Pattern::Class(set) => {
+            let start = nfa.add_state();
+            let end = nfa.add_state();
+            nfa.add_predicate(start, *set, end)?;
+            for group in groups.iter() {
+                nfa.add_group(end, group)?;
+            }
+            return Ok((start, end));
+        },
+        Pattern::Concat(patterns) => {
+            let (start, mut end) = build(nfa, &Pattern::Empty, groups)?;
+            for pattern in patterns {
+                let (next_start, next_end) = build(nfa, pattern, groups)?;
+                nfa.add_epsilon(end, next_start)?;
+                end = next_end;
+            }
+            return Ok((start, end));
+        },
+        Pattern::Alternation(patterns) => {
+            let start = nfa.add_state();
+            let end = nfa.add_state();
+            for pattern in patterns {
+                let (inner_start, inner_end) = build(nfa, pattern, groups)?;
+                nfa.add_epsilon(start, inner_start)?;
+                nfa.add_epsilon(inner_end, end)?;
+            }
+            return Ok((start, end));
+        },
+        Pattern::Star(pattern) => {
+            let start = nfa.add_state();
+            let end = nfa.add_state();
+            let (inner_start, inner_end) = build(nfa, pattern, groups)?;
+            nfa.add_epsilon(start, inner_start)?;
+            nfa.add_epsilon(start, end)?;
+            nfa.add_epsilon(inner_end, inner_start)?;
+            nfa.add_epsilon(inner_end, end)?;
+            return Ok((start, end));
+        },
+        Pattern::Plus(pattern) => {
+            let end = nfa.add_state();
+            let (inner_start, inner_end) = build(nfa, pattern, groups)?;
+            nfa.add_epsilon(inner_end, inner_start)?;
+            nfa.add_epsilon(inner_end, end)?;
+            return Ok((inner_start, end));
+        },
+        Pattern::Optional(pattern) => {
+            let start = nfa.add_state();
+            let end = nfa.add_state();
+            let (inner_start, inner_end) = build(nfa, pattern, groups)?;
+            nfa.add_epsilon(start, inner_start)?;
+            nfa.add_epsilon(start, end)?;
+            nfa.add_epsilon(inner_end, end)?;
+            return Ok((start, end));
+        },
+        Pattern::Group(name, pattern) => {
+            groups.push(name.clone());
+            let states = build(nfa, pattern, groups);
+            groups.pop();
+            return states;
+        }
+    }
+}
+
+/// Parse alternatives separated by '|'.
+fn parse_alternation(input: &str) -> ParseResult<'_, Pattern> {
+    map(separated_list1(char('|'), parse_concat), |mut patterns| {
+        if patterns.len() == 1 {
+            patterns.remove(0)
+        } else {
+            Pattern::Alternation(patterns)
+        }
+    }).parse(input)
+}
+
+/// Parse a possibly empty sequence of repeated atoms.
+fn parse_concat(input: &str) -> ParseResult<'_, Pattern> {
+    map(many0(parse_repeat), |mut patterns| {
+        if patterns.len() == 1 {
+            patterns.remove(0)
+        } else {
+            Pattern::Concat(patterns)
+        }
+    }).parse(input)
+}
+
+/// Parse an atom followed by any number of '*', '+' and '?'.
+fn parse_repeat(input: &str) -> ParseResult<'_, Pattern> {
+    let (input, atom) = parse_atom(input)?;
+    let (input, operators) = many0(alt((char('*'), char('+'), char('?'))))
+        .parse(input)?;
+
+    let pattern = operators.into_iter().fold(atom, |pattern, op| match op {
+        '*' => pattern.star(),
+        '+' => pattern.plus(),
+        _ => pattern.optional()
+    });
+    return Ok((input, pattern));
+}
+
+/// Parse a group, class, any byte or a literal character.
+fn parse_atom(input: &str) -> ParseResult<'_, Pattern> {
+    context("a pattern", alt((
+        map(delimited(char('('), pair(parse_group_name, parse_alternation),
+            char(')')),
+            |(name, pattern)| match name {
+                Some(name) => pattern.named(name),
+                None => pattern
+            }),
+        parse_class,
+        value(Pattern::Class(ByteSet::all()), char('.')),
+        map(alt((parse_escape, none_of("\\|*+?()[]."))), |c| {
+            Pattern::literal(c.encode_utf8(&mut [0; 4]).as_bytes())
+        })
+    ))).parse(input)
+}
+
+/// Parse the optional '?<name>' at the start of a group.
+fn parse_group_name(input: &str) -> ParseResult<'_, Option<&str>> {
+    let name = take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_');
+    opt(delimited(tag("?<"), context("a group name", name), char('>')))
+        .parse(input)
+}
+
+/// Parse a character class like [a-z0-9_] or [^,].
+fn parse_class(input: &str) -> ParseResult<'_, Pattern> {
+    let item = alt((parse_class_range, map(parse_class_byte, ByteSet::byte)));
+    let class = pair(opt(char('^')), many1(item));
+
+    map(delimited(char('['), class, char(']')), |(negated, sets)| {
+        let set = sets.iter().fold(ByteSet::empty(), |acc, s| acc.union(s));
+        match negated {
+            Some(_) => Pattern::Class(set.complement()),
+            None => Pattern::Class(set)
+        }
+    }).parse(input)
+}
+
+/// Parse a range of bytes inside a character class like a-z.
+///
+/// Fails without backtracking on a reversed range like z-a.
+fn parse_class_range(input: &str) -> ParseResult<'_, ByteSet> {
+    let (rest, (from, to)) =
+        separated_pair(parse_class_byte, char('-'), parse_class_byte)
+            .parse(input)?;
+    if from > to {
+        return Err(Err::Failure(InputError {
+            input: input,
+            expected: Expected::Context("a range from a lower to a higher byte")
+        }));
+    }
+    return Ok((rest, ByteSet::range(from..=to)));
+}
+
+/// Parse a single ASCII character inside a character class.
+fn parse_class_byte(input: &str) -> ParseResult<'_, u8> {
+    let byte = verify(alt((parse_escape, none_of("\\]"))), |c| c.is_ascii());
+    map(context("an ASCII character", byte), |c| c as u8).parse(input)
+}
+
+/// Parse a backslash escaped character.
+///
+/// Fails without backtracking on an unknown escape like \d.
+fn parse_escape(input: &str) -> ParseResult<'_, char> {
+    let escaped = alt((
+        value('\n', char('n')),
+        value('\t', char('t')),
+        value('\r', char('r')),
+        one_of("\\|*+?()[].-^")
+    ));
+    preceded(char('\\'), cut(context("a known escape", escaped))).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the automata on the input, giving whether each byte ends a match.
+    fn matches(automata: &mut FiniteAutomata, input: &str) -> Vec<bool> {
+        automata.reset();
+        return input.bytes().map(|byte| {
+            automata.transition(byte).expect("Compiled automatas are total.");
+            automata.accepts()
+        }).collect();
+    }
+
+    /// Test that patterns are parsed to the same pattern as built by hand.
+    #[test]
+    fn test_parse() {
+        let digit = Pattern::class(ByteSet::range(b'0'..=b'9'));
+        let expected = Pattern::literal(b"a")
+            .then(digit.clone().plus().named("n"))
+            .then(Pattern::class(ByteSet::byte(b',').complement()).star())
+            .then(Pattern::literal(b"(").optional())
+            .or(Pattern::class(ByteSet::all()));
+        assert_eq!(Pattern::parse("a(?<n>[0-9]+)[^,]*\\(?|."), Ok(expected));
+
+        assert_eq!(Pattern::parse("[a-c_]"),
+            Ok(Pattern::class(b"abc_".as_slice().into())));
+        assert_eq!(Pattern::parse(""), Ok(Pattern::Concat(vec![])));
+    }
+
+    /// Test that malformed patterns are rejected.
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Pattern::parse("ab)").is_err());
+        assert!(Pattern::parse("(ab").is_err());
+        assert!(Pattern::parse("[]").is_err());
+        assert!(Pattern::parse("*a").is_err());
+        assert!(Pattern::parse("(?<>a)").is_err());
+    }
+
+    /// Test that unknown escapes are rejected and special characters can be
+    /// escaped.
+    #[test]
+    fn test_parse_escape() {
+        let error = Pattern::parse("a\\d").unwrap_err();
+        assert!(error.to_string()
+            .contains("column 3: expected a known escape"));
+        assert!(Pattern::parse("[\\d]").is_err());
+        assert!(Pattern::parse("\\").is_err());
+
+        assert_eq!(Pattern::parse("\\.\\\\\\n"),
+            Ok(Pattern::literal(b".\\\n")));
+        assert_eq!(Pattern::parse("[\\]\\-]"),
+            Ok(Pattern::class(b"]-".as_slice().into())));
+    }
+
+    /// Test that reversed class ranges are rejected.
+    #[test]
+    fn test_parse_reversed_range() {
+        let error = Pattern::parse("x[z-a]").unwrap_err();
+        assert!(error.to_string().contains(
+            "column 3: expected a range from a lower to a higher byte"));
+        assert_eq!(Pattern::parse("[a-a-]"),
+            Ok(Pattern::class(b"a-".as_slice().into())));
+    }
+
+    /// Test that group names can have underscores.
+    #[test]
+    fn test_parse_group_name() {
+        let expected = Pattern::literal(b"x").named("first_x");
+        assert_eq!(Pattern::parse("(?<first_x>x)"), Ok(expected));
+        assert!(Pattern::parse("(?<a-b>x)").is_err());
+    }
+
+    /// Test that compiled patterns only accept whole matches from the start.
+    #[test]
+    fn test_compile() {
+        let mut automata = Pattern::parse("ab*c|d?").unwrap().compile();
+        assert!(automata.accepts());
+        assert_eq!(matches(&mut automata, "abbc"),
+            vec![false, false, false, true]);
+        assert_eq!(matches(&mut automata, "acx"), vec![false, true, false]);
+        assert_eq!(matches(&mut automata, "dd"), vec![true, false]);
+    }
+
+    /// Test that search automatas find overlapping matches anywhere.
+    #[test]
+    fn test_compile_search() {
+        let mut automata = Pattern::parse("XMAS|SAMX").unwrap()
+            .compile_search();
+        let found = matches(&mut automata, "XMASAMXMAS");
+        let ends: Vec<usize> = (0..found.len()).filter(|&i| found[i])
+            .collect();
+        assert_eq!(ends, vec![3, 6, 9]);
+    }
+
+    /// Test that searching for several patterns tells which of them matched.
+    #[test]
+    fn test_compile_search_all() {
+        let mut automata = Pattern::compile_search_all(&[
+            Pattern::parse("m(?<d>[0-9])z").unwrap(),
+            Pattern::parse("[0-9]").unwrap(),
+            Pattern::parse("5z").unwrap()
+        ]);
+        let found: Vec<Vec<usize>> = "m5z".bytes().map(|byte| {
+            automata.transition(byte).unwrap();
+            automata.keywords().to_vec()
+        }).collect();
+        assert_eq!(found, vec![vec![], vec![1], vec![0, 2]]);
+    }
+
+    /// Test that states know the named groups matching the last byte.
+    #[test]
+    fn test_groups() {
+        let mut automata = Pattern::parse("(?<a>x+)(?<b>y)z").unwrap()
+            .compile();
+        let groups: Vec<Vec<String>> = "xxyz".bytes().map(|byte| {
+            automata.transition(byte).unwrap();
+            automata.states()[automata.current_state()].groups().to_vec()
+        }).collect();
+        assert_eq!(groups, vec![
+            vec!["a".to_string()],
+            vec!["a".to_string()],
+            vec!["b".to_string()],
+            vec![]
+        ]);
+    }
+
+}
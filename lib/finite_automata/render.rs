@@ -0,0 +1,163 @@
+use std::fmt;
+use std::fmt::Write;
+use super::FiniteAutomata;
+
+impl FiniteAutomata {
+
+    /// Render the automata in the DOT language of Graphviz.
+    ///
+    /// Accepting states are drawn with a double circle and states are labelled
+    /// with their reference and the groups they belong to. Transitions are
+    /// labelled with their label. Render the output with something like:
+    ///
+    ///    dot -Tsvg automata.dot > automata.svg
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph automata {{");
+        let _ = writeln!(dot, "    rankdir=LR;");
+        let _ = writeln!(dot, "    start [shape=point];");
+        let _ = writeln!(dot, "    start -> 0;");
+        for state in self.states().iter() {
+            let shape = if state.is_accepting() {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            let mut label = state.state_ref().to_string();
+            if !state.groups().is_empty() {
+                label = format!("{}\n{}", label, state.groups().join(","));
+            }
+            let _ = writeln!(dot, "    {} [shape={}, label=\"{}\"];",
+                state.state_ref(), shape, escape(&label));
+        }
+        for state in self.states().iter() {
+            for transition in state.transitions().iter() {
+                let _ = writeln!(dot, "    {} -> {} [label=\"{}\"];",
+                    state.state_ref(), transition.result_state_ref(),
+                    escape(transition.label()));
+            }
+        }
+        let _ = writeln!(dot, "}}");
+
+        return dot;
+    }
+
+}
+
+/// Escape a string for use inside double quotes in the DOT language.
+fn escape(string: &str) -> String {
+    return string.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+}
+
+/// Display the automata as a table with a row per state.
+///
+/// The current state is marked with >, like:
+///
+/// ```text
+/// state  accepting  groups  transitions
+/// > 0                       'm' -> 1, [^m] -> 0
+///   1    yes        mul     'm' -> 1, [^m] -> 0
+/// ```
+impl fmt::Display for FiniteAutomata {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rows = vec![[
+            "state".to_string(),
+            "accepting".to_string(),
+            "groups".to_string(),
+            "transitions".to_string()
+        ]];
+        for state in self.states().iter() {
+            let marker = if state.state_ref() == self.current_state() {
+                ">"
+            } else {
+                " "
+            };
+            let transitions: Vec<String> = state.transitions().iter()
+                .map(|t| format!("{} -> {}", t.label(), t.result_state_ref()))
+                .collect();
+            rows.push([
+                format!("{} {}", marker, state.state_ref()),
+                if state.is_accepting() { "yes" } else { "" }.to_string(),
+                state.groups().join(","),
+                transitions.join(", ")
+            ]);
+        }
+
+        let widths: Vec<usize> = (0..3)
+            .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+            .collect();
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let line = format!("{:<w0$}  {:<w1$}  {:<w2$}  {}", row[0], row[1],
+                row[2], row[3], w0 = widths[0], w1 = widths[1],
+                w2 = widths[2]);
+            write!(f, "{}", line.trim_end())?;
+        }
+        return Ok(());
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::finite_automata::{ByteSet, FiniteAutomata, Pattern, Predicate};
+
+    /// Build an automata finding 'ab' with a labelled transition.
+    fn automata() -> FiniteAutomata {
+        let mut automata = FiniteAutomata::new();
+        let a = automata.add_state();
+        let b = automata.add_state();
+        automata.add_predicate(0, ByteSet::byte(b'a'), a).unwrap();
+        let predicate = Predicate::function(|c| c == b'b');
+        automata.add_labelled_transition(a, predicate, "\"b\"", b).unwrap();
+        automata.add_predicate_all(ByteSet::all(), 0).unwrap();
+        automata.set_accepting(b, true).unwrap();
+        return automata;
+    }
+
+    /// Test that the automata is rendered as a DOT graph.
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(automata().to_dot(), concat!(
+            "digraph automata {\n",
+            "    rankdir=LR;\n",
+            "    start [shape=point];\n",
+            "    start -> 0;\n",
+            "    0 [shape=circle, label=\"0\"];\n",
+            "    1 [shape=circle, label=\"1\"];\n",
+            "    2 [shape=doublecircle, label=\"2\"];\n",
+            "    0 -> 1 [label=\"'a'\"];\n",
+            "    0 -> 0 [label=\"*\"];\n",
+            "    1 -> 2 [label=\"\\\"b\\\"\"];\n",
+            "    1 -> 0 [label=\"*\"];\n",
+            "    2 -> 0 [label=\"*\"];\n",
+            "}\n"
+        ));
+    }
+
+    /// Test that the automata is displayed as a table.
+    #[test]
+    fn test_display() {
+        assert_eq!(automata().to_string(), concat!(
+            "state  accepting  groups  transitions\n",
+            "> 0                       'a' -> 1, * -> 0\n",
+            "  1                       \"b\" -> 2, * -> 0\n",
+            "  2    yes                * -> 0"
+        ));
+
+        let automata = Pattern::parse("(?<x>x)[^x]").unwrap().compile();
+        assert_eq!(automata.to_string(), concat!(
+            "state  accepting  groups  transitions\n",
+            "> 0                       [^x] -> 1, 'x' -> 2\n",
+            "  1                       * -> 1\n",
+            "  2               x       [^x] -> 3, 'x' -> 1\n",
+            "  3    yes                * -> 1"
+        ));
+    }
+
+}
@@ -0,0 +1,457 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{BufReader, Bytes, Read};
+use std::mem;
+use std::rc::Rc;
+use crate::aoc::AocError;
+use super::{FiniteAutomata, StateRef};
+
+/// A match found when searching with an automata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+
+    /// Offset of the first byte of the match.
+    pub start: usize,
+
+    /// Offset just past the last byte of the match.
+    pub end: usize,
+
+    /// The accepting state the automata was in at the end of the match.
+    pub state: StateRef
+
+}
+
+/// Which matches a search reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKind {
+
+    /// Report the match starting first, and of those the longest. The search
+    /// continues after the end of the match so matches never overlap.
+    #[default]
+    LeftmostLongest,
+
+    /// Report every match, also matches overlapping or inside other matches.
+    Overlapping
+
+}
+
+/// Configures a search with an automata.
+///
+/// Created by FiniteAutomata.searcher. The automata is run from its initial
+/// state at every offset of the input, a match is found whenever it reaches
+/// an accepting state. The searcher can be used for any number of searches.
+///
+/// Searching for leftmost longest matches is a single pass over the input.
+/// Runs that reach the same state have the same matches from then on, so
+/// only the run that started first is kept, tracking the leftmost start of
+/// every state. At most one run per state is advanced on each byte, which
+/// makes a search linear in the length of the input. Overlapping searches
+/// report the matches of every run, so they keep every run.
+pub struct Searcher {
+
+    /// Transitions of the automata, shared with the searches.
+    tables: Rc<Tables>,
+
+    /// Which matches to report.
+    kind: MatchKind
+
+}
+
+/// The transitions of an automata in a form that is fast to search with.
+struct Tables {
+
+    /// Next state for every state and byte, None if there is no transition.
+    table: Vec<[Option<StateRef>; 256]>,
+
+    /// Whether each state is accepting.
+    accepting: Vec<bool>,
+
+    /// Whether an accepting state can still be reached from each state.
+    live: Vec<bool>
+
+}
+
+/// Iterator over the matches in a byte stream.
+///
+/// Created by Searcher.find_iter. Bytes are read one at a time and matches are
+/// given as soon as they are known, so the input is never held in memory.
+pub struct FindIter<R: Read> {
+
+    /// Transitions of the automata.
+    tables: Rc<Tables>,
+
+    /// Which matches to report.
+    kind: MatchKind,
+
+    /// The bytes still to be read.
+    bytes: Bytes<BufReader<R>>,
+
+    /// Offset of the next byte.
+    position: usize,
+
+    /// Runs of the automata that can still match, in order of their start.
+    threads: Vec<Thread>,
+
+    /// Longest matches of runs that have stopped, by their start, waiting
+    /// for the runs that started before them.
+    pending: BTreeMap<usize, Match>,
+
+    /// Whether a run is in each state, only set while advancing the runs.
+    seen: Vec<bool>,
+
+    /// Matches found but not given yet.
+    found: VecDeque<Match>,
+
+    /// Whether the whole input has been read.
+    done: bool
+
+}
+
+/// A run of the automata from a start offset.
+struct Thread {
+
+    /// Offset the run started at.
+    start: usize,
+
+    /// Current state of the run, None once it can no longer match or was
+    /// merged with a run that started before it.
+    state: Option<StateRef>,
+
+    /// Longest match of the run so far.
+    longest: Option<Match>
+
+}
+
+impl FiniteAutomata {
+
+    /// Find the leftmost longest matches in the input.
+    ///
+    /// Shorthand for self.searcher().find_iter(reader).
+    pub fn find_iter<R: Read>(&self, reader: R) -> FindIter<R> {
+        return self.searcher().find_iter(reader);
+    }
+
+    /// Configure a search with the automata.
+    pub fn searcher(&self) -> Searcher {
+        let table = self.transition_table();
+        let accepting: Vec<bool> = self.states().iter()
+            .map(|state| state.is_accepting())
+            .collect();
+
+        return Searcher {
+            tables: Rc::new(Tables::new(table, accepting)),
+            kind: MatchKind::default()
+        };
+    }
+
+}
+
+impl Searcher {
+
+    /// Choose which matches are reported.
+    pub fn kind(mut self, kind: MatchKind) -> Self {
+        self.kind = kind;
+        return self;
+    }
+
+    /// Treat only the given states as accepting.
+    pub fn accepting(mut self, states: &[StateRef]) -> Self {
+        let accepting = (0..self.tables.table.len())
+            .map(|state| states.contains(&state))
+            .collect();
+        let table = self.tables.table.clone();
+        self.tables = Rc::new(Tables::new(table, accepting));
+        return self;
+    }
+
+    /// Search for matches in the input.
+    pub fn find_iter<R: Read>(&self, reader: R) -> FindIter<R> {
+        return FindIter {
+            tables: Rc::clone(&self.tables),
+            kind: self.kind,
+            bytes: BufReader::new(reader).bytes(),
+            position: 0,
+            threads: Vec::new(),
+            pending: BTreeMap::new(),
+            seen: vec![false; self.tables.table.len()],
+            found: VecDeque::new(),
+            done: false
+        };
+    }
+
+}
+
+impl<R: Read> FindIter<R> {
+
+    /// Start a run at the current position and advance all runs on the byte.
+    ///
+    /// When searching for leftmost longest matches, a run is stopped when it
+    /// reaches a state that a run which started before it is already in.
+    fn step(&mut self, byte: u8) {
+        self.start_thread();
+        self.position = self.position + 1;
+        let tables = &self.tables;
+        let seen = &mut self.seen;
+        let merge = self.kind == MatchKind::LeftmostLongest;
+        for thread in self.threads.iter_mut() {
+            let state = thread.state
+                .and_then(|state| tables.table[state][byte as usize])
+                .filter(|&state| tables.live[state])
+                .filter(|&state| !(merge && mem::replace(&mut seen[state],
+                    true)));
+            thread.state = state;
+            if let Some(state) = state.filter(|&s| tables.accepting[s]) {
+                let found = Match {
+                    start: thread.start,
+                    end: self.position,
+                    state: state
+                };
+                match self.kind {
+                    MatchKind::Overlapping => self.found.push_back(found),
+                    MatchKind::LeftmostLongest => thread.longest = Some(found)
+                }
+            }
+        }
+        for state in self.threads.iter().filter_map(|thread| thread.state) {
+            self.seen[state] = false;
+        }
+        self.resolve();
+    }
+
+    /// Start a run of the automata at the current position.
+    fn start_thread(&mut self) {
+        if !self.tables.live[0] {
+            return;
+        }
+        let empty = Match {
+            start: self.position,
+            end: self.position,
+            state: 0
+        };
+        let accepting = self.tables.accepting[0];
+        if accepting && self.kind == MatchKind::Overlapping {
+            self.found.push_back(empty);
+        }
+        self.threads.push(Thread {
+            start: self.position,
+            state: Some(0),
+            longest: if accepting { Some(empty) } else { None }
+        });
+    }
+
+    /// Drop the runs that have stopped and give the matches no run that
+    /// started before them can take the place of.
+    ///
+    /// The longest match of a stopped run waits until all runs that started
+    /// before it have stopped, so matches are given in order. A match drops
+    /// all runs and matches starting inside it.
+    fn resolve(&mut self) {
+        if self.kind == MatchKind::Overlapping {
+            self.threads.retain(|thread| thread.state.is_some());
+            return;
+        }
+
+        for thread in self.threads.iter().filter(|t| t.state.is_none()) {
+            if let Some(found) = thread.longest {
+                self.pending.insert(thread.start, found);
+            }
+        }
+        self.threads.retain(|thread| thread.state.is_some());
+        while let Some((&start, &found)) = self.pending.first_key_value() {
+            if self.threads.first().is_some_and(|t| t.start < start) {
+                return;
+            }
+            self.pending.pop_first();
+            while self.pending.first_key_value()
+                .is_some_and(|(&start, _)| start < found.end) {
+
+                self.pending.pop_first();
+            }
+            self.threads.retain(|t| t.start >= found.end);
+            self.found.push_back(found);
+        }
+    }
+
+    /// Stop all runs at the end of the input.
+    fn finish(&mut self) {
+        self.start_thread();
+        for thread in self.threads.iter_mut() {
+            thread.state = None;
+        }
+        self.resolve();
+        self.done = true;
+    }
+
+}
+
+impl<R: Read> Iterator for FindIter<R> {
+
+    type Item = Result<Match, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Some(Ok(found));
+            } else if self.done {
+                return None;
+            }
+
+            match self.bytes.next() {
+                Some(Ok(byte)) => self.step(byte),
+                Some(Err(err)) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                },
+                None => self.finish()
+            }
+        }
+    }
+
+}
+
+impl Tables {
+
+    /// Collect the tables, finding the states an accepting state can be
+    /// reached from.
+    fn new(table: Vec<[Option<StateRef>; 256]>, accepting: Vec<bool>)
+        -> Tables {
+
+        let mut live = accepting.clone();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for state in 0..table.len() {
+                let next = table[state].iter().flatten();
+                if !live[state] && next.clone().any(|&s| live[s]) {
+                    live[state] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        return Tables {
+            table: table,
+            accepting: accepting,
+            live: live
+        };
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finite_automata::Pattern;
+
+    /// Find the start and end of all matches.
+    fn spans(searcher: Searcher, input: &str) -> Vec<(usize, usize)> {
+        return searcher.find_iter(input.as_bytes())
+            .map(|found| found.map(|m| (m.start, m.end)))
+            .collect::<Result<_, _>>()
+            .unwrap();
+    }
+
+    /// Test that leftmost longest matches are found.
+    #[test]
+    fn test_leftmost_longest() {
+        let automata = Pattern::parse("ab*|bc").unwrap().compile();
+        assert_eq!(spans(automata.searcher(), "xabbbcabc"),
+            vec![(1, 5), (6, 8)]);
+        assert_eq!(spans(automata.searcher(), "bcab"), vec![(0, 2), (2, 4)]);
+        assert_eq!(spans(automata.searcher(), ""), vec![]);
+
+        let automata = Pattern::parse("x*").unwrap().compile();
+        assert_eq!(spans(automata.searcher(), "axx"),
+            vec![(0, 0), (1, 3), (3, 3)]);
+    }
+
+    /// Find the leftmost longest matches by trying every start, the slow way.
+    fn leftmost_longest(tables: &Tables, input: &[u8]) -> Vec<(usize, usize)> {
+        let longest = |start: usize| {
+            let mut state = Some(0);
+            let mut end = None;
+            for i in start..=input.len() {
+                match state {
+                    Some(s) if tables.accepting[s] => end = Some(i),
+                    Some(_) => (),
+                    None => break
+                }
+                state = state.zip(input.get(i))
+                    .and_then(|(s, &byte)| tables.table[s][byte as usize]);
+            }
+            return end;
+        };
+
+        let mut spans = Vec::new();
+        let mut position = 0;
+        while let Some((start, end)) = (position..=input.len())
+            .find_map(|start| Some((start, longest(start)?))) {
+
+            spans.push((start, end));
+            position = if end == start { start + 1 } else { end };
+        }
+        return spans;
+    }
+
+    /// Test that merging runs in the same state finds the same matches as
+    /// trying every start.
+    #[test]
+    fn test_leftmost_longest_merged() {
+        let patterns = ["ab*|bc", "a(ba)*|b", "(ab|a)(bab)*", "x*|ab", "a+b"];
+        for pattern in patterns {
+            let automata = Pattern::parse(pattern).unwrap().compile();
+            let tables = Rc::clone(&automata.searcher().tables);
+            for n in 0..4usize.pow(5) {
+                let input: Vec<u8> = (0..5)
+                    .map(|i| b"abcx"[n / 4usize.pow(i) % 4])
+                    .collect();
+                let input = String::from_utf8(input).unwrap();
+                assert_eq!(spans(automata.searcher(), &input),
+                    leftmost_longest(&tables, input.as_bytes()),
+                    "{} on {}", pattern, input);
+            }
+        }
+    }
+
+    /// Test that a run is kept per state at most, however many start.
+    #[test]
+    fn test_leftmost_longest_runs() {
+        let automata = Pattern::parse("a*b").unwrap().compile();
+        let states = automata.states().len();
+        let input = "a".repeat(1000);
+        let mut search = automata.searcher().find_iter(input.as_bytes());
+        while let Some(byte) = search.bytes.next() {
+            search.step(byte.unwrap());
+            assert!(search.threads.len() <= states);
+        }
+        assert_eq!(search.next().map(|found| found.unwrap()), None);
+    }
+
+    /// Test that overlapping matches are all found.
+    #[test]
+    fn test_overlapping() {
+        let automata = Pattern::parse("XMAS|SAMX|MA").unwrap().compile();
+        let searcher = automata.searcher().kind(MatchKind::Overlapping);
+        assert_eq!(spans(searcher, "XMASAMX"),
+            vec![(1, 3), (0, 4), (3, 7)]);
+    }
+
+    /// Test that only the chosen states are accepting.
+    #[test]
+    fn test_accepting() {
+        let automata = Pattern::parse("ab").unwrap().compile();
+        let after_a = automata.find_state(0).unwrap().transitions().iter()
+            .find(|t| t.matches(b'a'))
+            .unwrap()
+            .result_state_ref();
+        let matches: Vec<Match> = automata.searcher()
+            .accepting(&[after_a])
+            .find_iter(b"aab".as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(matches, vec![
+            Match { start: 0, end: 1, state: after_a },
+            Match { start: 1, end: 2, state: after_a }
+        ]);
+    }
+
+}
@@ -0,0 +1,501 @@
+// Saving and loading automatas as JSON.
+//
+// An automata is stored as an object with a format version and a list of
+// states, the first state is the initial state. Every state is written on a
+// line of its own so changes to an automata give readable diffs:
+//
+//    {"version":1,"states":[
+//    {"accepting":false,"groups":[],"keywords":[],"transitions":[
+//        {"bytes":[[48,57],[97,97]],"target":1,"label":"[0-9a]"}]},
+//    {"accepting":true,"groups":["n"],"keywords":[],"transitions":[]}
+//    ]}
+//
+// Here the transitions are broken over lines for readability, they are on the
+// same line as their state in files written by FiniteAutomata.to_json. The
+// fields are:
+//
+//    accepting    whether the state is accepting
+//    groups       names of the groups the state belongs to
+//    keywords     indices of the keywords found when entering the state
+//    transitions  transitions in the order they are tried
+//    bytes        inclusive ranges of the bytes a transition is taken on
+//    target       index of the state a transition goes to
+//    label        human readable description of the bytes
+//
+// Any JSON whitespace is allowed between tokens and fields can appear in any
+// order, but every field must appear exactly once. Only transitions over byte
+// sets can be stored, functions used as predicates can not be inspected.
+//
+// Automatas can be compiled ahead of time and loaded at startup, like the
+// tokenizer of day 3, see TokenizerBuilder.build_from.
+use nom::{
+    Err,
+    Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0, none_of, satisfy},
+    combinator::{cut, map, map_opt, map_res, recognize, value, verify},
+    error::context,
+    multi::{count, fold_many0, separated_list0},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+};
+use crate::aoc::{AocError, ParseError};
+use crate::aoc::output::json_string;
+use crate::nom_helpers::{
+    Expected, InputError, ParseResult, parse_all, parse_u64
+};
+use super::{ByteSet, FiniteAutomata, FiniteAutomataError, Predicate, State};
+
+/// Version of the format written by FiniteAutomata.to_json.
+const VERSION: u64 = 1;
+
+/// A state as read from JSON before the automata is checked.
+type StateEntry = (bool, Vec<String>, Vec<usize>, Vec<TransitionEntry>);
+
+/// A transition as read from JSON, the bytes, target and label.
+type TransitionEntry = (ByteSet, usize, String);
+
+/// A field of the automata object.
+enum AutomataField {
+    Version(u64),
+    States(Vec<StateEntry>)
+}
+
+/// A field of a state object.
+enum StateField {
+    Accepting(bool),
+    Groups(Vec<String>),
+    Keywords(Vec<usize>),
+    Transitions(Vec<TransitionEntry>)
+}
+
+/// A field of a transition object.
+enum TransitionField {
+    Bytes(ByteSet),
+    Target(usize),
+    Label(String)
+}
+
+impl FiniteAutomata {
+
+    /// Write the automata as JSON in the format described above.
+    ///
+    /// # Errors
+    ///
+    /// Will give error FiniteAutomataError.FunctionPredicate if any transition
+    /// uses a function as predicate.
+    pub fn to_json(&self) -> Result<String, FiniteAutomataError> {
+        let mut lines = Vec::new();
+        for state in self.states.iter() {
+            let mut transitions = Vec::new();
+            for transition in state.transitions.iter() {
+                let set = match &transition.predicate {
+                    Predicate::Bytes(set) => set,
+                    Predicate::Function(_) | Predicate::Local(_) => {
+                        return Err(FiniteAutomataError::FunctionPredicate);
+                    }
+                };
+                let ranges: Vec<String> = set.ranges().iter()
+                    .map(|r| format!("[{},{}]", r.start(), r.end()))
+                    .collect();
+                transitions.push(format!(
+                    "{{\"bytes\":[{}],\"target\":{},\"label\":{}}}",
+                    ranges.join(","), transition.result_state_ref,
+                    json_string(&transition.label)));
+            }
+
+            let groups: Vec<String> = state.groups.iter()
+                .map(|group| json_string(group))
+                .collect();
+            let keywords: Vec<String> = state.keywords.iter()
+                .map(|keyword| keyword.to_string())
+                .collect();
+            lines.push(format!(
+                "{{\"accepting\":{},\"groups\":[{}],\"keywords\":[{}],\
+                \"transitions\":[{}]}}", state.accepting, groups.join(","),
+                keywords.join(","), transitions.join(",")));
+        }
+
+        return Ok(format!("{{\"version\":{},\"states\":[\n{}\n]}}\n", VERSION,
+            lines.join(",\n")));
+    }
+
+    /// Read an automata written as JSON in the format described above.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError if the JSON is malformed, is of an
+    /// unknown version, has no states or has a transition to a state that
+    /// does not exist.
+    pub fn from_json(json: &str) -> Result<FiniteAutomata, AocError> {
+        let (version, entries) = parse_all(parse_automata, json)?;
+        if version != VERSION {
+            return Err(AocError::ParseInputError(ParseError::new(format!(
+                "unsupported automata format version {}", version))));
+        } else if entries.is_empty() {
+            return Err(AocError::ParseInputError(ParseError::new(
+                "an automata needs at least one state")));
+        }
+
+        let state_count = entries.len();
+        let mut states = Vec::new();
+        for (i, (accepting, groups, keywords, transitions)) in
+            entries.into_iter().enumerate() {
+
+            let mut state = State::new(i);
+            state.accepting = accepting;
+            state.groups = groups;
+            state.keywords = keywords;
+            for (set, target, label) in transitions {
+                if target >= state_count {
+                    return Err(AocError::ParseInputError(ParseError::new(
+                        format!("state {} has a transition to missing state \
+                            {}", i, target))));
+                }
+                state.add_labelled_predicate(set, &label, target);
+            }
+            states.push(state);
+        }
+
+        return Ok(FiniteAutomata {
+            current_state: 0,
+            states: states
+        });
+    }
+
+}
+
+/// Parse the token surrounded by optional whitespace.
+fn token<'a>(c: char) -> impl FnMut(&'a str) -> ParseResult<'a, char> {
+    delimited(multispace0, char(c), multispace0)
+}
+
+/// Parse a field of the name, the colon following it and its value.
+///
+/// Once the name is found the value must follow, errors in it are not
+/// backtracked from.
+fn field<'a, O, P>(name: &'static str, value: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, O>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    let name = terminated(delimited(char('"'), tag(name), char('"')),
+        token(':'));
+    preceded(name, cut(value))
+}
+
+/// Parse a comma separated list in square brackets.
+fn list<'a, O, P>(item: P) -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    delimited(token('['), separated_list0(token(','), item), token(']'))
+}
+
+/// Parse the comma separated fields of an object in curly brackets, in any
+/// order.
+fn object<'a, O, P>(field: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    delimited(token('{'), separated_list0(token(','), field), token('}'))
+}
+
+/// Fail parsing the object at the input for not having the expected fields
+/// exactly once.
+fn missing_fields<'a>(input: &'a str, expected: &'static str)
+    -> Err<InputError<'a>> {
+
+    return Err::Failure(InputError {
+        input: input.trim_start(),
+        expected: Expected::Context(expected)
+    });
+}
+
+/// Put the value of a field in its slot, giving false if the field was
+/// already given.
+fn fill<T>(slot: &mut Option<T>, value: T) -> bool {
+    return slot.replace(value).is_none();
+}
+
+/// Parse the version and states of an automata.
+fn parse_automata(input: &str) -> ParseResult<'_, (u64, Vec<StateEntry>)> {
+    const FIELDS: &str = "an automata with the fields version and states";
+    let field = alt((
+        map(field("version", parse_u64), AutomataField::Version),
+        map(field("states", list(parse_state)), AutomataField::States)
+    ));
+    let (rest, fields) = object(field).parse(input)?;
+
+    let (mut version, mut states) = (None, None);
+    for field in fields {
+        let once = match field {
+            AutomataField::Version(v) => fill(&mut version, v),
+            AutomataField::States(s) => fill(&mut states, s)
+        };
+        if !once {
+            return Err(missing_fields(input, FIELDS));
+        }
+    }
+    match (version, states) {
+        (Some(version), Some(states)) => return Ok((rest, (version, states))),
+        _ => return Err(missing_fields(input, FIELDS))
+    }
+}
+
+/// Parse a single state.
+fn parse_state(input: &str) -> ParseResult<'_, StateEntry> {
+    const FIELDS: &str = "a state with the fields accepting, groups, keywords \
+        and transitions";
+    let accepting = alt((value(true, tag("true")), value(false, tag("false"))));
+    let field = alt((
+        map(field("accepting", context("a boolean", accepting)),
+            StateField::Accepting),
+        map(field("groups", list(parse_string)), StateField::Groups),
+        map(field("keywords", list(parse_index)), StateField::Keywords),
+        map(field("transitions", list(parse_transition)),
+            StateField::Transitions)
+    ));
+    let (rest, fields) = object(field).parse(input)?;
+
+    let mut state = (None, None, None, None);
+    for field in fields {
+        let once = match field {
+            StateField::Accepting(a) => fill(&mut state.0, a),
+            StateField::Groups(g) => fill(&mut state.1, g),
+            StateField::Keywords(k) => fill(&mut state.2, k),
+            StateField::Transitions(t) => fill(&mut state.3, t)
+        };
+        if !once {
+            return Err(missing_fields(input, FIELDS));
+        }
+    }
+    match state {
+        (Some(accepting), Some(groups), Some(keywords), Some(transitions)) => {
+            return Ok((rest, (accepting, groups, keywords, transitions)));
+        },
+        _ => return Err(missing_fields(input, FIELDS))
+    }
+}
+
+/// Parse a single transition.
+fn parse_transition(input: &str) -> ParseResult<'_, TransitionEntry> {
+    const FIELDS: &str = "a transition with the fields bytes, target and label";
+    let bytes = map(list(parse_range), |sets| sets.iter()
+        .fold(ByteSet::empty(), |all, set| all.union(set)));
+    let field = alt((
+        map(field("bytes", bytes), TransitionField::Bytes),
+        map(field("target", parse_index), TransitionField::Target),
+        map(field("label", parse_string), TransitionField::Label)
+    ));
+    let (rest, fields) = object(field).parse(input)?;
+
+    let mut transition = (None, None, None);
+    for field in fields {
+        let once = match field {
+            TransitionField::Bytes(b) => fill(&mut transition.0, b),
+            TransitionField::Target(t) => fill(&mut transition.1, t),
+            TransitionField::Label(l) => fill(&mut transition.2, l)
+        };
+        if !once {
+            return Err(missing_fields(input, FIELDS));
+        }
+    }
+    match transition {
+        (Some(bytes), Some(target), Some(label)) => {
+            return Ok((rest, (bytes, target, label)));
+        },
+        _ => return Err(missing_fields(input, FIELDS))
+    }
+}
+
+/// Parse an inclusive range of bytes written as a list of its start and end.
+///
+/// Fails without backtracking on a range with its start after its end.
+fn parse_range(input: &str) -> ParseResult<'_, ByteSet> {
+    let (rest, (start, end)) = delimited(token('['),
+        separated_pair(parse_byte, token(','), parse_byte), token(']'))
+        .parse(input)?;
+    if start > end {
+        return Err(Err::Failure(InputError {
+            input: input,
+            expected: Expected::Context("a range with start <= end")
+        }));
+    }
+    return Ok((rest, ByteSet::range(start..=end)));
+}
+
+/// Parse a byte written as a number.
+fn parse_byte(input: &str) -> ParseResult<'_, u8> {
+    context("a byte", map_res(parse_u64, u8::try_from)).parse(input)
+}
+
+/// Parse an index of a state or keyword written as a number.
+fn parse_index(input: &str) -> ParseResult<'_, usize> {
+    context("an index", map_res(parse_u64, usize::try_from)).parse(input)
+}
+
+/// Parse a JSON string.
+///
+/// Characters outside the basic multilingual plane are written as a pair of
+/// surrogate escapes, like \ud83d\ude00.
+fn parse_string(input: &str) -> ParseResult<'_, String> {
+    let hex = || map_res(recognize(count(satisfy(|c| c.is_ascii_hexdigit()),
+        4)), |digits: &str| u32::from_str_radix(digits, 16));
+    let high = verify(hex(), |u| (0xd800..0xdc00).contains(u));
+    let low = preceded(tag("\\u"), verify(hex(), |u| (0xdc00..0xe000)
+        .contains(u)));
+    let surrogates = map(pair(high, low),
+        |(high, low)| 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00));
+    let unicode = map_opt(preceded(char('u'), alt((surrogates, hex()))),
+        char::from_u32);
+    let escape = preceded(char('\\'), alt((
+        value('"', char('"')),
+        value('\\', char('\\')),
+        value('/', char('/')),
+        value('\n', char('n')),
+        value('\r', char('r')),
+        value('\t', char('t')),
+        unicode
+    )));
+    let characters = fold_many0(alt((escape, none_of("\"\\"))), String::new,
+        |mut s, c| {
+            s.push(c);
+            s
+        });
+    context("a string", delimited(char('"'), characters, char('"')))
+        .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finite_automata::Pattern;
+
+    /// Test that automatas are the same after being written and read.
+    #[test]
+    fn test_round_trip() {
+        let automata = Pattern::parse("(?<n>[0-9]+)|\"\\\\").unwrap().compile();
+        let json = automata.to_json().unwrap();
+        let loaded = FiniteAutomata::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+        assert_eq!(loaded.to_string(), automata.to_string());
+        assert!(loaded.is_equivalent(&automata));
+
+        let automata = FiniteAutomata::from_keywords(&[b"ab", b"b"]);
+        let json = automata.to_json().unwrap();
+        let loaded = FiniteAutomata::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), json);
+    }
+
+    /// Test the written format.
+    #[test]
+    fn test_to_json() {
+        let mut automata = FiniteAutomata::new();
+        let state = automata.add_state();
+        automata.add_labelled_transition(0, ByteSet::range(b'0'..=b'9'),
+            "\"digit\"\n", state).unwrap();
+        automata.set_accepting(state, true).unwrap();
+        assert_eq!(automata.to_json().unwrap(), concat!(
+            "{\"version\":1,\"states\":[\n",
+            "{\"accepting\":false,\"groups\":[],\"keywords\":[],",
+            "\"transitions\":[{\"bytes\":[[48,57]],\"target\":1,",
+            "\"label\":\"\\\"digit\\\"\\n\"}]},\n",
+            "{\"accepting\":true,\"groups\":[],\"keywords\":[],",
+            "\"transitions\":[]}\n",
+            "]}\n"
+        ));
+
+        automata.add_transition(state, &|_| true, 0).unwrap();
+        assert_eq!(automata.to_json(),
+            Err(FiniteAutomataError::FunctionPredicate));
+    }
+
+    /// Test that JSON with whitespace and escapes is read.
+    #[test]
+    fn test_from_json() {
+        let json = r#" { "version" : 1, "states" : [
+            { "accepting": false, "groups": [], "keywords": [],
+              "transitions": [
+                { "bytes": [[97, 98], [100, 100]], "target": 1,
+                  "label": "a\u0062\td" } ] },
+            { "accepting": true, "groups": ["x", "y"], "keywords": [0],
+              "transitions": [] } ] }
+        "#;
+        let mut automata = FiniteAutomata::from_json(json).unwrap();
+        let state = automata.find_state(0).unwrap();
+        assert_eq!(state.transitions()[0].label(), "ab\td");
+        assert!(automata.find_state(1).unwrap().in_group("y"));
+        automata.transition(b'd').unwrap();
+        assert!(automata.accepts());
+        assert_eq!(automata.keywords(), &[0]);
+        assert!(automata.transition(b'c').is_err());
+    }
+
+    /// Test that fields are read in any order.
+    #[test]
+    fn test_from_json_field_order() {
+        let json = concat!("{\"states\":[",
+            "{\"transitions\":[{\"label\":\"a\",\"target\":0,",
+            "\"bytes\":[[97,97]]}],\"keywords\":[],\"groups\":[],",
+            "\"accepting\":true}],\"version\":1}");
+        let mut automata = FiniteAutomata::from_json(json).unwrap();
+        automata.transition(b'a').unwrap();
+        assert!(automata.accepts());
+        assert_eq!(automata.find_state(0).unwrap().transitions()[0].label(),
+            "a");
+    }
+
+    /// Test that characters outside the basic multilingual plane are read
+    /// from surrogate pairs and lone surrogates are rejected.
+    #[test]
+    fn test_parse_string_surrogates() {
+        assert_eq!(parse_string("\"\\ud83d\\ude00!\""),
+            Ok(("", "\u{1f600}!".to_string())));
+        assert_eq!(parse_string("\"\\u00e6\""), Ok(("", "æ".to_string())));
+        assert!(parse_string("\"\\ud83d\"").is_err());
+        assert!(parse_string("\"\\ud83d\\u0041\"").is_err());
+        assert!(parse_string("\"\\ude00\"").is_err());
+    }
+
+    /// Test that invalid JSON is rejected.
+    #[test]
+    fn test_from_json_invalid() {
+        let json = "{\"version\":1,\"states\":[}";
+        let error = FiniteAutomata::from_json(json).err().unwrap();
+        assert!(error.to_string().contains("column 24"));
+
+        let missing = concat!("{\"version\":1,\"states\":[",
+            "{\"accepting\":false,\"groups\":[],\"keywords\":[],",
+            "\"transitions\":[{\"bytes\":[],\"target\":1,\"label\":\"\"}]}]}");
+        assert!(FiniteAutomata::from_json(missing).is_err());
+        assert!(FiniteAutomata::from_json("{\"version\":2,\"states\":[]}")
+            .is_err());
+        assert!(FiniteAutomata::from_json("{\"version\":1,\"states\":[]}")
+            .is_err());
+
+        let error = FiniteAutomata::from_json("{\"version\":1}").err().unwrap();
+        assert!(error.to_string().contains(
+            "expected an automata with the fields version and states"));
+        let twice = "{\"version\":1,\"version\":1,\"states\":[]}";
+        assert!(FiniteAutomata::from_json(twice).is_err());
+        let missing = concat!("{\"version\":1,\"states\":[",
+            "{\"accepting\":false,\"groups\":[],\"transitions\":[]}]}");
+        let error = FiniteAutomata::from_json(missing).err().unwrap();
+        assert!(error.to_string().contains("column 24"));
+    }
+
+    /// Test that ranges with their start after their end are rejected.
+    #[test]
+    fn test_from_json_reversed_range() {
+        let json = concat!("{\"version\":1,\"states\":[",
+            "{\"accepting\":false,\"groups\":[],\"keywords\":[],",
+            "\"transitions\":[{\"bytes\":[[97,97],[98,97]],\"target\":0,",
+            "\"label\":\"\"}]}]}");
+        let error = FiniteAutomata::from_json(json).err().unwrap();
+        assert!(error.to_string().contains(
+            "column 102: expected a range with start <= end"));
+    }
+
+}
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use super::{
+    CompiledAutomata, FiniteAutomata, FiniteAutomataError, Pattern, StateRef
+};
+
+/// Turns a stream of bytes into a stream of tokens.
+///
+/// The tokenizer is made of rules, each a pattern with a name and an action.
+/// The patterns are searched for anywhere in the input, also overlapping each
+/// other. Every time a match of a rule ends the action of the rule is run on
+/// the bytes captured by the named groups of the pattern to produce a token.
+/// This makes the automata a Moore machine where the output is decided by the
+/// rules with a match ending in the state entered, see
+/// Pattern::compile_search_all. Groups are kept apart per rule so rules can
+/// use the same group names.
+pub struct Tokenizer<T> {
+
+    /// Automata searching for all rules at once.
+    automata: CompiledAutomata,
+
+    /// The rules in the order they were added.
+    rules: Vec<Rule<T>>,
+
+    /// Bytes captured by groups since they were last entered.
+    captures: Captures
+
+}
+
+/// Builds a tokenizer from rules.
+pub struct TokenizerBuilder<T> {
+
+    /// Patterns of the rules with their groups qualified by the rule name.
+    patterns: Vec<Pattern>,
+
+    /// The rules in the order they were added.
+    rules: Vec<Rule<T>>
+
+}
+
+/// Bytes matched by the named groups of a pattern.
+///
+/// A group captures the bytes read since the automata last entered a state
+/// belonging to the group from a state outside it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Captures {
+
+    /// Bytes of each group.
+    groups: HashMap<String, Vec<u8>>
+
+}
+
+/// Produces a token from the captures of a match, or None to drop the match.
+type Action<T> = Box<dyn Fn(&Captures) -> Option<T>>;
+
+/// A named pattern with the action producing its token.
+struct Rule<T> {
+
+    /// Name of the rule, qualifying the groups of its pattern.
+    name: String,
+
+    /// Produces the token from the captures, or None to drop the match.
+    action: Action<T>
+
+}
+
+impl<T> Tokenizer<T> {
+
+    /// Start building a tokenizer without any rules.
+    pub fn builder() -> TokenizerBuilder<T> {
+        return TokenizerBuilder {
+            patterns: Vec::new(),
+            rules: Vec::new()
+        };
+    }
+
+    /// Go back to the initial state forgetting all captures.
+    pub fn reset(&mut self) {
+        self.automata.reset();
+        self.captures.groups.clear();
+    }
+
+    /// Feed the next byte of the input to the tokenizer.
+    ///
+    /// Gives a token if a match of a rule ends at the byte. If matches of
+    /// several rules end at the same byte the first rule added wins.
+    pub fn feed(&mut self, byte: u8) -> Option<T> {
+        let previous = self.automata.current_state();
+        self.automata.transition(byte)
+            .expect("Compiled patterns have a transition on every byte.");
+        let current = self.automata.current_state();
+        self.capture(previous, current, byte);
+
+        let &first = self.automata.keywords().first()?;
+        let rule = &self.rules[first];
+        return (rule.action)(&self.captures.of_rule(&rule.name));
+    }
+
+    /// Feed all bytes to the tokenizer, giving the tokens found.
+    pub fn tokens<'a, I>(&'a mut self, bytes: I) -> impl Iterator<Item=T> + 'a
+    where
+        I: IntoIterator<Item=u8>,
+        I::IntoIter: 'a,
+    {
+        return bytes.into_iter().filter_map(|byte| self.feed(byte));
+    }
+
+    /// Add the byte to the captures of the groups of the current state.
+    fn capture(&mut self, previous: StateRef, current: StateRef, byte: u8) {
+        let entered = self.automata.groups(current);
+        let left = self.automata.groups(previous);
+        for group in entered.iter() {
+            if !self.captures.groups.contains_key(group) {
+                self.captures.groups.insert(group.clone(), Vec::new());
+            }
+            let bytes = self.captures.groups.get_mut(group)
+                .expect("The group was just added.");
+            if !left.contains(group) {
+                bytes.clear();
+            }
+            bytes.push(byte);
+        }
+    }
+
+}
+
+impl<T> TokenizerBuilder<T> {
+
+    /// Add a rule producing a token with the action every time the pattern
+    /// matches.
+    ///
+    /// The action can use the captures of the named groups in the pattern, it
+    /// gives None if the match should not produce a token.
+    pub fn rule(mut self, name: &str, pattern: Pattern,
+        action: impl Fn(&Captures) -> Option<T> + 'static) -> Self {
+
+        self.patterns.push(qualify(pattern, name));
+        self.rules.push(Rule {
+            name: name.to_string(),
+            action: Box::new(action)
+        });
+        return self;
+    }
+
+    /// Compile the rules into a tokenizer.
+    pub fn build(self) -> Tokenizer<T> {
+        let automata = self.automata();
+        return self.build_from(&automata)
+            .expect("The automata of the rules finds only their keywords.");
+    }
+
+    /// The automata searching for all rules at once.
+    ///
+    /// It can be compiled ahead of time and saved with FiniteAutomata.to_json
+    /// to be loaded at startup and given to build_from, saving the cost of
+    /// compiling the rules.
+    pub fn automata(&self) -> FiniteAutomata {
+        return Pattern::compile_search_all(&self.patterns);
+    }
+
+    /// Build a tokenizer running the automata of the rules, as given by
+    /// automata, instead of compiling them.
+    ///
+    /// # Errors
+    ///
+    /// Will give FiniteAutomataError.MissingRule if the automata finds the
+    /// keyword of a rule that was not added.
+    pub fn build_from(self, automata: &FiniteAutomata)
+        -> Result<Tokenizer<T>, FiniteAutomataError> {
+
+        let rules = self.rules.len();
+        let mut keywords = automata.states.iter()
+            .flat_map(|state| state.keywords.iter());
+        if keywords.any(|&keyword| keyword >= rules) {
+            return Err(FiniteAutomataError::MissingRule);
+        }
+        return Ok(Tokenizer {
+            automata: automata.compile(),
+            rules: self.rules,
+            captures: Captures::default()
+        });
+    }
+
+}
+
+impl Captures {
+
+    /// Get the bytes captured by the named group.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        return self.groups.get(name).map(|bytes| bytes.as_slice());
+    }
+
+    /// Parse the bytes captured by the named group, giving None if the group
+    /// captured nothing or the bytes could not be parsed.
+    pub fn parse<F: FromStr>(&self, name: &str) -> Option<F> {
+        let bytes = self.get(name)?;
+        return std::str::from_utf8(bytes).ok()?.parse().ok();
+    }
+
+    /// The captures of the groups of the rule, without the rule name.
+    fn of_rule(&self, rule: &str) -> Captures {
+        let prefix = format!("{}.", rule);
+        let groups = self.groups.iter()
+            .filter_map(|(name, bytes)| {
+                let name = name.strip_prefix(&prefix)?;
+                return Some((name.to_string(), bytes.clone()));
+            })
+            .collect();
+        return Captures {
+            groups: groups
+        };
+    }
+
+}
+
+/// Qualify the names of the groups in the pattern with the rule name, like
+/// rule.group, keeping the groups of different rules apart.
+fn qualify(pattern: Pattern, rule: &str) -> Pattern {
+    let all = |patterns: Vec<Pattern>| patterns.into_iter()
+        .map(|pattern| qualify(pattern, rule))
+        .collect();
+    match pattern {
+        Pattern::Empty => Pattern::Empty,
+        Pattern::Class(set) => Pattern::Class(set),
+        Pattern::Concat(patterns) => Pattern::Concat(all(patterns)),
+        Pattern::Alternation(patterns) => Pattern::Alternation(all(patterns)),
+        Pattern::Star(pattern) => qualify(*pattern, rule).star(),
+        Pattern::Plus(pattern) => qualify(*pattern, rule).plus(),
+        Pattern::Optional(pattern) => qualify(*pattern, rule).optional(),
+        Pattern::Group(name, pattern) => qualify(*pattern, rule)
+            .named(&format!("{}.{}", rule, name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tokens produced by the test tokenizer.
+    #[derive(Debug, PartialEq)]
+    enum Token {
+        Number(u32),
+        Pair(u32, u32)
+    }
+
+    /// Rules finding numbers and pairs of numbers.
+    fn tokenizer_rules() -> TokenizerBuilder<Token> {
+        let pair = Pattern::parse("<(?<a>[0-9]+),(?<b>[0-9]+)>").unwrap();
+        return Tokenizer::builder()
+            .rule("pair", pair, |c| Some(Token::Pair(c.parse("a")?,
+                c.parse("b")?)))
+            .rule("number", Pattern::parse("#(?<n>[0-9]+)#").unwrap(),
+                |c| Some(Token::Number(c.parse("n")?)));
+    }
+
+    /// Tokenizer finding numbers and pairs of numbers.
+    fn tokenizer() -> Tokenizer<Token> {
+        return tokenizer_rules().build();
+    }
+
+    /// Test that tokens are produced when matches end.
+    #[test]
+    fn test_tokens() {
+        let mut tokenizer = tokenizer();
+        let input = "x<1,23>#4#<5,<6,7>#8";
+        let tokens: Vec<Token> = tokenizer.tokens(input.bytes()).collect();
+        assert_eq!(tokens, vec![
+            Token::Pair(1, 23),
+            Token::Number(4),
+            Token::Pair(6, 7)
+        ]);
+    }
+
+    /// Test that captures restart when a group is entered again.
+    #[test]
+    fn test_captures() {
+        let mut tokenizer = tokenizer();
+        for byte in "<12,3<45".bytes() {
+            assert_eq!(tokenizer.feed(byte), None);
+        }
+        let captures = tokenizer.captures.of_rule("pair");
+        assert_eq!(captures.get("a"), Some(b"45".as_slice()));
+        assert_eq!(captures.get("b"), Some(b"3".as_slice()));
+        assert_eq!(captures.parse::<u32>("a"), Some(45));
+        assert_eq!(captures.parse::<u32>("c"), None);
+        assert_eq!(tokenizer.captures.get("pair.a"), Some(b"45".as_slice()));
+
+        tokenizer.reset();
+        assert_eq!(tokenizer.captures.of_rule("pair").get("a"), None);
+    }
+
+    /// Test that only rules with a full match fire, with their own captures,
+    /// even while inside the pattern of another rule.
+    #[test]
+    fn test_overlapping_rules() {
+        let mut tokenizer = Tokenizer::builder()
+            .rule("a", Pattern::parse("m(?<d>[0-9])z").unwrap(),
+                |c| Some(Token::Pair(c.parse("d")?, 0)))
+            .rule("b", Pattern::parse("(?<e>[0-9])").unwrap(),
+                |c| match c.get("d") {
+                    None => Some(Token::Number(c.parse("e")?)),
+                    Some(_) => None
+                })
+            .build();
+
+        let tokens: Vec<Token> = tokenizer.tokens("m5".bytes()).collect();
+        assert_eq!(tokens, vec![Token::Number(5)]);
+
+        tokenizer.reset();
+        let tokens: Vec<Token> = tokenizer.tokens("m5zm7z".bytes()).collect();
+        assert_eq!(tokens, vec![
+            Token::Number(5),
+            Token::Pair(5, 0),
+            Token::Number(7),
+            Token::Pair(7, 0)
+        ]);
+    }
+
+    /// Test that a tokenizer can run an automata compiled ahead of time, as
+    /// long as it has a rule for every keyword.
+    #[test]
+    fn test_build_from() {
+        let json = tokenizer_rules().automata().to_json().unwrap();
+        let automata = FiniteAutomata::from_json(&json).unwrap();
+        let mut tokenizer = tokenizer_rules().build_from(&automata).unwrap();
+        let tokens: Vec<Token> = tokenizer.tokens("#4#<5,6>".bytes())
+            .collect();
+        assert_eq!(tokens, vec![Token::Number(4), Token::Pair(5, 6)]);
+
+        let pair = Tokenizer::<Token>::builder()
+            .rule("pair", Pattern::parse("<[0-9]>").unwrap(), |_| None);
+        assert_eq!(pair.build_from(&automata).err(),
+            Some(FiniteAutomataError::MissingRule));
+    }
+
+}
@@ -0,0 +1,500 @@
+// Two dimensional grids of cells.
+//
+// Many puzzles give their input as a rectangular map of characters, like:
+//
+//    ....#.....
+//    ....^....#
+//    ..........
+//
+// A Grid stores such a map row by row and gives bounds-checked access to the
+// cells using (x, y) positions where x is the column and y is the row, both
+// starting from 0 in the top left corner.
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+use crate::aoc::{AocError, Location, ParseError};
+
+/// (x, y) position in a grid, x is the column and y is the row.
+pub type Position = (usize, usize);
+
+/// Steps to the 4 neighbours of a cell, clockwise starting from north.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [
+    (0, -1), (1, 0), (0, 1), (-1, 0)
+];
+
+/// Steps to the 8 neighbours of a cell, clockwise starting from north.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)
+];
+
+/// A rectangular grid of cells.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Grid<T> {
+
+    /// The cells of the grid, row by row.
+    cells: Vec<T>,
+
+    /// Number of columns in the grid.
+    width: usize,
+
+    /// Number of rows in the grid.
+    height: usize
+
+}
+
+impl Grid<u8> {
+
+    /// Parse a grid of bytes from the reader, one row per line.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError if the rows are not all the same
+    /// length.
+    pub fn parse(reader: &mut dyn BufRead) -> Result<Grid<u8>, AocError> {
+        return Grid::parse_with(reader, Some);
+    }
+
+}
+
+impl<T> Grid<T> {
+
+    /// Construct a grid from a list of rows.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError if the rows are not all the same
+    /// length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, AocError> {
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AocError::ParseInputError(ParseError::new(format!(
+                    "expected {} cells on row {}, found {}",
+                    width, y + 1, row.len()))));
+            }
+            cells.extend(row);
+        }
+
+        return Ok(Grid {
+            cells: cells,
+            width: width,
+            height: height
+        });
+    }
+
+    /// Parse a grid from the reader, one row per line, converting each byte to
+    /// a cell using the given function.
+    ///
+    /// Carriage returns at the end of lines and empty lines at the end of the
+    /// input are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Will give AocError.ParseInputError pointing out the offending line if
+    /// the rows are not all the same length or if the function does not accept
+    /// a byte.
+    pub fn parse_with<F>(reader: &mut dyn BufRead, mut cell: F)
+        -> Result<Grid<T>, AocError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            lines.push(line.trim_end_matches('\r').to_string());
+        }
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let width = lines.first().map(|line| line.len()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, byte) in line.bytes().enumerate() {
+                let c = cell(byte).ok_or_else(|| ParseError {
+                    message: format!("unexpected {:?}", byte as char),
+                    expected: None,
+                    location: Some(location(line, y, x))
+                })?;
+                cells.push(c);
+            }
+            if line.len() != width {
+                let message = format!("expected {} cells, found {}",
+                    width, line.len());
+                return Err(AocError::ParseInputError(ParseError {
+                    message: message,
+                    expected: Some(format!("{} cells", width)),
+                    location: Some(location(line, y, line.len().min(width)))
+                }));
+            }
+        }
+
+        return Ok(Grid {
+            cells: cells,
+            width: width,
+            height: lines.len()
+        });
+    }
+
+    /// Number of columns in the grid.
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    /// Number of rows in the grid.
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Test whether the position is inside the grid.
+    pub fn in_bounds(&self, position: Position) -> bool {
+        let (x, y) = position;
+        return x < self.width && y < self.height;
+    }
+
+    /// Convert signed coordinates to a position if they are inside the grid.
+    pub fn position(&self, x: isize, y: isize) -> Option<Position> {
+        let position = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        return Some(position).filter(|&p| self.in_bounds(p));
+    }
+
+    /// Take a step from the position, giving the new position if it is inside
+    /// the grid.
+    pub fn offset(&self, position: Position, step: (isize, isize))
+        -> Option<Position> {
+
+        let (x, y) = position;
+        let (dx, dy) = step;
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        return Some(position).filter(|&p| self.in_bounds(p));
+    }
+
+    /// Get the cell at the position if it is inside the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if !self.in_bounds(position) {
+            return None;
+        }
+        let (x, y) = position;
+        return self.cells.get(y * self.width + x);
+    }
+
+    /// Get the cell at the position mutably if it is inside the grid.
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if !self.in_bounds(position) {
+            return None;
+        }
+        let (x, y) = position;
+        return self.cells.get_mut(y * self.width + x);
+    }
+
+    /// Iterate over all positions of the grid row by row.
+    pub fn positions(&self) -> impl Iterator<Item=Position> {
+        let width = self.width;
+        return (0..self.height)
+            .flat_map(move |y| (0..width).map(move |x| (x, y)));
+    }
+
+    /// Iterate over all cells of the grid and their positions row by row.
+    pub fn iter(&self) -> impl Iterator<Item=(Position, &T)> {
+        return self.positions().zip(self.cells.iter());
+    }
+
+    /// Iterate over the up to 4 positions next to the position horizontally
+    /// and vertically that are inside the grid.
+    pub fn neighbours_4(&self, position: Position)
+        -> impl Iterator<Item=Position> + '_ {
+
+        return NEIGHBOURS_4.iter()
+            .filter_map(move |&step| self.offset(position, step));
+    }
+
+    /// Iterate over the up to 8 positions next to the position horizontally,
+    /// vertically and diagonally that are inside the grid.
+    pub fn neighbours_8(&self, position: Position)
+        -> impl Iterator<Item=Position> + '_ {
+
+        return NEIGHBOURS_8.iter()
+            .filter_map(move |&step| self.offset(position, step));
+    }
+
+    /// Iterate over the positions from the start taking the same step over and
+    /// over until leaving the grid. The start is included if inside the grid.
+    pub fn walk(&self, start: Position, step: (isize, isize))
+        -> impl Iterator<Item=Position> + '_ {
+
+        let first = Some(start).filter(|&p| self.in_bounds(p));
+        return std::iter::successors(first, move |&p| self.offset(p, step));
+    }
+
+    /// Iterate over the cells from the start taking the same step over and
+    /// over until leaving the grid.
+    pub fn line(&self, start: Position, step: (isize, isize))
+        -> impl Iterator<Item=&T> + '_ {
+
+        return self.walk(start, step).map(move |p| &self[p]);
+    }
+
+    /// Iterate over the cells of row y from left to right.
+    pub fn row(&self, y: usize) -> impl Iterator<Item=&T> + '_ {
+        return self.line((0, y), (1, 0));
+    }
+
+    /// Iterate over the cells of column x from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> + '_ {
+        return self.line((x, 0), (0, 1));
+    }
+
+    /// Iterate over all rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> + '_ {
+        return (0..self.height).map(move |y| self.row(y));
+    }
+
+    /// Iterate over all columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> + '_ {
+        return (0..self.width).map(move |x| self.column(x));
+    }
+
+    /// Iterate over all diagonals going down and to the right, starting with
+    /// the one in the top right corner. The cells of each diagonal are given
+    /// from the top.
+    pub fn diagonals(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> + '_ {
+        let top = (0..self.width).rev().map(|x| (x, 0));
+        let left = (1..self.side_height()).map(|y| (0, y));
+        return top.chain(left).map(move |start| self.line(start, (1, 1)));
+    }
+
+    /// Iterate over all diagonals going down and to the left, starting with
+    /// the one in the top left corner. The cells of each diagonal are given
+    /// from the top.
+    pub fn anti_diagonals(&self)
+        -> impl Iterator<Item=impl Iterator<Item=&T>> + '_ {
+
+        let top = (0..self.width).map(|x| (x, 0));
+        let right = (1..self.side_height()).map(|y| (self.width - 1, y));
+        return top.chain(right).map(move |start| self.line(start, (-1, 1)));
+    }
+
+    /// Number of rows along the sides of the grid where diagonals start, none
+    /// if the rows are empty.
+    fn side_height(&self) -> usize {
+        if self.width == 0 {
+            return 0;
+        }
+        return self.height;
+    }
+
+    /// Construct a new grid of the same size by converting every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        return Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height
+        };
+    }
+
+}
+
+impl<T> Index<Position> for Grid<T> {
+
+    type Output = T;
+
+    /// Get the cell at the position, panics if outside the grid.
+    fn index(&self, position: Position) -> &T {
+        return self.get(position).expect("Position inside the grid.");
+    }
+
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+
+    /// Get the cell at the position mutably, panics if outside the grid.
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        return self.get_mut(position).expect("Position inside the grid.");
+    }
+
+}
+
+/// Display the grid with a character per cell and a line per row, the same
+/// way it is given in the puzzle input.
+impl<T> fmt::Display for Grid<T>
+where
+    T: Copy + Into<char>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{}", (*cell).into())?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/// Location of byte x on line y of the input, both starting from 0.
+///
+/// The column is counted in characters, a byte inside a character is at the
+/// column of the character.
+fn location(line: &str, y: usize, x: usize) -> Location {
+    let mut x = x.min(line.len());
+    while !line.is_char_boundary(x) {
+        x = x - 1;
+    }
+    let column = line[..x].chars().count() + 1;
+    return Location {
+        line: y + 1,
+        column: column,
+        snippet: line.to_string(),
+        snippet_column: column
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The grid used in most tests.
+    fn example() -> Grid<u8> {
+        return Grid::parse(&mut "abc\ndef\n".as_bytes()).unwrap();
+    }
+
+    /// Collect the cells of an iterator into a string.
+    fn string<'a>(cells: impl Iterator<Item=&'a u8>) -> String {
+        return cells.map(|&c| c as char).collect();
+    }
+
+    /// Test that parsing a grid works.
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], b'a');
+        assert_eq!(grid[(2, 1)], b'f');
+        assert_eq!(Grid::parse(&mut "abc\r\ndef\r\n\n".as_bytes()), Ok(grid));
+
+        let empty = Grid::parse(&mut "".as_bytes()).unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    /// Test that rows of different lengths are rejected with their position.
+    #[test]
+    fn test_parse_ragged() {
+        let error = Grid::parse(&mut "abc\nde\nfgh\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 3: expected 3 cells, found 2\n",
+            "2 | de\n",
+            "  |   ^"
+        ));
+
+        let error = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(error.to_string(),
+            "Parse error: expected 2 cells on row 2, found 1");
+    }
+
+    /// Test that cells are converted while parsing.
+    #[test]
+    fn test_parse_with() {
+        let digit = |c: u8| (c as char).to_digit(10);
+        let grid = Grid::parse_with(&mut "01\n23\n".as_bytes(), digit).unwrap();
+        assert_eq!(grid, Grid::from_rows(vec![vec![0, 1], vec![2, 3]]).unwrap());
+
+        let error = Grid::parse_with(&mut "01\n2.\n".as_bytes(), digit)
+            .unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 2: unexpected '.'\n",
+            "2 | 2.\n",
+            "  |  ^"
+        ));
+
+        let error = Grid::parse_with(&mut "éé.\n".as_bytes(),
+            |c| (c != b'.').then_some(c)).unwrap_err();
+        assert!(error.to_string()
+            .starts_with("Parse error on line 1, column 3: unexpected '.'"));
+    }
+
+    /// Test that access outside the grid is rejected.
+    #[test]
+    fn test_bounds() {
+        let mut grid = example();
+        assert_eq!(grid.get((2, 0)), Some(&b'c'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert!(grid.get_mut((3, 3)).is_none());
+        *grid.get_mut((1, 1)).unwrap() = b'x';
+        assert_eq!(grid[(1, 1)], b'x');
+
+        assert_eq!(grid.position(2, 1), Some((2, 1)));
+        assert_eq!(grid.position(-1, 1), None);
+        assert_eq!(grid.position(2, 2), None);
+        assert_eq!(grid.offset((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+    }
+
+    /// Test that neighbours are limited to the grid.
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        let neighbours: Vec<Position> = grid.neighbours_4((0, 0)).collect();
+        assert_eq!(neighbours, vec![(1, 0), (0, 1)]);
+        let neighbours: Vec<Position> = grid.neighbours_4((1, 1)).collect();
+        assert_eq!(neighbours, vec![(1, 0), (2, 1), (0, 1)]);
+        let neighbours: Vec<Position> = grid.neighbours_8((1, 0)).collect();
+        assert_eq!(neighbours, vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]);
+    }
+
+    /// Test that rows, columns and diagonals are iterated in order.
+    #[test]
+    fn test_lines() {
+        let grid = example();
+        assert_eq!(string(grid.row(1)), "def");
+        assert_eq!(string(grid.column(2)), "cf");
+        assert_eq!(grid.rows().map(string).collect::<Vec<_>>(),
+            vec!["abc", "def"]);
+        assert_eq!(grid.columns().map(string).collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]);
+        assert_eq!(grid.diagonals().map(string).collect::<Vec<_>>(),
+            vec!["c", "bf", "ae", "d"]);
+        assert_eq!(grid.anti_diagonals().map(string).collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]);
+        assert_eq!(string(grid.line((0, 1), (1, -1))), "db");
+    }
+
+    /// Test that grids with empty rows have no diagonals.
+    #[test]
+    fn test_lines_empty_rows() {
+        let grid: Grid<u8> = Grid::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(grid.diagonals().count(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    /// Test that all cells are visited row by row.
+    #[test]
+    fn test_iter() {
+        let grid = example();
+        let cells: Vec<(Position, u8)> = grid.iter().map(|(p, &c)| (p, c))
+            .take(4)
+            .collect();
+        assert_eq!(cells, vec![
+            ((0, 0), b'a'), ((1, 0), b'b'), ((2, 0), b'c'), ((0, 1), b'd')
+        ]);
+        assert!(grid.map(|&c| c == b'e')[(1, 1)]);
+    }
+
+    /// Test that grids are displayed like the puzzle input.
+    #[test]
+    fn test_display() {
+        assert_eq!(example().to_string(), "abc\ndef");
+        let grid = example().map(|&c| if c == b'e' { '#' } else { '.' });
+        assert_eq!(grid.to_string(), "...\n.#.");
+    }
+
+}
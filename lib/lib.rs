@@ -1,3 +1,19 @@
 pub mod finite_automata;
 pub mod nom_helpers;
 pub mod aoc;
+pub mod bench;
+pub mod verify;
+pub mod scaffold;
+pub mod grid;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
@@ -1,25 +1,854 @@
+use std::fmt;
+use std::io::{ErrorKind as IoErrorKind, Read};
+use std::marker::PhantomData;
+use std::str;
+use crate::aoc::{AocError, Location, ParseError};
+use crate::grid::{Grid, Position};
 use nom::{
+    Err,
     Parser,
     IResult,
-    multi::many1,
-    character::complete::{one_of},
-    combinator::{map_res, recognize}
+    Needed,
+    branch::alt,
+    character::complete::{line_ending, space0, space1},
+    combinator::{all_consuming, eof, fail},
+    multi::separated_list1,
+    sequence::{separated_pair, tuple},
+    error::{ContextError, ErrorKind, FromExternalError}
 };
 
+/// Result of the parsers in this crate.
+pub type ParseResult<'a, O> = IResult<&'a str, O, InputError<'a>>;
+
+/// Error produced by the nom parsers in this crate.
+///
+/// Remembers the remaining input at the point of the error so the position
+/// can be found in the full input afterwards, see parse_all.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InputError<'a> {
+
+    /// The input remaining when the error occurred.
+    pub input: &'a str,
+
+    /// What the parser expected to find.
+    pub expected: Expected
+
+}
+
+/// Description of what a parser expected to find in the input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Expected {
+
+    /// A specific character.
+    Char(char),
+
+    /// Something described by a context, like "a number".
+    Context(&'static str),
+
+    /// A number small enough to fit in the integer type of the given name.
+    Overflow(&'static str),
+
+    /// A row of a grid with the given number of cells, rows counted from 1.
+    Width {
+
+        /// Number of cells in every row.
+        cells: usize,
+
+        /// The row with a different number of cells.
+        row: usize
+
+    },
+
+    /// Something matching the nom parser of the given kind.
+    Kind(ErrorKind)
+
+}
+
+impl InputError<'_> {
+
+    /// Describe the error without a position.
+    pub fn describe(&self) -> String {
+        let found = match self.input.chars().next() {
+            Some(c) => format!("{:?}", c),
+            None => "end of input".to_string()
+        };
+        return format!("expected {}, found {}", self.expected, found);
+    }
+
+}
+
+impl<'a> nom::error::ParseError<&'a str> for InputError<'a> {
+
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        return InputError {
+            input: input,
+            expected: Expected::Kind(kind)
+        };
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        return other;
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        return InputError {
+            input: input,
+            expected: Expected::Char(c)
+        };
+    }
+
+}
+
+impl<'a> ContextError<&'a str> for InputError<'a> {
+
+    /// Describe the error using the context if the error happened right where
+    /// the context starts. Errors further into the input are more precise, so
+    /// they are kept as they are.
+    fn add_context(input: &'a str, ctx: &'static str, other: Self) -> Self {
+        if input.len() == other.input.len() {
+            return InputError {
+                input: input,
+                expected: Expected::Context(ctx)
+            };
+        } else {
+            return other;
+        }
+    }
+
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for InputError<'a> {
+
+    fn from_external_error(input: &'a str, kind: ErrorKind, _error: E) -> Self {
+        return InputError {
+            input: input,
+            expected: Expected::Kind(kind)
+        };
+    }
+
+}
+
+impl fmt::Display for Expected {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Char(c) => write!(f, "{:?}", c),
+            Expected::Context(ctx) => write!(f, "{}", ctx),
+            Expected::Overflow(name) => {
+                write!(f, "a number that fits in {}", name)
+            },
+            Expected::Width { cells, row } => {
+                write!(f, "{} cells on row {}", cells, row)
+            },
+            Expected::Kind(ErrorKind::Eof) => write!(f, "end of input"),
+            Expected::Kind(ErrorKind::CrLf) => write!(f, "a line ending"),
+            Expected::Kind(ErrorKind::Digit) => write!(f, "a digit"),
+            Expected::Kind(ErrorKind::Space) => write!(f, "a space"),
+            Expected::Kind(kind) => {
+                write!(f, "{}", kind.description().to_lowercase())
+            }
+        }
+    }
+
+}
+
+/// Run the parser on the full input and convert any error into an AocError.
+///
+/// The parser must consume all of the input. Errors will point out the line
+/// and column of the input where the parser failed.
+pub fn parse_all<'a, O, P>(parser: P, input: &'a str) -> Result<O, AocError>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    match all_consuming(parser).parse(input) {
+        Ok((_, parsed)) => return Ok(parsed),
+        Err(Err::Error(error)) | Err(Err::Failure(error)) => {
+            let offset = input.len() - error.input.len();
+            return Err(AocError::ParseInputError(ParseError::expected_at(
+                input, offset, error.expected.to_string())));
+        },
+        Err(Err::Incomplete(_)) => {
+            return Err(AocError::ParseInputError(ParseError::expected_at(
+                input, input.len(), "more input")));
+        }
+    }
+}
+
+/// Parse records, one per line, until the end of the input or a blank line.
+///
+/// The record parser is given each line without its line ending, which may be
+/// "\n" or "\r\n" and is optional after the last record. An error inside a
+/// record is given as is, so it points out the failing line when the records
+/// are parsed with parse_all. A blank line ends the records without being
+/// consumed, see sections.
+pub fn lines_of<'a, O, P>(mut record: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    move |mut input: &'a str| {
+        let mut records = Vec::new();
+        let blank = |input| line_ending::<_, InputError>(input).is_ok();
+        while !input.is_empty() && !blank(input) {
+            let (rest, parsed) = record.parse(input)?;
+            let (rest, _) = alt((line_ending, eof)).parse(rest)?;
+            records.push(parsed);
+            input = rest;
+        }
+        return Ok((input, records));
+    }
+}
+
+/// Parse a blank line, used to separate sections of the input.
+///
+/// The line ending of the line before must already be consumed, as lines_of
+/// does.
+pub fn blank_line(input: &str) -> ParseResult<'_, &str> {
+    line_ending(input)
+}
+
+/// Parse two sections of the input with a separator between them.
+///
+/// Usually the sections are parsed with lines_of and separated by blank_line,
+/// like the rules and updates in:
+///
+/// ```text
+/// 47|53
+/// 97|13
+///
+/// 75,47,61,53,29
+/// ```
+pub fn sections<'a, O1, O2, S, P1, P2>(separator: S, first: P1, second: P2)
+    -> impl FnMut(&'a str) -> ParseResult<'a, (O1, O2)>
+where
+    S: Parser<&'a str, &'a str, InputError<'a>>,
+    P1: Parser<&'a str, O1, InputError<'a>>,
+    P2: Parser<&'a str, O2, InputError<'a>>,
+{
+    separated_pair(first, separator, second)
+}
+
+/// Parse one or more items separated by spaces or tabs.
+pub fn whitespace_list<'a, O, P>(item: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, InputError<'a>>,
+{
+    separated_list1(space1, item)
+}
+
+/// Parse a key and a value with a separator between them, like "190: 10 19".
+///
+/// Spaces and tabs around the separator are skipped.
+pub fn key_value<'a, K, V, S, O, PK, PV>(key: PK, separator: S, value: PV)
+    -> impl FnMut(&'a str) -> ParseResult<'a, (K, V)>
+where
+    PK: Parser<&'a str, K, InputError<'a>>,
+    S: Parser<&'a str, O, InputError<'a>>,
+    PV: Parser<&'a str, V, InputError<'a>>,
+{
+    separated_pair(key, tuple((space0, separator, space0)), value)
+}
+
+/// Special cells of a grid with their positions, see grid_with.
+pub type Specials<S> = Vec<(Position, S)>;
+
+/// Parse a rectangular grid of cells, one row per line.
+///
+/// Each cell is parsed by the cell parser, which must not accept line endings.
+/// The rows end with "\n" or "\r\n", which is optional after the last row.
+/// The grid ends at the end of the input or at a blank line, which is not
+/// consumed. Rows with a different number of cells than the first row give a
+/// failure with Expected.Width, pointing at the first extra cell or the end of
+/// a short row.
+pub fn grid<'a, T, P>(cell: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, Grid<T>>
+where
+    P: Parser<&'a str, T, InputError<'a>>,
+{
+    let mut parser = grid_with(fail::<_, (T, ()), _>, cell);
+    move |input: &'a str| {
+        let (input, (grid, _)) = parser(input)?;
+        return Ok((input, grid));
+    }
+}
+
+/// Parse a rectangular grid of cells where some cells are special.
+///
+/// Works like grid, but the special parser is tried before the cell parser at
+/// every cell. A special cell gives both the cell stored in the grid and a
+/// value collected with the position of the cell, like the guard in:
+///
+/// ```text
+/// ....#.....
+/// ....^....#
+/// ```
+///
+/// The special values are given in the order of the cells, row by row.
+pub fn grid_with<'a, T, S, PS, P>(mut special: PS, mut cell: P)
+    -> impl FnMut(&'a str) -> ParseResult<'a, (Grid<T>, Specials<S>)>
+where
+    PS: Parser<&'a str, (T, S), InputError<'a>>,
+    P: Parser<&'a str, T, InputError<'a>>,
+{
+    move |mut input: &'a str| {
+        let row_end = |input: &str| input.is_empty()
+            || line_ending::<_, InputError>(input).is_ok();
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        let mut specials = Vec::new();
+        while !row_end(input) {
+            let y = rows.len();
+            let width = rows.first().map(|row| row.len());
+            let ragged = |input, cells| Err::Failure(InputError {
+                input: input,
+                expected: Expected::Width { cells: cells, row: y + 1 }
+            });
+
+            let mut row = Vec::new();
+            while !row_end(input) {
+                if let Some(width) = width.filter(|&w| w == row.len()) {
+                    return Err(ragged(input, width));
+                }
+                let (rest, parsed) = match special.parse(input) {
+                    Ok((rest, (parsed, value))) => {
+                        specials.push(((row.len(), y), value));
+                        (rest, parsed)
+                    },
+                    Err(Err::Error(_)) => cell.parse(input)?,
+                    Err(error) => return Err(error)
+                };
+                if rest.len() == input.len() {
+                    return Err(Err::Error(InputError {
+                        input: input,
+                        expected: Expected::Kind(ErrorKind::Many1)
+                    }));
+                }
+                row.push(parsed);
+                input = rest;
+            }
+            if let Some(width) = width.filter(|&w| w != row.len()) {
+                return Err(ragged(input, width));
+            }
+
+            rows.push(row);
+            (input, _) = alt((line_ending, eof)).parse(input)?;
+        }
+
+        let grid = Grid::from_rows(rows).expect("Rows have the same length.");
+        return Ok((input, (grid, specials)));
+    }
+}
+
+/// Number of bytes read at a time by Records.
+const READ_SIZE: usize = 8192;
+
+/// Most characters of a line Records shows on either side of a parse error.
+const SNIPPET_SIZE: usize = 40;
+
+/// Iterator over records parsed from a reader by a streaming parser.
+///
+/// Created by records. The input is read into a buffer as needed and the
+/// parser is run on what has been read so far. When the parser gives
+/// Err.Incomplete more input is read and the parser is run again, so the
+/// parser must be built from the nom streaming parsers, see
+/// IntParser.streaming. Parsed input is dropped from the buffer as more is
+/// read, only the last SNIPPET_SIZE characters of the line before the next
+/// record are kept for error messages, so inputs of any size can be parsed in
+/// memory proportional to the longest record, even when they are on one line.
+/// The snippet of an error is cut down to SNIPPET_SIZE characters on either
+/// side of the error, marked by ...
+///
+/// Input is checked to be UTF-8 once as it is read. The records before the
+/// first byte that is not are parsed as usual, the record containing it is an
+/// error.
+///
+/// A line ending is added if the input does not end with one, so parsers of
+/// line based records can expect every record to end with a line ending. Any
+/// error ends the iteration.
+pub struct Records<R: Read, O, P> {
+
+    /// Where the input is read from.
+    reader: R,
+
+    /// Parser of a single record.
+    parser: P,
+
+    /// Input read and found to be valid UTF-8 but not dropped yet.
+    text: String,
+
+    /// Input read but not valid UTF-8 yet, like a character cut off by a
+    /// read.
+    pending: Vec<u8>,
+
+    /// Whether the pending input starts with a byte that is not valid UTF-8.
+    invalid: bool,
+
+    /// Offset in the text of the start of the line of the next record, 0 if
+    /// the line starts in the dropped text.
+    line_start: usize,
+
+    /// The last SNIPPET_SIZE characters dropped from the text on the line of
+    /// the next record.
+    dropped_text: String,
+
+    /// Number of characters dropped from the text on the line of the next
+    /// record.
+    dropped: usize,
+
+    /// Offset in the text of the next record.
+    start: usize,
+
+    /// Number of lines before the line of the next record.
+    line: usize,
+
+    /// Whether all input has been read.
+    eof: bool,
+
+    /// Whether iteration has ended.
+    done: bool,
+
+    /// The type of the records.
+    record: PhantomData<O>
+
+}
+
+/// Result of running the parser on the buffered input.
+enum Step<O> {
+
+    /// A record was parsed from the given number of bytes.
+    Record(usize, O),
+
+    /// More input is needed to parse the record.
+    Incomplete,
+
+    /// The parser failed at the given offset expecting something.
+    Failed(usize, String)
+
+}
+
+/// Parse records from the reader using the streaming parser.
+///
+/// The records are given as an iterator, like:
+///
+/// ```text
+/// fn parse_stone(input: &str) -> ParseResult<'_, u64> {
+///     terminated(IntParser::new().streaming(true), one_of(" \n"))(input)
+/// }
+///
+/// let stones: Vec<u64> = records(reader, parse_stone).collect()?;
+/// ```
+///
+/// Parse errors give the line and column of the error in the full input.
+pub fn records<R, O, P>(reader: R, parser: P) -> Records<R, O, P>
+where
+    R: Read,
+    P: FnMut(&str) -> ParseResult<'_, O>,
+{
+    return Records {
+        reader: reader,
+        parser: parser,
+        text: String::new(),
+        pending: Vec::new(),
+        invalid: false,
+        line_start: 0,
+        dropped_text: String::new(),
+        dropped: 0,
+        start: 0,
+        line: 0,
+        eof: false,
+        done: false,
+        record: PhantomData
+    };
+}
+
+impl<R, O, P> Records<R, O, P>
+where
+    R: Read,
+    P: FnMut(&str) -> ParseResult<'_, O>,
+{
+
+    /// Drop the parsed records from the text and read more input.
+    fn refill(&mut self) -> Result<(), AocError> {
+        let parsed = &self.text[self.line_start..self.start];
+        self.dropped = self.dropped + parsed.chars().count();
+        self.dropped_text.push_str(parsed);
+        let cut = self.dropped_text.char_indices().rev()
+            .nth(SNIPPET_SIZE - 1)
+            .map_or(0, |(i, _)| i);
+        self.dropped_text.drain(..cut);
+        self.text.drain(..self.start);
+        self.start = 0;
+        self.line_start = 0;
+
+        let length = self.pending.len();
+        self.pending.resize(length + READ_SIZE, 0);
+        let read = loop {
+            match self.reader.read(&mut self.pending[length..]) {
+                Err(e) if e.kind() == IoErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.pending.truncate(length);
+                    return Err(e.into());
+                },
+                Ok(read) => break read
+            }
+        };
+        self.pending.truncate(length + read);
+
+        if read == 0 {
+            self.eof = true;
+            let ended = self.pending.last()
+                .or(self.text.as_bytes().last())
+                .is_none_or(|&b| b == b'\n');
+            if !ended {
+                self.pending.push(b'\n');
+            }
+        }
+        self.validate();
+        return Ok(());
+    }
+
+    /// Move the pending input that is valid UTF-8 to the text.
+    ///
+    /// A character cut off at the end of the pending input is left pending
+    /// unless all input has been read.
+    fn validate(&mut self) {
+        let valid = match str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(error) => {
+                self.invalid = self.eof || error.error_len().is_some();
+                error.valid_up_to()
+            }
+        };
+        let text = str::from_utf8(&self.pending[..valid])
+            .expect("Prefix is valid UTF-8.");
+        self.text.push_str(text);
+        self.pending.drain(..valid);
+    }
+
+    /// Run the parser on the text after the parsed records.
+    fn step(&mut self) -> Step<O> {
+        let text = &self.text[self.start..];
+        match (self.parser)(text) {
+            Ok((rest, record)) => {
+                return Step::Record(text.len() - rest.len(), record);
+            },
+            Err(Err::Incomplete(_)) => return Step::Incomplete,
+            Err(Err::Error(error)) | Err(Err::Failure(error)) => {
+                let offset = text.len() - error.input.len();
+                return Step::Failed(offset, error.expected.to_string());
+            }
+        }
+    }
+
+    /// Find the location of the offset from the next record in the full
+    /// input, with the snippet cut down to SNIPPET_SIZE characters on either
+    /// side.
+    fn location(&self, offset: usize) -> Location {
+        let line = &self.text[self.line_start..];
+        let found = Location::find(line, self.start - self.line_start + offset);
+        let kept = found.column - 1;
+        let (dropped_text, dropped) = match found.line {
+            1 => (self.dropped_text.as_str(), self.dropped),
+            _ => ("", 0)
+        };
+
+        let before: Vec<char> = dropped_text.chars()
+            .chain(found.snippet.chars().take(kept))
+            .collect();
+        let before = &before[before.len().saturating_sub(SNIPPET_SIZE)..];
+        let mut snippet = String::new();
+        if dropped + kept > before.len() {
+            snippet.push_str("...");
+        }
+        snippet.extend(before);
+        let snippet_column = snippet.chars().count() + 1;
+        let mut after = found.snippet.chars().skip(kept);
+        snippet.extend(after.by_ref().take(SNIPPET_SIZE));
+        if after.next().is_some() {
+            snippet.push_str("...");
+        }
+
+        return Location {
+            line: self.line + found.line,
+            column: dropped + kept + 1,
+            snippet: snippet,
+            snippet_column: snippet_column
+        };
+    }
+
+    /// Give a parse error at the offset from the next record.
+    fn error_at(&self, offset: usize, expected: impl Into<String>)
+        -> AocError {
+
+        let text = &self.text[self.start..];
+        let error = ParseError {
+            location: Some(self.location(offset)),
+            ..ParseError::expected_at(text, offset, expected)
+        };
+        return AocError::ParseInputError(error);
+    }
+
+    /// Give an error pointing out the first byte that is not valid UTF-8.
+    fn invalid_error(&self) -> AocError {
+        let error = ParseError {
+            location: Some(self.location(self.text.len() - self.start)),
+            ..ParseError::new("input is not valid UTF-8")
+        };
+        return AocError::ParseInputError(error);
+    }
+
+    /// Parse the next record, reading more input when needed.
+    fn next_record(&mut self) -> Result<Option<O>, AocError> {
+        loop {
+            if self.start == self.text.len() && self.invalid {
+                return Err(self.invalid_error());
+            }
+            if self.start == self.text.len() && self.eof {
+                return Ok(None);
+            }
+            match self.step() {
+                Step::Record(0, _) => {
+                    let message = "Record parser did not consume any input.";
+                    return Err(AocError::UnexpectedError(message.to_string()));
+                },
+                Step::Record(consumed, record) => {
+                    let end = self.start + consumed;
+                    let parsed = &self.text[self.start..end];
+                    for (i, b) in parsed.bytes().enumerate() {
+                        if b == b'\n' {
+                            self.line = self.line + 1;
+                            self.line_start = self.start + i + 1;
+                            self.dropped_text.clear();
+                            self.dropped = 0;
+                        }
+                    }
+                    self.start = end;
+                    return Ok(Some(record));
+                },
+                Step::Incomplete if self.invalid => {
+                    return Err(self.invalid_error());
+                },
+                Step::Incomplete if self.eof => {
+                    let end = self.text.len() - self.start;
+                    return Err(self.error_at(end, "more input"));
+                },
+                Step::Incomplete => self.refill()?,
+                Step::Failed(offset, expected) => {
+                    return Err(self.error_at(offset, expected));
+                }
+            }
+        }
+    }
+
+}
+
+impl<R, O, P> Iterator for Records<R, O, P>
+where
+    R: Read,
+    P: FnMut(&str) -> ParseResult<'_, O>,
+{
+
+    type Item = Result<O, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let record = self.next_record();
+        if !matches!(record, Ok(Some(_))) {
+            self.done = true;
+        }
+        return record.transpose();
+    }
+
+}
+
+/// Integer types that can be parsed by parse_int.
+pub trait Integer: Copy {
+
+    /// Name of the type used in error messages.
+    const NAME: &'static str;
+
+    /// Whether the type can hold negative numbers.
+    const SIGNED: bool;
+
+    /// The number zero.
+    const ZERO: Self;
+
+    /// Append a decimal digit to the number, subtracting it instead of adding
+    /// it for negative numbers. Gives None if the result does not fit.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+
+}
+
+/// Implement Integer for the primitive integer types.
+macro_rules! integer {
+    ($($t:ty: $signed:expr),*) => {
+        $(
+            impl Integer for $t {
+
+                const NAME: &'static str = stringify!($t);
+
+                const SIGNED: bool = $signed;
+
+                const ZERO: Self = 0;
+
+                fn push_digit(self, digit: u8, negative: bool)
+                    -> Option<Self> {
+
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        return shifted.checked_sub(digit as Self);
+                    } else {
+                        return shifted.checked_add(digit as Self);
+                    }
+                }
+
+            }
+        )*
+    };
+}
+
+integer!(u8: false, u16: false, u32: false, u64: false, u128: false,
+    usize: false, i8: true, i16: true, i32: true, i64: true, i128: true,
+    isize: true);
+
+/// Parser of decimal integers of any primitive integer type.
+///
+/// Signed types take an optional + or - in front of the digits, unsigned
+/// types take only digits. Numbers too large for the type give a failure with
+/// Expected.Overflow pointing at the start of the number, rather than an
+/// error other parsers could recover from. By default no underscores or
+/// whitespace is allowed and the parser is complete, these can be changed with
+/// the methods below.
+#[derive(Debug, Clone, Copy)]
+pub struct IntParser<T: Integer> {
+
+    /// Whether digits can be separated by single underscores, as in 1_000.
+    underscores: bool,
+
+    /// Whether spaces and tabs before the number are skipped.
+    whitespace: bool,
+
+    /// Whether running out of input in the number asks for more input.
+    streaming: bool,
+
+    /// The type parsed.
+    integer: PhantomData<T>
+
+}
+
+impl<T: Integer> IntParser<T> {
+
+    /// Create a parser of plain decimal integers.
+    pub fn new() -> Self {
+        return IntParser {
+            underscores: false,
+            whitespace: false,
+            streaming: false,
+            integer: PhantomData
+        };
+    }
+
+    /// Choose whether digits can be separated by single underscores.
+    pub fn underscores(mut self, underscores: bool) -> Self {
+        self.underscores = underscores;
+        return self;
+    }
+
+    /// Choose whether spaces and tabs before the number are skipped.
+    pub fn whitespace(mut self, whitespace: bool) -> Self {
+        self.whitespace = whitespace;
+        return self;
+    }
+
+    /// Choose whether the parser is streaming, for use with records.
+    ///
+    /// A streaming parser gives Err.Incomplete rather than a number when the
+    /// input ends where the number could continue.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        return self;
+    }
+
+}
+
+impl<T: Integer> Default for IntParser<T> {
+
+    fn default() -> Self {
+        return IntParser::new();
+    }
+
+}
+
+impl<'a, T: Integer> Parser<&'a str, T, InputError<'a>> for IntParser<T> {
+
+    fn parse(&mut self, input: &'a str) -> ParseResult<'a, T> {
+        let start = if self.whitespace {
+            input.trim_start_matches([' ', '\t'])
+        } else {
+            input
+        };
+        let (digits, negative) = match start.as_bytes().first() {
+            Some(b'-') if T::SIGNED => (&start[1..], true),
+            Some(b'+') if T::SIGNED => (&start[1..], false),
+            _ => (start, false)
+        };
+
+        let bytes = digits.as_bytes();
+        let mut value = T::ZERO;
+        let mut i = 0;
+        while i < bytes.len() {
+            let separator = self.underscores && bytes[i] == b'_' && i > 0
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+            if bytes[i].is_ascii_digit() {
+                value = value.push_digit(bytes[i] - b'0', negative)
+                    .ok_or(Err::Failure(InputError {
+                        input: start,
+                        expected: Expected::Overflow(T::NAME)
+                    }))?;
+            } else if !separator {
+                break;
+            }
+            i = i + 1;
+        }
+
+        let trailing = self.underscores && i + 1 == bytes.len()
+            && bytes[i] == b'_';
+        if self.streaming && (i == bytes.len() || trailing) {
+            return Err(Err::Incomplete(Needed::new(1)));
+        } else if i == 0 {
+            return Err(Err::Error(InputError {
+                input: start,
+                expected: Expected::Context("a number")
+            }));
+        }
+        return Ok((&digits[i..], value));
+    }
+
+}
+
+/// Parse a decimal integer of any primitive integer type from the input.
+///
+/// Will return the parsed integer together with any remaining input. See
+/// IntParser for the accepted format and for allowing underscores and
+/// whitespace.
+pub fn parse_int<T: Integer>(input: &str) -> ParseResult<'_, T> {
+    return IntParser::new().parse(input);
+}
+
 /// Parse an unsigned integer from the input.
 ///
 /// Will return the parsed integer together with any remaining input.
-pub fn parse_u32(input: &str) -> IResult<&str, u32> {
-    map_res(recognize(many1(one_of("0123456789"))),
-        |ns: &str| u32::from_str_radix(ns, 10)).parse(input)
+pub fn parse_u32(input: &str) -> ParseResult<'_, u32> {
+    return parse_int(input);
 }
 
 /// Parse an unsigned integer from the input.
 ///
 /// Will return the parsed integer together with any remaining input.
-pub fn parse_u64(input: &str) -> IResult<&str, u64> {
-    map_res(recognize(many1(one_of("0123456789"))),
-        |ns: &str| u64::from_str_radix(ns, 10)).parse(input)
+pub fn parse_u64(input: &str) -> ParseResult<'_, u64> {
+    return parse_int(input);
 }
 
 #[cfg(test)]
@@ -54,4 +883,360 @@ mod tests {
         assert!(parsed.is_err());
     }
 
+    /// Test that failing numbers are described by their context.
+    #[test]
+    fn test_parse_u32_error_context() {
+        let parsed = parse_u32("x1");
+        assert_eq!(parsed, Err(Err::Error(InputError {
+            input: "x1",
+            expected: Expected::Context("a number")
+        })));
+    }
+
+    /// Test that integers of all widths and signs are parsed.
+    #[test]
+    fn test_parse_int() {
+        assert_eq!(parse_int::<i32>("-123x"), Ok(("x", -123)));
+        assert_eq!(parse_int::<i8>("+12"), Ok(("", 12)));
+        assert_eq!(parse_int::<i8>("-128"), Ok(("", -128)));
+        assert_eq!(parse_int::<u128>("340282366920938463463374607431768211455"),
+            Ok(("", u128::MAX)));
+        assert_eq!(parse_int::<usize>("007 1"), Ok((" 1", 7)));
+        assert_eq!(parse_int::<u8>("-1"), Err(Err::Error(InputError {
+            input: "-1",
+            expected: Expected::Context("a number")
+        })));
+        assert!(parse_int::<i64>("-").is_err());
+        assert!(parse_int::<i64>("").is_err());
+    }
+
+    /// Test that numbers too large for the type give an overflow failure.
+    #[test]
+    fn test_parse_int_overflow() {
+        assert_eq!(parse_int::<u8>("256"), Err(Err::Failure(InputError {
+            input: "256",
+            expected: Expected::Overflow("u8")
+        })));
+        assert_eq!(parse_int::<i8>("-129"), Err(Err::Failure(InputError {
+            input: "-129",
+            expected: Expected::Overflow("i8")
+        })));
+
+        let error = parse_all(parse_u32, "99999999999").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 1, column 1: ",
+            "expected a number that fits in u32, found '9'\n",
+            "1 | 99999999999\n",
+            "  | ^"
+        ));
+    }
+
+    /// Test that underscores and whitespace are only allowed when asked for.
+    #[test]
+    fn test_int_parser_policy() {
+        let mut parser = IntParser::<i32>::new();
+        assert_eq!(parser.parse("1_000"), Ok(("_000", 1)));
+        assert!(parser.parse(" 1").is_err());
+
+        let mut parser = IntParser::<i32>::new()
+            .underscores(true)
+            .whitespace(true);
+        assert_eq!(parser.parse(" \t-1_000_000"), Ok(("", -1_000_000)));
+        assert_eq!(parser.parse("1__0"), Ok(("__0", 1)));
+        assert_eq!(parser.parse("1_"), Ok(("_", 1)));
+        assert!(parser.parse("_1").is_err());
+    }
+
+    /// Test that records are parsed one per line.
+    #[test]
+    fn test_lines_of() {
+        let mut parser = lines_of(whitespace_list(parse_u32));
+        assert_eq!(parser("1 2\r\n3\t4\n5"),
+            Ok(("", vec![vec![1, 2], vec![3, 4], vec![5]])));
+        assert_eq!(parser("1\n\n2\n"), Ok(("\n2\n", vec![vec![1]])));
+        assert_eq!(parser(""), Ok(("", vec![])));
+
+        let error = parse_all(lines_of(parse_u32), "1\n2\nx3\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 3, column 1: expected a number, found 'x'\n",
+            "3 | x3\n",
+            "  | ^"
+        ));
+
+        let error = parse_all(lines_of(parse_u32), "1\n2x\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 2: ",
+            "expected end of input, found 'x'\n",
+            "2 | 2x\n",
+            "  |  ^"
+        ));
+    }
+
+    /// Test that sections separated by blank lines are parsed.
+    #[test]
+    fn test_sections() {
+        use nom::character::complete::char;
+
+        let rule = separated_pair(parse_u32, char('|'), parse_u32);
+        let update = separated_list1(char(','), parse_u32);
+        let parser = sections(blank_line, lines_of(rule), lines_of(update));
+        assert_eq!(parse_all(parser, "1|2\n3|4\n\n1,2\n"),
+            Ok((vec![(1, 2), (3, 4)], vec![vec![1, 2]])));
+
+        let rule = separated_pair(parse_u32, char('|'), parse_u32);
+        let update = separated_list1(char(','), parse_u32);
+        let parser = sections(blank_line, lines_of(rule), lines_of(update));
+        let error = parse_all(parser, "1|2\n\n1,2\n3|4\n").unwrap_err();
+        assert!(error.to_string()
+            .starts_with("Parse error on line 4, column 2"));
+    }
+
+    /// Test that keys and values are parsed with spaces around the separator.
+    #[test]
+    fn test_key_value() {
+        use nom::character::complete::char;
+
+        let mut parser = key_value(parse_u64, char(':'),
+            whitespace_list(parse_u64));
+        assert_eq!(parser("190: 10 19"), Ok(("", (190, vec![10, 19]))));
+        assert_eq!(parser("190 :10"), Ok(("", (190, vec![10]))));
+        assert!(parser("190 10").is_err());
+    }
+
+    /// Reader giving a single byte at a time.
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() || buffer.is_empty() {
+                return Ok(0);
+            }
+            buffer[0] = self.0[0];
+            self.0 = &self.0[1..];
+            return Ok(1);
+        }
+
+    }
+
+    /// Parse a number followed by a line ending.
+    fn number_line(input: &str) -> ParseResult<'_, u32> {
+        use nom::character::streaming::line_ending;
+        use nom::sequence::terminated;
+
+        terminated(IntParser::new().streaming(true), line_ending)(input)
+    }
+
+    /// Test that records are parsed as more input is read.
+    #[test]
+    fn test_records() {
+        let parsed: Result<Vec<u32>, _> =
+            records(ByteReader(b"12\n345\r\n6"), number_line).collect();
+        assert_eq!(parsed, Ok(vec![12, 345, 6]));
+
+        let parsed: Result<Vec<u32>, _> =
+            records("".as_bytes(), number_line).collect();
+        assert_eq!(parsed, Ok(vec![]));
+
+        let mut parser = IntParser::<u32>::new().streaming(true);
+        assert_eq!(parser.parse("12"), Err(Err::Incomplete(Needed::new(1))));
+        assert_eq!(parser.parse("12 "), Ok((" ", 12)));
+    }
+
+    /// Test that characters cut off by a read are put back together.
+    #[test]
+    fn test_records_utf8() {
+        use nom::bytes::streaming::is_not;
+        use nom::character::streaming::char;
+        use nom::sequence::terminated;
+
+        fn word(input: &str) -> ParseResult<'_, String> {
+            let (input, word) = terminated(is_not("\n"), char('\n'))(input)?;
+            return Ok((input, word.to_string()));
+        }
+        let parsed: Result<Vec<String>, _> =
+            records(ByteReader("æø\nå".as_bytes()), word).collect();
+        assert_eq!(parsed, Ok(vec!["æø".to_string(), "å".to_string()]));
+    }
+
+    /// Test that errors point out the line and column in the full input.
+    #[test]
+    fn test_records_error() {
+        let mut parsed = records(ByteReader(b"1\n2\n3x\n4\n"), number_line);
+        assert_eq!(parsed.next(), Some(Ok(1)));
+        assert_eq!(parsed.next(), Some(Ok(2)));
+        let error = parsed.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 3, column 2: ",
+            "expected a line ending, found 'x'\n",
+            "3 | 3x\n",
+            "  |  ^"
+        ));
+        assert_eq!(parsed.next(), None);
+
+    }
+
+    /// Test that parsed input on a long line is dropped as more is read and
+    /// errors show the part of the line around them.
+    #[test]
+    fn test_records_long_line() {
+        use nom::character::streaming::one_of;
+        use nom::error::context;
+        use nom::sequence::terminated;
+
+        fn number(input: &str) -> ParseResult<'_, u32> {
+            let separator = context("a separator", one_of(" \n"));
+            terminated(IntParser::new().streaming(true), separator)(input)
+        }
+        let mut input = "12 ".repeat(100_000);
+        input.push_str("3x 4\n");
+        let mut parsed = records(input.as_bytes(), number);
+        for _ in 0..100_000 {
+            assert_eq!(parsed.next(), Some(Ok(12)));
+            assert!(parsed.text.len() <= 2 * READ_SIZE);
+        }
+        let error = parsed.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 1, column 300002: ",
+            "expected a separator, found 'x'\n",
+            "1 | ...12 12 12 12 12 12 12 12 12 12 12 12 12 3x 4\n",
+            "  |                                            ^"
+        ));
+
+        let input = format!("1 2x{}\n", "5".repeat(100));
+        let error = records(input.as_bytes(), number).nth(1).unwrap();
+        assert_eq!(error.unwrap_err().to_string(), format!(concat!(
+            "Parse error on line 1, column 4: ",
+            "expected a separator, found 'x'\n",
+            "1 | 1 2x{}...\n",
+            "  |    ^"
+        ), "5".repeat(39)));
+    }
+
+    /// Test that records before input that is not UTF-8 are parsed and the
+    /// record containing it is an error.
+    #[test]
+    fn test_records_invalid_utf8() {
+        let mut parsed = records(b"1\n\xff".as_slice(), number_line);
+        assert_eq!(parsed.next(), Some(Ok(1)));
+        let error = parsed.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 1: input is not valid UTF-8\n",
+            "2 | \n",
+            "  | ^"
+        ));
+        assert_eq!(parsed.next(), None);
+
+        let mut parsed = records(ByteReader(b"1\n23\xff\n4\n"), number_line);
+        assert_eq!(parsed.next(), Some(Ok(1)));
+        let error = parsed.next().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 3: input is not valid UTF-8\n",
+            "2 | 23\n",
+            "  |   ^"
+        ));
+    }
+
+    /// Test that grids of typed cells are parsed.
+    #[test]
+    fn test_grid() {
+        use nom::character::complete::{anychar, one_of};
+        use nom::combinator::map;
+        use nom::error::context;
+
+        let digit = map(one_of("0123456789"), |c| c as u8 - b'0');
+        let parsed = parse_all(grid(digit), "012\r\n345\r\n").unwrap();
+        let expected = Grid::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(Ok(parsed), expected);
+
+        let mut parser = grid(anychar);
+        let (rest, parsed) = parser("ab\ncd\n\nef").unwrap();
+        assert_eq!((rest, parsed.height()), ("\nef", 2));
+        let (rest, parsed) = parser("").unwrap();
+        assert_eq!((rest, parsed.height()), ("", 0));
+
+        let digit = context("a digit",
+            map(one_of("0123456789"), |c| c as u8 - b'0'));
+        let error = parse_all(grid(digit), "01\n2x\n").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 2: expected a digit, found 'x'\n",
+            "2 | 2x\n",
+            "  |  ^"
+        ));
+    }
+
+    /// Test that ragged rows are rejected with their row number.
+    #[test]
+    fn test_grid_ragged() {
+        use nom::character::complete::anychar;
+
+        let error = parse_all(grid(anychar), "abc\nde\nfgh").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 2, column 3: ",
+            "expected 3 cells on row 2, found '\\n'\n",
+            "2 | de\n",
+            "  |   ^"
+        ));
+
+        let error = parse_all(grid(anychar), "ab\ncd\nefg").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 3, column 3: ",
+            "expected 2 cells on row 3, found 'g'\n",
+            "3 | efg\n",
+            "  |   ^"
+        ));
+    }
+
+    /// Test that special cells are collected with their positions.
+    #[test]
+    fn test_grid_with() {
+        use nom::character::complete::{char, one_of};
+        use nom::combinator::{map, value};
+
+        let guard = value((false, 'N'), char('^'));
+        let cell = map(one_of(".#"), |c| c == '#');
+        let (map, guards) = parse_all(grid_with(guard, cell), "#..\n.^.\n")
+            .unwrap();
+        let expected = Grid::from_rows(vec![
+            vec![true, false, false],
+            vec![false, false, false]
+        ]);
+        assert_eq!(Ok(map), expected);
+        assert_eq!(guards, vec![((1, 1), 'N')]);
+    }
+
+    /// Test that parsing all input works when everything is consumed.
+    #[test]
+    fn test_parse_all_valid() {
+        assert_eq!(parse_all(parse_u32, "123"), Ok(123));
+    }
+
+    /// Test that errors point out where parsing stopped.
+    #[test]
+    fn test_parse_all_error_position() {
+        use nom::character::complete::char;
+        use nom::multi::separated_list1;
+
+        let parser = separated_list1(char('\n'), parse_u32);
+        let error = parse_all(parser, "12\n34\n5x\n").unwrap_err();
+        let expected = ParseError::expected_at("12\n34\n5x\n", 7,
+            "end of input");
+        assert_eq!(error, AocError::ParseInputError(expected));
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 3, column 2: ",
+            "expected end of input, found 'x'\n",
+            "3 | 5x\n",
+            "  |  ^"
+        ));
+
+        let parser = separated_list1(char('\n'), parse_u32);
+        let error = parse_all(parser, "12\nab").unwrap_err();
+        assert_eq!(error.to_string(), concat!(
+            "Parse error on line 1, column 3: ",
+            "expected end of input, found '\\n'\n",
+            "1 | 12\n",
+            "  |   ^"
+        ));
+    }
+
 }
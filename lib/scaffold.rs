@@ -0,0 +1,408 @@
+// Scaffolding of new days.
+//
+// A day is made of these files, relative to the root of the repository:
+//
+//    lib/dayNN/{mod,part1,part2}.rs    the solutions of the parts
+//    dayNN/part{1,2}/main.rs           thin binaries running the solutions
+//    tests/day-NN.rs                   tests of the examples, empty and actual
+//                                      input
+//    inputs/NN.txt                     the actual input of the puzzle, empty
+//    inputs/examples/NN.txt            the worked example of the puzzle
+//
+// A day is registered by adding its binaries to Cargo.toml, its module to
+// lib/lib.rs and its parts to aoc::PUZZLES. The answers of the example are
+// left to be filled in inputs/examples/answers.toml and the inputs are left
+// empty to be filled in, so the tests of the day fail until the inputs and
+// solution are in place. Inputs that already exist are kept.
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::aoc::AocError;
+use crate::aoc::answers::ANSWERS_FILE;
+use crate::aoc::examples::EXAMPLE_DIR;
+use crate::aoc::inputs::{read_path, INPUT_DIR};
+
+/// Names of the parts as written in the comments of the files of a day.
+const PART_NAMES: [&str; 2] = ["one", "two"];
+
+/// Number of days imported on each line of the use declaration in lib/aoc.rs.
+const DAYS_PER_LINE: usize = 6;
+
+/// Create and register the files of a new day in the repository at the root.
+///
+/// Nothing is written unless all files can be generated. Gives the paths of
+/// the files created or changed, relative to the root.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if the day is not between 1 and 25,
+/// already exists or the registries can not be found, and AocError.IoError if
+/// a file can not be read or written.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::UnexpectedError(
+            format!("Invalid day {}, expected a day from 1 to 25.", day)));
+    }
+    let module = format!("day{:02}", day);
+    if root.join("lib").join(&module).exists() {
+        return Err(AocError::UnexpectedError(
+            format!("Day {} already exists in lib/{}.", day, module)));
+    }
+
+    let read = |path: &str| {
+        let bytes = read_path(&root.join(path))?;
+        return String::from_utf8(bytes).map_err(|_| AocError::IoError(
+            format!("{} is not valid UTF-8.", path)));
+    };
+    let answers_path = format!("{}/{}", EXAMPLE_DIR, ANSWERS_FILE);
+    let answers = match root.join(&answers_path).is_file() {
+        true => read(&answers_path)?,
+        false => String::new()
+    };
+
+    let mut files = vec![
+        (format!("lib/{}/mod.rs", module),
+            "pub mod part1;\npub mod part2;\n".to_string()),
+        (format!("lib/{}/part1.rs", module), solution(day, 1)),
+        (format!("lib/{}/part2.rs", module), solution(day, 2)),
+        (format!("{}/part1/main.rs", module), binary(day, 1)),
+        (format!("{}/part2/main.rs", module), binary(day, 2)),
+        (format!("tests/day-{:02}.rs", day), tests(day)),
+        (format!("{}/{:02}.txt", INPUT_DIR, day), String::new()),
+        (format!("{}/{:02}.txt", EXAMPLE_DIR, day), String::new()),
+        (answers_path, register_answers(&answers, day)),
+        ("Cargo.toml".to_string(), register_bins(&read("Cargo.toml")?, day)?),
+        ("lib/lib.rs".to_string(), register_module(&read("lib/lib.rs")?, day)?),
+        ("lib/aoc.rs".to_string(), register_puzzles(&read("lib/aoc.rs")?, day)?)
+    ];
+    files.retain(|(path, _)| {
+        !(path.ends_with(".txt") && root.join(path).exists())
+    });
+
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        let full_path = root.join(&path);
+        let write = |full_path: &Path| -> std::io::Result<()> {
+            if let Some(dir) = full_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            return fs::write(full_path, &contents);
+        };
+        write(&full_path).map_err(|err| AocError::IoError(
+            format!("Could not write {}: {}", path, err)))?;
+        written.push(PathBuf::from(path));
+    }
+
+    return Ok(written);
+}
+
+/// The solution of a part of the day, implementing Solution with a solve
+/// function that is still to be written.
+fn solution(day: u8, part: u8) -> String {
+    return format!("\
+// --- Day {day} ---
+//
+// Puzzle text of part {name}.
+use std::io::BufRead;
+use crate::aoc::{{AocError, Solution}};
+
+/// Solution of part {name} of the puzzle.
+pub struct Solver;
+
+impl Solution for Solver {{
+
+    type Input = String;
+
+    type Answer = u64;
+
+    fn parse(reader: &mut dyn BufRead) -> Result<Self::Input, AocError> {{
+        parse(reader)
+    }}
+
+    fn solve(input: &Self::Input) -> Result<Self::Answer, AocError> {{
+        solve(input)
+    }}
+
+}}
+
+/// Parse the puzzle input.
+pub fn parse(reader: &mut dyn BufRead) -> Result<String, AocError> {{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    return Ok(input);
+}}
+
+/// Compute the answer to the puzzle.
+pub fn solve(_input: &str) -> Result<u64, AocError> {{
+    return Err(AocError::UnexpectedError(
+        \"Day {day} part {part} is not solved yet.\".to_string()));
+}}
+", day = day, part = part, name = PART_NAMES[part as usize - 1]);
+}
+
+/// The binary of a part of the day.
+fn binary(day: u8, part: u8) -> String {
+    return format!("\
+// --- Day {day} ---
+//
+// Part {name}. Thin wrapper running aoc2024::day{day:02}::part{part} on stdin.
+use std::process::ExitCode;
+
+fn main() -> ExitCode {{
+    aoc2024::aoc::main({day}, {part})
+}}
+", day = day, part = part, name = PART_NAMES[part as usize - 1]);
+}
+
+/// The tests of the day, with placeholders for the answers of the empty and
+/// actual input.
+fn tests(day: u8) -> String {
+    let mut tests = format!("\
+use std::fs;
+use assert_cmd::Command;
+
+aoc2024::example_tests!({day});
+", day = day);
+    for part in 1..=2 {
+        tests.push_str(&format!("
+/// Test that if we pass no lines we will get back 0.
+#[test]
+fn test_part_{part}_empty_input() {{
+    Command::cargo_bin(\"day{day:02}_{part}\")
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq(\"0\\n\"));
+}}
+
+/// Test that if we pass actual input we will compute expected output.
+#[test]
+fn test_part_{part}_actual_input() {{
+    let input: String = fs::read_to_string(\"inputs/{day:02}.txt\").unwrap();
+
+    Command::cargo_bin(\"day{day:02}_{part}\")
+        .unwrap()
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq(\"the answer of part {part}\\n\"));
+}}
+", day = day, part = part));
+    }
+    return tests;
+}
+
+/// Add a table for the answers of the example of the day to the example
+/// answers file.
+fn register_answers(answers: &str, day: u8) -> String {
+    let mut answers = answers.trim_end().to_string();
+    if !answers.is_empty() {
+        answers.push_str("\n\n");
+    }
+    answers.push_str(&format!("[day{:02}]\n# part1 = \n# part2 = \n", day));
+    return answers;
+}
+
+/// Add the binaries of the day to Cargo.toml, before the binaries of any
+/// later day.
+fn register_bins(manifest: &str, day: u8) -> Result<String, AocError> {
+    let stanzas: String = (1..=2)
+        .map(|part| format!("[[bin]]\nname = \"day{:02}_{}\"\n\
+            path = \"day{:02}/part{}/main.rs\"\n\n", day, part, day, part))
+        .collect();
+
+    let later = manifest.match_indices("[[bin]]\nname = \"day")
+        .find(|(index, stanza)| {
+            let number = &manifest[index + stanza.len()..];
+            number.get(..2).and_then(|n| n.parse::<u8>().ok())
+                .is_some_and(|n| n > day)
+        });
+    let index = match later {
+        Some((index, _)) => index,
+        None => {
+            let last = manifest.rfind("[[bin]]").ok_or_else(|| missing(
+                "Cargo.toml", "[[bin]]"))?;
+            manifest[last..].find("\n\n")
+                .map(|end| last + end + 2)
+                .unwrap_or(manifest.len())
+        }
+    };
+
+    let mut manifest = manifest.to_string();
+    if later.is_none() && index == manifest.len() {
+        manifest.push('\n');
+        manifest.push_str(stanzas.trim_end());
+        manifest.push('\n');
+    } else {
+        manifest.insert_str(index, &stanzas);
+    }
+    return Ok(manifest);
+}
+
+/// Add the module of the day to lib/lib.rs, after the modules of the earlier
+/// days.
+fn register_module(lib: &str, day: u8) -> Result<String, AocError> {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let days: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod day"))
+        .collect();
+    let last = days.last().ok_or_else(|| missing("lib/lib.rs", "pub mod day"))?;
+    let index = days.iter()
+        .find(|&&i| lines[i] > format!("pub mod day{:02};", day).as_str())
+        .copied()
+        .unwrap_or(last + 1);
+
+    let module = format!("pub mod day{:02};", day);
+    lines.insert(index, &module);
+    return Ok(lines.join("\n") + "\n");
+}
+
+/// Add the day to the modules used by lib/aoc.rs and its parts to PUZZLES,
+/// keeping the puzzles ordered by day.
+fn register_puzzles(aoc: &str, day: u8) -> Result<String, AocError> {
+    let use_start = "use crate::{\n";
+    let start = aoc.find(use_start)
+        .ok_or_else(|| missing("lib/aoc.rs", use_start))?;
+    let end = start + aoc[start..].find("};\n")
+        .ok_or_else(|| missing("lib/aoc.rs", "};"))?;
+    let mut days: Vec<String> = aoc[start + use_start.len()..end]
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    days.push(format!("day{:02}", day));
+    days.sort();
+    let rows: Vec<String> = days.chunks(DAYS_PER_LINE)
+        .map(|row| format!("    {}", row.join(", ")))
+        .collect();
+    let mut aoc = format!("{}{}{}\n{}", &aoc[..start], use_start,
+        rows.join(",\n"), &aoc[end..]);
+
+    let puzzles_start = "pub const PUZZLES: &[Puzzle] = &[\n";
+    let start = aoc.find(puzzles_start)
+        .ok_or_else(|| missing("lib/aoc.rs", puzzles_start))?
+        + puzzles_start.len();
+    let end = start + aoc[start..].find("];\n")
+        .ok_or_else(|| missing("lib/aoc.rs", "];"))?;
+    let mut index = start;
+    for line in aoc[start..end].split_inclusive('\n') {
+        let later = line.trim_start().strip_prefix("Puzzle { day: ")
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|n| n.parse::<u8>().ok())
+            .is_some_and(|n| n > day);
+        if later {
+            break;
+        }
+        index = index + line.len();
+    }
+    let entries: String = (1..=2)
+        .map(|part| format!("    Puzzle {{ day: {day}, part: {part}, \
+            entry: run_solution::<day{day:02}::part{part}::Solver> }},\n",
+            day = day, part = part))
+        .collect();
+    aoc.insert_str(index, &entries);
+
+    return Ok(aoc);
+}
+
+/// Error for a file missing the text a day is registered next to.
+fn missing(path: &str, text: &str) -> AocError {
+    return AocError::UnexpectedError(
+        format!("Could not find {:?} in {}.", text.trim_end(), path));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// Test that binaries are registered in order of the days.
+    #[test]
+    fn test_register_bins() {
+        let manifest = concat!(
+            "[[bin]]\nname = \"day01_1\"\npath = \"day01/part1/main.rs\"\n\n",
+            "[[bin]]\nname = \"day03_1\"\npath = \"day03/part1/main.rs\"\n\n",
+            "[lints.clippy]\n"
+        );
+        let registered = register_bins(manifest, 2).unwrap();
+        assert!(registered.starts_with(concat!(
+            "[[bin]]\nname = \"day01_1\"\npath = \"day01/part1/main.rs\"\n\n",
+            "[[bin]]\nname = \"day02_1\"\npath = \"day02/part1/main.rs\"\n\n",
+            "[[bin]]\nname = \"day02_2\"\npath = \"day02/part2/main.rs\"\n\n",
+            "[[bin]]\nname = \"day03_1\"")));
+
+        let registered = register_bins(manifest, 4).unwrap();
+        assert!(registered.ends_with(
+            "path = \"day04/part2/main.rs\"\n\n[lints.clippy]\n"));
+        assert!(register_bins("[lib]\n", 4).is_err());
+    }
+
+    /// Test that modules and puzzles are registered in order of the days.
+    #[test]
+    fn test_register_module_and_puzzles() {
+        let lib = "pub mod aoc;\npub mod day01;\npub mod day03;\n";
+        assert_eq!(register_module(lib, 2).unwrap(),
+            "pub mod aoc;\npub mod day01;\npub mod day02;\npub mod day03;\n");
+        assert_eq!(register_module(lib, 4).unwrap(),
+            "pub mod aoc;\npub mod day01;\npub mod day03;\npub mod day04;\n");
+
+        let aoc = concat!(
+            "use crate::{\n    day01, day03\n};\n\n",
+            "pub const PUZZLES: &[Puzzle] = &[\n",
+            "    Puzzle { day: 1, part: 1, entry: e },\n",
+            "    Puzzle { day: 3, part: 1, entry: e },\n",
+            "];\n"
+        );
+        assert_eq!(register_puzzles(aoc, 2).unwrap(), concat!(
+            "use crate::{\n    day01, day02, day03\n};\n\n",
+            "pub const PUZZLES: &[Puzzle] = &[\n",
+            "    Puzzle { day: 1, part: 1, entry: e },\n",
+            "    Puzzle { day: 2, part: 1, ",
+            "entry: run_solution::<day02::part1::Solver> },\n",
+            "    Puzzle { day: 2, part: 2, ",
+            "entry: run_solution::<day02::part2::Solver> },\n",
+            "    Puzzle { day: 3, part: 1, entry: e },\n",
+            "];\n"
+        ));
+        assert!(register_puzzles("", 2).is_err());
+    }
+
+    /// Test that a day is created and registered in the repository, and that
+    /// it can not be created twice.
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir()
+            .join(format!("aoc2024-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("lib")).unwrap();
+        for path in ["Cargo.toml", "lib/lib.rs", "lib/aoc.rs"] {
+            fs::copy(path, root.join(path)).unwrap();
+        }
+
+        let written = new_day(&root, 13).unwrap();
+        assert_eq!(written.len(), 12);
+        assert!(root.join("day13/part2/main.rs").is_file());
+        assert!(root.join("inputs/examples/13.txt").is_file());
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!(read("inputs/13.txt"), "");
+        assert!(read("tests/day-13.rs")
+            .contains("fn test_part_2_empty_input() {"));
+        assert!(read("lib/day13/part1.rs")
+            .contains("\"Day 13 part 1 is not solved yet.\""));
+        assert!(read("lib/lib.rs")
+            .ends_with("pub mod day12;\npub mod day13;\n"));
+        assert!(read("lib/aoc.rs").contains("day13::part2::Solver> },\n];"));
+        assert!(read("Cargo.toml").contains("path = \"day13/part2/main.rs\""));
+        assert_eq!(read("inputs/examples/answers.toml"),
+            "[day13]\n# part1 = \n# part2 = \n");
+
+        assert!(new_day(&root, 13).is_err());
+        assert!(new_day(&root, 26).is_err());
+
+        fs::write(root.join("inputs/14.txt"), "14\n").unwrap();
+        assert_eq!(new_day(&root, 14).unwrap().len(), 11);
+        assert_eq!(read("inputs/14.txt"), "14\n");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+}
@@ -0,0 +1,278 @@
+// Verification of the puzzle solutions against known answers.
+//
+// Every part of a puzzle is run once on the main input of the day from the
+// input store, and the answer is compared to the answer stored next to the
+// input, see aoc::answers. The results are summarized in a matrix with a row
+// per day and a column per part, giving the time taken by each part:
+//
+//    day  part 1           part 2
+//      1  pass       0.512 pass       0.601
+//      6  FAIL      12.004 new      812.310
+//
+// followed by the details of every part that did not pass.
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use crate::aoc::{AocError, Outcome, Puzzle, PUZZLES};
+use crate::aoc::answers::Answers;
+use crate::aoc::inputs::{read_path, InputStore};
+use crate::aoc::output::millis;
+
+/// How a part of a puzzle did compared to its known answer.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Status {
+
+    /// The answer is the known answer.
+    Pass,
+
+    /// The answer is not the known answer, which is given.
+    Fail(String),
+
+    /// There is no known answer for the part.
+    New,
+
+    /// The part could not be run, with the reason why.
+    Error(String)
+
+}
+
+/// The result of verifying a single part of a puzzle.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Check {
+
+    /// The day of the puzzle.
+    pub day: u8,
+
+    /// The part of the puzzle.
+    pub part: u8,
+
+    /// How the part did compared to its known answer.
+    pub status: Status,
+
+    /// The outcome of running the part, unless it could not be run.
+    pub outcome: Option<Outcome>
+
+}
+
+impl Check {
+
+    /// Whether the part failed, either giving a wrong answer or not running.
+    pub fn failed(&self) -> bool {
+        return matches!(self.status, Status::Fail(_) | Status::Error(_));
+    }
+
+}
+
+/// Run a part of a puzzle on the input and compare the answer to the expected
+/// answer, if known.
+pub fn check_puzzle(puzzle: &Puzzle, input: &[u8], expected: Option<&str>)
+    -> Check {
+
+    let outcome = match (puzzle.entry)(&mut &input[..], None) {
+        Ok(outcome) => outcome,
+        Err(err) => return error_check(puzzle, err)
+    };
+    let status = match expected {
+        Some(expected) if expected == outcome.answer => Status::Pass,
+        Some(expected) => Status::Fail(expected.to_string()),
+        None => Status::New
+    };
+
+    return Check {
+        day: puzzle.day,
+        part: puzzle.part,
+        status: status,
+        outcome: Some(outcome)
+    };
+}
+
+/// Verify both parts of the given day, or of all days if no day is given.
+///
+/// The main input of each day is read from the input store, and compared to
+/// the answers stored in the directory it is found in. A missing input is
+/// reported as an error of the parts of its day.
+///
+/// # Errors
+///
+/// Will give AocError.UnexpectedError if there is no solution for the day and
+/// an error if an answers file can not be read.
+pub fn verify(day: Option<u8>) -> Result<Vec<Check>, AocError> {
+    let puzzles: Vec<&Puzzle> = PUZZLES.iter()
+        .filter(|p| day.is_none() || day == Some(p.day))
+        .collect();
+    if puzzles.is_empty() {
+        return Err(AocError::UnexpectedError(
+                format!("No solution for day {}.", day.unwrap_or(0))));
+    }
+
+    let store = InputStore::from_env();
+    let mut answers: HashMap<PathBuf, Answers> = HashMap::new();
+    let mut checks = Vec::new();
+    for puzzle in puzzles {
+        let path = match store.find(puzzle.day, None) {
+            Ok(path) => path,
+            Err(err) => {
+                checks.push(error_check(puzzle, err));
+                continue;
+            }
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        if !answers.contains_key(dir) {
+            answers.insert(dir.to_path_buf(), Answers::read_dir(dir)?);
+        }
+
+        let check = match read_path(&path) {
+            Ok(input) => {
+                let expected = answers[dir].get(puzzle.day, None, puzzle.part);
+                check_puzzle(puzzle, &input, expected)
+            },
+            Err(err) => error_check(puzzle, err)
+        };
+        checks.push(check);
+    }
+
+    return Ok(checks);
+}
+
+/// Format the checks as a matrix with a row per day and a column per part,
+/// followed by the details of the parts that did not pass and a summary.
+///
+/// Times are the parse and solve times together in milliseconds.
+pub fn to_matrix(checks: &[Check]) -> String {
+    let mut days: Vec<u8> = checks.iter().map(|c| c.day).collect();
+    days.dedup();
+    let cell = |day: u8, part: u8| {
+        let Some(check) = checks.iter()
+            .find(|c| c.day == day && c.part == part) else {
+
+            return "-".to_string();
+        };
+        let status = match check.status {
+            Status::Pass => "pass",
+            Status::Fail(_) => "FAIL",
+            Status::New => "new",
+            Status::Error(_) => "ERROR"
+        };
+        let time = check.outcome.as_ref()
+            .map(|o| millis(o.parse_time + o.solve_time))
+            .unwrap_or_default();
+        return format!("{:<5} {:>10}", status, time);
+    };
+
+    let mut matrix = String::new();
+    let _ = writeln!(matrix, "day  {:<16} part 2", "part 1");
+    for &day in days.iter() {
+        let row = format!("{:>3}  {:<16} {}", day, cell(day, 1), cell(day, 2));
+        let _ = writeln!(matrix, "{}", row.trim_end());
+    }
+
+    let mut details = String::new();
+    for check in checks {
+        let answer = check.outcome.as_ref().map(|o| o.answer.as_str());
+        let _ = match (&check.status, answer) {
+            (Status::Fail(expected), Some(answer)) => writeln!(details,
+                "Day {} part {}: expected {}, got {}.", check.day, check.part,
+                expected, answer),
+            (Status::New, Some(answer)) => writeln!(details,
+                "Day {} part {}: no known answer, got {}.", check.day,
+                check.part, answer),
+            (Status::Error(message), _) => writeln!(details,
+                "Day {} part {}: {}", check.day, check.part, message),
+            _ => Ok(())
+        };
+    }
+    if !details.is_empty() {
+        let _ = write!(matrix, "\n{}", details);
+    }
+
+    let count = |status: fn(&Status) -> bool| {
+        checks.iter().filter(|c| status(&c.status)).count()
+    };
+    let _ = writeln!(matrix, "\n{} passed, {} failed, {} without answers.",
+        count(|s| *s == Status::Pass),
+        count(|s| matches!(s, Status::Fail(_) | Status::Error(_))),
+        count(|s| *s == Status::New));
+
+    return matrix;
+}
+
+/// A check of a part of a puzzle that could not be run.
+fn error_check(puzzle: &Puzzle, err: AocError) -> Check {
+    return Check {
+        day: puzzle.day,
+        part: puzzle.part,
+        status: Status::Error(err.to_string()),
+        outcome: None
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::aoc::find_puzzle;
+
+    /// Test that answers are compared to the known answers.
+    #[test]
+    fn test_check_puzzle() {
+        let input = b"2333133121414131402\n";
+
+        let check = check_puzzle(find_puzzle(9, 1).unwrap(), input,
+            Some("1928"));
+        assert_eq!((check.day, check.part), (9, 1));
+        assert_eq!(check.status, Status::Pass);
+        assert!(!check.failed());
+
+        let check = check_puzzle(find_puzzle(9, 2).unwrap(), input, Some("1"));
+        assert_eq!(check.status, Status::Fail("1".to_string()));
+        assert!(check.failed());
+
+        let check = check_puzzle(find_puzzle(1, 1).unwrap(), b"1 2\n", None);
+        assert_eq!(check.status, Status::New);
+
+        let check = check_puzzle(find_puzzle(1, 1).unwrap(), b"x\n", None);
+        assert!(matches!(check.status, Status::Error(_)));
+        assert_eq!(check.outcome, None);
+    }
+
+    /// Test that checks are formatted as a matrix with details.
+    #[test]
+    fn test_to_matrix() {
+        let outcome = |answer: &str| Some(Outcome {
+            answer: answer.to_string(),
+            parse_time: Duration::from_micros(500),
+            solve_time: Duration::from_micros(12)
+        });
+        let checks = [
+            Check {
+                day: 1,
+                part: 1,
+                status: Status::Pass,
+                outcome: outcome("11")
+            },
+            Check {
+                day: 1,
+                part: 2,
+                status: Status::Fail("31".to_string()),
+                outcome: outcome("30")
+            },
+            Check {
+                day: 12,
+                part: 1,
+                status: Status::Error("No input.".to_string()),
+                outcome: None
+            }
+        ];
+        assert_eq!(to_matrix(&checks), concat!(
+            "day  part 1           part 2\n",
+            "  1  pass       0.512 FAIL       0.512\n",
+            " 12  ERROR            -\n",
+            "\n",
+            "Day 1 part 2: expected 31, got 30.\n",
+            "Day 12 part 1: No input.\n",
+            "\n",
+            "1 passed, 2 failed, 0 without answers.\n"
+        ));
+    }
+
+}
@@ -0,0 +1,247 @@
+use std::fs;
+use assert_cmd::Command;
+
+/// Test that running a day with an input file computes the expected output.
+#[test]
+fn test_run_input_file() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "1", "1", "--input", "inputs/01.txt"])
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("2086478\n"));
+}
+
+/// Test that the outcome is printed in the format chosen by the flag or the
+/// environment, the flag taking precedence.
+#[test]
+fn test_run_format() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .env("AOC_FORMAT", "tsv")
+        .args(["run", "1", "1", "--input", "inputs/01.txt"])
+        .args(["--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(
+            "^\\{\"day\":1,\"part\":1,\"answer\":2086478,\
+            \"parse_ms\":[0-9]+\\.[0-9]{3},\
+            \"solve_ms\":[0-9]+\\.[0-9]{3}\\}\n$")
+            .unwrap());
+
+    Command::cargo_bin("day01_1")
+        .unwrap()
+        .env("AOC_FORMAT", "tsv")
+        .pipe_stdin("inputs/01.txt")
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(
+            "^1\t1\t2086478\t[0-9.]+\t[0-9.]+\n$").unwrap());
+
+    Command::cargo_bin("day01_1")
+        .unwrap()
+        .args(["--format", "xml"])
+        .write_stdin("")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicates::str::starts_with("Unknown output format 'xml'"));
+}
+
+/// Test that explaining prints notes to stderr and only the answer to stdout.
+#[test]
+fn test_run_explain() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "2", "1", "--input", "inputs/examples/02.txt"])
+        .arg("--explain")
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("2\n"))
+        .stderr(predicates::str::contains(
+            "1 2 7 8 9: unsafe, 2 to 7 rises by 5\n"));
+
+    Command::cargo_bin("day07_2")
+        .unwrap()
+        .args(["--explain", "--format", "json"])
+        .pipe_stdin("inputs/examples/07.txt")
+        .unwrap()
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("{\"day\":7,\"part\":2,\
+            \"answer\":11387,"))
+        .stderr(predicates::str::contains(
+            "{\"day\":7,\"part\":2,\"record\":\"156: 15 6\",\
+            \"verdict\":\"solvable\",\"reason\":\"15 || 6\"}\n"));
+}
+
+/// Test that verifying the inputs in the repository passes.
+#[test]
+fn test_verify() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["verify", "1"])
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(
+            "^day  part 1           part 2\n  1  pass +[0-9.]+ pass +[0-9.]+\n")
+            .unwrap())
+        .stdout(predicates::str::ends_with(
+            "2 passed, 0 failed, 0 without answers.\n"));
+}
+
+/// Test that wrong answers fail verification.
+#[test]
+fn test_verify_wrong_answer() {
+    let dir = std::env::temp_dir()
+        .join(format!("aoc2024-verify-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("01.txt"), "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n").unwrap();
+    fs::write(dir.join("answers.toml"), "[day01]\npart2 = 30\n").unwrap();
+
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .env("AOC_INPUT_DIR", &dir)
+        .args(["verify", "1"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(predicates::str::contains("  1  new  "))
+        .stdout(predicates::str::contains(
+            "Day 1 part 1: no known answer, got 11.\n\
+            Day 1 part 2: expected 30, got 31.\n"))
+        .stderr(predicates::ord::eq("1 of 2 parts failed verification.\n"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// Test that named inputs are read from the input directory.
+#[test]
+fn test_run_named_input() {
+    let dir = std::env::temp_dir()
+        .join(format!("aoc2024-named-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("01-example.txt"), "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n")
+        .unwrap();
+
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .env("AOC_INPUT_DIR", &dir)
+        .args(["run", "1", "1", "--name", "example"])
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("11\n"));
+
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .env("AOC_INPUT_DIR", &dir)
+        .args(["run", "1", "1", "--name", "alt"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicates::str::starts_with("No input 01-alt.txt for day 1"))
+        .stderr(predicates::str::contains("found for the day: example"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// Test that a missing input file is reported with its path.
+#[test]
+fn test_run_missing_input_file() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "1", "1", "--input", "inputs/missing.txt"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicates::str::starts_with(
+            "Could not read inputs/missing.txt"));
+}
+
+/// Test that running a day without an input file reads stdin.
+#[test]
+fn test_run_stdin() {
+    let input: String = fs::read_to_string("inputs/09.txt").unwrap();
+
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "9", "2"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("6353648390778\n"));
+}
+
+/// Test that running a day with the input file - reads stdin.
+#[test]
+fn test_run_stdin_file() {
+    let input: String = fs::read_to_string("inputs/09.txt").unwrap();
+
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "9", "2", "--input", "-"])
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("6353648390778\n"));
+}
+
+/// Test that empty stdin that is not a terminal, like /dev/null, is ignored
+/// and the main input of the day is read instead.
+#[test]
+fn test_run_empty_stdin() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "1", "1"])
+        .write_stdin("")
+        .assert()
+        .success()
+        .stdout(predicates::ord::eq("2086478\n"));
+}
+
+/// Test that running a day without a solution fails.
+#[test]
+fn test_run_missing_day() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "25", "1"])
+        .assert()
+        .failure()
+        .code(1);
+}
+
+/// Test that invalid arguments print usage and fail.
+#[test]
+fn test_invalid_arguments() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["run", "1"])
+        .assert()
+        .failure()
+        .code(2)
+        .stderr(predicates::str::contains("Usage: aoc run"));
+}
+
+/// Test that benchmarking a day reports timings for both parts as JSON.
+#[test]
+fn test_bench_json() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["bench", "1", "--runs", "2", "--json"])
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("[{\"day\":1,\"part\":1,\"runs\":2,"))
+        .stdout(predicates::str::contains("{\"day\":1,\"part\":2,\"runs\":2,"));
+}
+
+/// Test that benchmarking a day without a solution fails.
+#[test]
+fn test_bench_missing_day() {
+    Command::cargo_bin("aoc")
+        .unwrap()
+        .args(["bench", "25"])
+        .assert()
+        .failure()
+        .code(1);
+}
@@ -1,6 +1,8 @@
 use std::fs;
 use assert_cmd::Command;
 
+aoc2024::example_tests!(1);
+
 /// Test that if we pass no lines we will get back 0.
 #[test]
 fn test_part_1_empty_input() {
@@ -11,26 +13,6 @@ fn test_part_1_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat!(
-        "3   4\n",
-        "4   3\n",
-        "2   5\n",
-        "1   3\n",
-        "3   9\n",
-        "3   3\n"
-    );
-
-    Command::cargo_bin("day01_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("11\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_1_actual_input() {
@@ -54,26 +36,6 @@ fn test_part_2_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat!(
-        "3   4\n",
-        "4   3\n",
-        "2   5\n",
-        "1   3\n",
-        "3   9\n",
-        "3   3\n"
-    );
-
-    Command::cargo_bin("day01_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("31\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
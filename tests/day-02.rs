@@ -1,6 +1,8 @@
 use std::fs;
 use assert_cmd::Command;
 
+aoc2024::example_tests!(2);
+
 /// Test that if we pass no lines we will get back 0.
 #[test]
 fn test_part_1_empty_input() {
@@ -11,25 +13,6 @@ fn test_part_1_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat!(
-        "7 6 4 2 1\n",
-        "1 2 7 8 9\n",
-        "9 7 6 2 1\n",
-        "1 3 2 4 5\n",
-        "8 6 4 4 1\n",
-        "1 3 6 7 9\n");
-
-    Command::cargo_bin("day02_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("2\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_1_actual_input() {
@@ -53,25 +36,6 @@ fn test_part_2_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat!(
-        "7 6 4 2 1\n",
-        "1 2 7 8 9\n",
-        "9 7 6 2 1\n",
-        "1 3 2 4 5\n",
-        "8 6 4 4 1\n",
-        "1 3 6 7 9\n");
-
-    Command::cargo_bin("day02_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("4\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
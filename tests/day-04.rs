@@ -1,6 +1,8 @@
 use std::fs;
 use assert_cmd::Command;
 
+aoc2024::example_tests!(4);
+
 /// Test that if we pass no lines we will get back 0.
 #[test]
 fn test_part_1_empty_input() {
@@ -11,30 +13,6 @@ fn test_part_1_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat![
-        "MMMSXXMASM\n",
-        "MSAMXMSMSA\n",
-        "AMXSXMAAMM\n",
-        "MSAMASMSMX\n",
-        "XMASAMXAMM\n",
-        "XXAMMXXAMA\n",
-        "SMSMSASXSS\n",
-        "SAXAMASAAA\n",
-        "MAMMMXMMMM\n",
-        "MXMXAXMASX"
-    ];
-
-    Command::cargo_bin("day04_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("18\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_1_actual_input() {
@@ -58,30 +36,6 @@ fn test_part_2_empty_input() {
         .stdout(predicates::ord::eq("0\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat![
-        "MMMSXXMASM\n",
-        "MSAMXMSMSA\n",
-        "AMXSXMAAMM\n",
-        "MSAMASMSMX\n",
-        "XMASAMXAMM\n",
-        "XXAMMXXAMA\n",
-        "SMSMSASXSS\n",
-        "SAXAMASAAA\n",
-        "MAMMMXMMMM\n",
-        "MXMXAXMASX"
-    ];
-
-    Command::cargo_bin("day04_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("9\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
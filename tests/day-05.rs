@@ -1,47 +1,7 @@
 use std::fs;
 use assert_cmd::Command;
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat![
-        "47|53\n",
-        "97|13\n",
-        "97|61\n",
-        "97|47\n",
-        "75|29\n",
-        "61|13\n",
-        "75|53\n",
-        "29|13\n",
-        "97|29\n",
-        "53|29\n",
-        "61|53\n",
-        "97|53\n",
-        "61|29\n",
-        "47|13\n",
-        "75|47\n",
-        "97|75\n",
-        "47|61\n",
-        "75|61\n",
-        "47|29\n",
-        "75|13\n",
-        "53|13\n",
-        "\n",
-        "75,47,61,53,29\n",
-        "97,61,53,29,13\n",
-        "75,29,13\n",
-        "75,97,47,61,53\n",
-        "61,13,29\n",
-        "97,13,75,29,47\n"
-    ];
-
-    Command::cargo_bin("day05_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("143\n"));
-}
+aoc2024::example_tests!(5);
 
 /// Test that if we pass actual input we will compute expected output.
 #[test]
@@ -56,48 +16,6 @@ fn test_part_1_actual_input() {
         .stdout(predicates::ord::eq("5248\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat![
-        "47|53\n",
-        "97|13\n",
-        "97|61\n",
-        "97|47\n",
-        "75|29\n",
-        "61|13\n",
-        "75|53\n",
-        "29|13\n",
-        "97|29\n",
-        "53|29\n",
-        "61|53\n",
-        "97|53\n",
-        "61|29\n",
-        "47|13\n",
-        "75|47\n",
-        "97|75\n",
-        "47|61\n",
-        "75|61\n",
-        "47|29\n",
-        "75|13\n",
-        "53|13\n",
-        "\n",
-        "75,47,61,53,29\n",
-        "97,61,53,29,13\n",
-        "75,29,13\n",
-        "75,97,47,61,53\n",
-        "61,13,29\n",
-        "97,13,75,29,47\n"
-    ];
-
-    Command::cargo_bin("day05_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("123\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
@@ -1,29 +1,7 @@
 use std::fs;
 use assert_cmd::Command;
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat![
-        "....#.....\n",
-        ".........#\n",
-        "..........\n",
-        "..#.......\n",
-        ".......#..\n",
-        "..........\n",
-        ".#..^.....\n",
-        "........#.\n",
-        "#.........\n",
-        "......#...\n"
-    ];
-
-    Command::cargo_bin("day06_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("41\n"));
-}
+aoc2024::example_tests!(6);
 
 /// Test that if we pass actual input we will compute expected output.
 #[test]
@@ -38,30 +16,6 @@ fn test_part_1_actual_input() {
         .stdout(predicates::ord::eq("4665\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat![
-        "....#.....\n",
-        ".........#\n",
-        "..........\n",
-        "..#.......\n",
-        ".......#..\n",
-        "..........\n",
-        ".#..^.....\n",
-        "........#.\n",
-        "#.........\n",
-        "......#...\n"
-    ];
-
-    Command::cargo_bin("day06_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("6\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
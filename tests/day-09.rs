@@ -1,18 +1,7 @@
 use std::fs;
 use assert_cmd::Command;
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = "2333133121414131402\n";
-
-    Command::cargo_bin("day09_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("1928\n"));
-}
+aoc2024::example_tests!(9);
 
 /// Test that if we pass actual input we will compute expected output.
 #[test]
@@ -27,19 +16,6 @@ fn test_part_1_actual_input() {
         .stdout(predicates::ord::eq("6332189866718\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = "2333133121414131402\n";
-
-    Command::cargo_bin("day09_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("2858\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
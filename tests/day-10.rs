@@ -1,27 +1,7 @@
 use std::fs;
 use assert_cmd::Command;
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat![
-        "89010123\n",
-        "78121874\n",
-        "87430965\n",
-        "96549874\n",
-        "45678903\n",
-        "32019012\n",
-        "01329801\n",
-        "10456732\n",
-    ];
-
-    Command::cargo_bin("day10_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("36\n"));
-}
+aoc2024::example_tests!(10);
 
 /// Test that if we pass actual input we will compute expected output.
 #[test]
@@ -36,28 +16,6 @@ fn test_part_1_actual_input() {
         .stdout(predicates::ord::eq("667\n"));
 }
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat![
-        "89010123\n",
-        "78121874\n",
-        "87430965\n",
-        "96549874\n",
-        "45678903\n",
-        "32019012\n",
-        "01329801\n",
-        "10456732\n",
-    ];
-
-    Command::cargo_bin("day10_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("81\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {
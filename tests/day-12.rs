@@ -1,29 +1,7 @@
 use std::fs;
 use assert_cmd::Command;
 
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_1_example_input() {
-    let input = concat![
-        "RRRRIICCFF\n",
-        "RRRRIICCCF\n",
-        "VVRRRCCFFF\n",
-        "VVRCCCJFFF\n",
-        "VVVVCJJCFE\n",
-        "VVIVCCJJEE\n",
-        "VVIIICJJEE\n",
-        "MIIIIIJJEE\n",
-        "MIIISIJEEE\n",
-        "MMMISSJEEE\n",
-    ];
-
-    Command::cargo_bin("day12_1")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("1930\n"));
-}
+aoc2024::example_tests!(12);
 
 /// Test that if we pass actual input we will compute expected output.
 #[test]
@@ -38,27 +16,6 @@ fn test_part_1_actual_input() {
         .stdout(predicates::ord::eq("1433460\n"));
 }
 
-
-/// Test that if we pass example input we will generate expected output.
-#[test]
-fn test_part_2_example_input() {
-    let input = concat![
-        "AAAAAA\n",
-        "AAABBA\n",
-        "AAABBA\n",
-        "ABBAAA\n",
-        "ABBAAA\n",
-        "AAAAAA\n",
-    ];
-
-    Command::cargo_bin("day12_2")
-        .unwrap()
-        .write_stdin(input)
-        .assert()
-        .success()
-        .stdout(predicates::ord::eq("368\n"));
-}
-
 /// Test that if we pass actual input we will compute expected output.
 #[test]
 fn test_part_2_actual_input() {